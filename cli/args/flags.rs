@@ -1915,7 +1915,10 @@ fn permission_args(app: Command) -> Command {
         .help("Allow environment access")
         .validator(|keys| {
           for key in keys.split(',') {
-            if key.is_empty() || key.contains(&['=', '\0'] as &[char]) {
+            if key.is_empty()
+              || key.contains(&['=', '\0'] as &[char])
+              || key.strip_suffix('*').unwrap_or(key).contains('*')
+            {
               return Err(format!("invalid key \"{key}\""));
             }
           }
@@ -4630,6 +4633,12 @@ mod tests {
     let r =
       flags_from_vec(svec!["deno", "run", "--allow-env=H\0ME", "script.ts"]);
     assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-env=AWS_*", "script.ts"]);
+    assert!(r.is_ok());
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-env=A*_KEY", "script.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -298,6 +298,7 @@ pub async fn run(
     shared_array_buffer_store: None,
     compiled_wasm_module_store: None,
     stdio: Default::default(),
+    resource_limits: Default::default(),
   };
   let mut worker = MainWorker::bootstrap_from_options(
     main_module.clone(),
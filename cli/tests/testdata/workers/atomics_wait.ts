@@ -0,0 +1,7 @@
+globalThis.addEventListener("message", (e) => {
+  const state = new Int32Array(e.data);
+  self.postMessage("waiting");
+  // Blocks the worker thread until the main thread notifies it.
+  Atomics.wait(state, 0, 0);
+  self.postMessage(Atomics.load(state, 1));
+});
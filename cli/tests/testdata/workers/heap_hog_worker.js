@@ -0,0 +1,6 @@
+const chunks = [];
+self.onmessage = function (_evt) {
+  while (true) {
+    chunks.push(new Array(1024 * 1024).fill("x"));
+  }
+};
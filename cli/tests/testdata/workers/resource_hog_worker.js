@@ -0,0 +1,7 @@
+self.onmessage = function (_evt) {
+  const files = [];
+  for (let i = 0; i < 16; i++) {
+    files.push(Deno.openSync(new URL(import.meta.url)));
+  }
+  postMessage(files.length);
+};
@@ -0,0 +1,11 @@
+self.onmessage = function (e) {
+  const { readable } = e.data;
+  const upper = readable.pipeThrough(
+    new TransformStream({
+      transform(chunk, controller) {
+        controller.enqueue(chunk.toUpperCase());
+      },
+    }),
+  );
+  postMessage({ readable: upper }, [upper]);
+};
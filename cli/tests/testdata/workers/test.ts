@@ -747,6 +747,30 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "worker Atomics.wait on SharedArrayBuffer",
+  fn: async function () {
+    const promise = deferred();
+    const w = new Worker(
+      import.meta.resolve("./atomics_wait.ts"),
+      { type: "module" },
+    );
+    const sab = new SharedArrayBuffer(8);
+    const state = new Int32Array(sab);
+    w.onmessage = () => {
+      w.onmessage = (e) => {
+        promise.resolve(e.data);
+      };
+      Atomics.store(state, 1, 42);
+      Atomics.store(state, 0, 1);
+      Atomics.notify(state, 0);
+    };
+    w.postMessage(sab);
+    assertEquals(await promise, 42);
+    w.terminate();
+  },
+});
+
 Deno.test({
   name: "Send MessagePorts from / to workers",
   fn: async function () {
@@ -812,3 +836,128 @@ Deno.test({
     w.terminate();
   },
 });
+
+Deno.test({
+  name: "worker resource limits wall time",
+  fn: async function () {
+    const worker = new Worker(
+      import.meta.resolve("./busy_worker.js"),
+      { type: "module", deno: { resourceLimits: { maxWallTimeMs: 200 } } },
+    );
+    const promise = deferred<ErrorEvent>();
+    worker.onerror = (e) => {
+      e.preventDefault();
+      promise.resolve(e);
+    };
+    worker.postMessage(null);
+    const event = await promise;
+    assertMatch(event.message, /exceeded maxWallTimeMs \(200 ms\)/);
+    worker.terminate();
+  },
+});
+
+Deno.test({
+  name: "worker resource limits cpu time",
+  ignore: Deno.build.os !== "linux",
+  fn: async function () {
+    const worker = new Worker(
+      import.meta.resolve("./busy_worker.js"),
+      { type: "module", deno: { resourceLimits: { maxCpuTimeMs: 200 } } },
+    );
+    const promise = deferred<ErrorEvent>();
+    worker.onerror = (e) => {
+      e.preventDefault();
+      promise.resolve(e);
+    };
+    worker.postMessage(null);
+    const event = await promise;
+    assertMatch(event.message, /exceeded maxCpuTimeMs \(200 ms\)/);
+    worker.terminate();
+  },
+});
+
+Deno.test({
+  name: "worker resource limits heap size",
+  fn: async function () {
+    const worker = new Worker(
+      import.meta.resolve("./heap_hog_worker.js"),
+      { type: "module", deno: { resourceLimits: { maxHeapSizeMb: 32 } } },
+    );
+    const promise = deferred<ErrorEvent>();
+    worker.onerror = (e) => {
+      e.preventDefault();
+      promise.resolve(e);
+    };
+    worker.postMessage(null);
+    const event = await promise;
+    assertMatch(event.message, /exceeded maxHeapSizeMb \(32 MB\)/);
+    worker.terminate();
+  },
+});
+
+Deno.test({
+  name: "worker resource limits open resources",
+  fn: async function () {
+    const worker = new Worker(
+      import.meta.resolve("./resource_hog_worker.js"),
+      {
+        type: "module",
+        deno: {
+          permissions: { read: true },
+          resourceLimits: { maxResources: 8 },
+        },
+      },
+    );
+    const promise = deferred<ErrorEvent>();
+    worker.onerror = (e) => {
+      e.preventDefault();
+      promise.resolve(e);
+    };
+    worker.postMessage(null);
+    const event = await promise;
+    assertMatch(event.message, /exceeded maxResources \(8\)/);
+    worker.terminate();
+  },
+});
+
+Deno.test({
+  name: "worker resource limits validation",
+  fn: function () {
+    assertThrows(
+      () =>
+        new Worker(
+          import.meta.resolve("./test_worker.js"),
+          { type: "module", deno: { resourceLimits: { maxHeapSizeMb: 0 } } },
+        ),
+      TypeError,
+      "maxHeapSizeMb must be greater than 0",
+    );
+  },
+});
+
+Deno.test({
+  name: "worker transfers streams both ways",
+  fn: async function () {
+    const worker = new Worker(
+      import.meta.resolve("./stream_worker.js"),
+      { type: "module" },
+    );
+    const original = new ReadableStream({
+      start(controller) {
+        controller.enqueue("hello");
+        controller.enqueue("world");
+        controller.close();
+      },
+    });
+    const promise = deferred<ReadableStream<string>>();
+    worker.onmessage = (e) => promise.resolve(e.data.readable);
+    worker.postMessage({ readable: original }, [original]);
+    const readable = await promise;
+    const chunks = [];
+    for await (const chunk of readable) {
+      chunks.push(chunk);
+    }
+    assertEquals(chunks, ["HELLO", "WORLD"]);
+    worker.terminate();
+  },
+});
@@ -6,6 +6,7 @@ import {
   assertRejects,
   assertStringIncludes,
   assertThrows,
+  deferred,
 } from "./test_util.ts";
 
 Deno.test(
@@ -865,3 +866,289 @@ Deno.test(
     }
   },
 );
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandPty() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "console.log(Deno.isatty(Deno.stdout.rid), Deno.consoleSize().columns)",
+      ],
+      pty: { rows: 30, columns: 100 },
+    }).spawn();
+    const { success, stdout } = await child.output();
+    assert(success);
+    assertStringIncludes(new TextDecoder().decode(stdout), "true 100");
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandPtyResize() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        `Deno.addSignalListener("SIGWINCH", () => {
+          console.log("columns", Deno.consoleSize().columns);
+          Deno.exit(0);
+        });
+        console.log("ready");`,
+      ],
+      pty: true,
+    }).spawn();
+    const reader = child.stdout.pipeThrough(new TextDecoderStream())
+      .getReader();
+    let output = "";
+    while (!output.includes("ready")) {
+      output += (await reader.read()).value;
+    }
+    child.resizePty({ rows: 24, columns: 120 });
+    while (!output.includes("columns 120")) {
+      const { value, done } = await reader.read();
+      if (done) break;
+      output += value;
+    }
+    assertStringIncludes(output, "columns 120");
+    assert((await child.status).success);
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  function commandPtyWithStdioFails() {
+    assertThrows(
+      () => {
+        new Deno.Command(Deno.execPath(), {
+          args: ["eval", "console.log('hello world')"],
+          pty: true,
+          stdout: "null",
+        }).spawn();
+      },
+      TypeError,
+      "stdin, stdout and stderr can not be set when spawning with a pty",
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  function commandSyncPtyFails() {
+    assertThrows(
+      () => {
+        new Deno.Command(Deno.execPath(), {
+          args: ["eval", "console.log('hello world')"],
+          pty: true,
+        }).outputSync();
+      },
+      TypeError,
+      "pty is not supported by Deno.Command().outputSync()",
+    );
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandIpc() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "--unstable",
+        `Deno.onmessage = (e) => {
+          Deno.postMessage({ echo: e.data, map: new Map([[1, 2]]) });
+        };`,
+      ],
+      stdout: "null",
+      ipc: true,
+    }).spawn();
+    const reply = deferred<MessageEvent>();
+    child.onmessage = (e) => reply.resolve(e);
+    child.postMessage({ hello: ["world"] });
+    const { data } = await reply;
+    assertEquals(data.echo, { hello: ["world"] });
+    assertEquals(data.map.get(1), 2);
+    child.disconnect();
+    assert((await child.status).success);
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandIpcPostMessageFailures() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: ["eval", "--unstable", "Deno.onmessage = () => Deno.exit(0);"],
+      stdout: "null",
+      ipc: true,
+    }).spawn();
+    assertThrows(
+      () => child.postMessage(new Uint8Array(64 * 1024 * 1024 + 1)),
+      RangeError,
+      "exceeds the 64 MiB limit",
+    );
+    await child.postMessage("exit");
+    await child.status;
+    await assertRejects(async () => {
+      // The pipe may still take a message or two before the write fails.
+      for (let i = 0; i < 10; i++) {
+        await child.postMessage("gone");
+      }
+    });
+  },
+);
+
+Deno.test(function parentIpcChannelIsLazy() {
+  assert(!Object.keys(Deno).includes("onmessage"));
+  assertEquals(Deno.onmessage, null);
+  assertThrows(
+    () => {
+      Deno.onmessage = () => {};
+    },
+    TypeError,
+    "Process was not spawned with an IPC channel",
+  );
+});
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  function commandSyncIpcFails() {
+    assertThrows(
+      () => {
+        new Deno.Command(Deno.execPath(), {
+          args: ["eval", "console.log('hello world')"],
+          ipc: true,
+        }).outputSync();
+      },
+      TypeError,
+      "ipc is not supported by Deno.Command().outputSync()",
+    );
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandKillProcessGroup() {
+    const child = new Deno.Command("sh", {
+      args: ["-c", "sleep 60 & echo started; wait"],
+      processGroup: true,
+    }).spawn();
+    const reader = child.stdout.pipeThrough(new TextDecoderStream())
+      .getReader();
+    assertStringIncludes((await reader.read()).value!, "started");
+    child.kill("SIGKILL", { group: true });
+    // The grandchild holds on to stdout, so it only ends once it was killed
+    // as well.
+    while (!(await reader.read()).done);
+    const status = await child.status;
+    assertEquals(status.signal, "SIGKILL");
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandKillProcessGroupAfterExitFails() {
+    const child = new Deno.Command("true", {
+      processGroup: true,
+      stdout: "null",
+      stderr: "null",
+    }).spawn();
+    await child.status;
+    assertThrows(
+      () => child.kill("SIGKILL", { group: true }),
+      TypeError,
+      "Child process has already terminated.",
+    );
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandDetached() {
+    const child = new Deno.Command("sh", {
+      args: ["-c", "ps -o sid= -p $$; ps -o sid= -p $PPID"],
+      detached: true,
+      stdout: "piped",
+    }).spawn();
+    const { success, stdout } = await child.output();
+    assert(success);
+    const [childSid, parentSid] = new TextDecoder().decode(stdout).trim()
+      .split(/\s+/);
+    // The child leads a session of its own.
+    assertEquals(childSid, String(child.pid));
+    assert(childSid !== parentSid);
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os !== "windows",
+  },
+  async function commandKillGroupOnWindowsFails() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: ["eval", "setTimeout(() => {}, 10000)"],
+      processGroup: true,
+      stdout: "null",
+      stderr: "null",
+    }).spawn();
+    assertThrows(
+      () => child.kill("SIGTERM", { group: true }),
+      Deno.errors.NotSupported,
+    );
+    child.kill();
+    await child.status;
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function commandKillGroupWithoutProcessGroupFails() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: ["eval", "setTimeout(() => {}, 10000)"],
+      stdout: "null",
+      stderr: "null",
+    }).spawn();
+    assertThrows(
+      () => child.kill("SIGTERM", { group: true }),
+      TypeError,
+      "Child process does not lead a process group",
+    );
+    child.kill();
+    await child.status;
+  },
+);
+
+Deno.test(
+  {
+    permissions: { run: true, read: true },
+    ignore: Deno.build.os === "windows",
+  },
+  async function commandResourceLimits() {
+    const { success, stdout } = await new Deno.Command("sh", {
+      args: ["-c", "ulimit -n; ulimit -t"],
+      limits: { openFiles: 64, cpu: 30 },
+    }).output();
+    assert(success);
+    assertEquals(new TextDecoder().decode(stdout), "64\n30\n");
+  },
+);
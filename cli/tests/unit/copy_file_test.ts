@@ -1,5 +1,10 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import { assertEquals, assertRejects, assertThrows } from "./test_util.ts";
+import {
+  assert,
+  assertEquals,
+  assertRejects,
+  assertThrows,
+} from "./test_util.ts";
 
 function readFileString(filename: string | URL): string {
   const dataRead = Deno.readFileSync(filename);
@@ -236,3 +241,21 @@ Deno.test(
     copyFileSyncMode("Hello world!".repeat(128 * 1024));
   },
 );
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function copyFilePreserve() {
+    const tempDir = Deno.makeTempDirSync();
+    const fromFilename = tempDir + "/from.txt";
+    const toFilename = tempDir + "/to.txt";
+    writeFileString(fromFilename, "Hello world!");
+    const mtime = new Date(2000, 0, 1);
+    Deno.utimeSync(fromFilename, mtime, mtime);
+    await Deno.copyFile(fromFilename, toFilename, { preserve: true });
+    assertSameContent(fromFilename, toFilename);
+    assertEquals(Deno.statSync(toFilename).mtime, mtime);
+    Deno.copyFileSync(fromFilename, toFilename);
+    assert(Deno.statSync(toFilename).mtime!.getTime() > mtime.getTime());
+    Deno.removeSync(tempDir, { recursive: true });
+  },
+);
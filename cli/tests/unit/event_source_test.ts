@@ -0,0 +1,166 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import {
+  assertEquals,
+  assertThrows,
+  Deferred,
+  deferred,
+} from "./test_util.ts";
+
+function serveEvents(
+  handler: (request: Request, count: number) => Response,
+): { listening: Deferred<void>; ac: AbortController; server: Promise<void> } {
+  const listening = deferred<void>();
+  const ac = new AbortController();
+  let count = 0;
+  const server = Deno.serve({
+    handler: (request) => handler(request, count++),
+    port: 4502,
+    signal: ac.signal,
+    onListen: () => listening.resolve(),
+    onError: (err) => {
+      console.error(err);
+      ac.abort();
+      return new Response("Internal server error", { status: 500 });
+    },
+  });
+  return { listening, ac, server };
+}
+
+function eventStream(body: string): Response {
+  return new Response(body, {
+    headers: { "content-type": "text/event-stream" },
+  });
+}
+
+Deno.test(function eventSourceInvalidUrl() {
+  assertThrows(
+    () => new EventSource("http://[invalid"),
+    DOMException,
+  );
+});
+
+Deno.test(
+  { permissions: { net: true } },
+  async function eventSourceParsesEvents() {
+    const { listening, ac, server } = serveEvents((request) => {
+      assertEquals(request.headers.get("accept"), "text/event-stream");
+      assertEquals(request.headers.get("authorization"), "Bearer foo");
+      return eventStream(
+        ": comment\r\n" +
+          "data: first\r\n\r\n" +
+          "event: update\ndata:line 1\ndata: line 2\nid: 42\n\n" +
+          "data\n\n" +
+          "data: trailing, never dispatched",
+      );
+    });
+    await listening;
+
+    const source = new EventSource("http://localhost:4502/", {
+      headers: { authorization: "Bearer foo" },
+    });
+    const opened = deferred<void>();
+    source.onopen = () => opened.resolve();
+    const messages: MessageEvent[] = [];
+    const done = deferred<void>();
+    source.onmessage = (e) => {
+      messages.push(e);
+      if (messages.length === 2) done.resolve();
+    };
+    source.addEventListener("update", (e) => messages.push(e as MessageEvent));
+
+    await opened;
+    assertEquals(source.readyState, EventSource.OPEN);
+    await done;
+    source.close();
+    assertEquals(source.readyState, EventSource.CLOSED);
+
+    assertEquals(
+      messages.map((e) => [e.type, e.data, e.lastEventId]),
+      [
+        ["message", "first", ""],
+        ["update", "line 1\nline 2", "42"],
+        ["message", "", "42"],
+      ],
+    );
+    assertEquals(messages[0].origin, "http://localhost:4502");
+
+    ac.abort();
+    await server;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function eventSourceReconnectsWithLastEventId() {
+    const lastEventIds: (string | null)[] = [];
+    const { listening, ac, server } = serveEvents((request, count) => {
+      lastEventIds.push(request.headers.get("last-event-id"));
+      return eventStream(`retry: 10\nid: ${count}\ndata: ${count}\n\n`);
+    });
+    await listening;
+
+    const source = new EventSource("http://localhost:4502/");
+    const errors: number[] = [];
+    source.onerror = () => errors.push(source.readyState);
+    const done = deferred<void>();
+    source.onmessage = (e) => {
+      if (e.data === "2") done.resolve();
+    };
+    await done;
+    source.close();
+
+    assertEquals(lastEventIds, [null, "0", "1"]);
+    assertEquals(errors, [EventSource.CONNECTING, EventSource.CONNECTING]);
+
+    ac.abort();
+    await server;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function eventSourceFailsOnInvalidContentType() {
+    const { listening, ac, server } = serveEvents(() =>
+      new Response("data: foo\n\n", {
+        headers: { "content-type": "text/plain" },
+      })
+    );
+    await listening;
+
+    const source = new EventSource("http://localhost:4502/");
+    const failed = deferred<void>();
+    source.onerror = () => failed.resolve();
+    source.onmessage = () => failed.reject(new Error("unexpected message"));
+    await failed;
+    assertEquals(source.readyState, EventSource.CLOSED);
+
+    ac.abort();
+    await server;
+  },
+);
+
+Deno.test(function eventSourceInvalidClient() {
+  assertThrows(
+    () =>
+      new EventSource("http://localhost:4502/", {
+        // @ts-expect-error: not a Deno.HttpClient
+        client: {},
+      }),
+    TypeError,
+  );
+});
+
+Deno.test(
+  { permissions: { net: false } },
+  async function eventSourceFailsOnPermissionDenied() {
+    const source = new EventSource("http://localhost:4502/");
+    const errors: number[] = [];
+    const failed = deferred<void>();
+    source.onerror = () => {
+      errors.push(source.readyState);
+      failed.resolve();
+    };
+    await failed;
+    assertEquals(errors, [EventSource.CLOSED]);
+  },
+);
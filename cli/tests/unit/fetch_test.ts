@@ -3,6 +3,7 @@ import {
   assert,
   assertEquals,
   assertRejects,
+  assertThrows,
   deferred,
   delay,
   fail,
@@ -1151,6 +1152,107 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchCustomClientResolve(): Promise<void> {
+    const client = Deno.createHttpClient({
+      resolve: { "api.local": "127.0.0.1" },
+    });
+    const response = await fetch("http://api.local:4545/assets/fixture.json", {
+      client,
+    });
+    const json = await response.json();
+    assertEquals(json.name, "deno");
+    client.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: ["api.local"] } },
+  async function fetchCustomClientResolvePermissions(): Promise<void> {
+    const client = Deno.createHttpClient({
+      resolve: { "api.local": "127.0.0.1" },
+    });
+    await assertRejects(
+      () => fetch("http://api.local:4545/assets/fixture.json", { client }),
+      Deno.errors.PermissionDenied,
+    );
+    client.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchCustomClientReadTimeout(): Promise<void> {
+    const listener = Deno.listen({ hostname: "127.0.0.1", port: 4520 });
+    const server = (async () => {
+      const conn = await listener.accept();
+      listener.close();
+      // Read the request, but never respond.
+      const buf = new Uint8Array(1024);
+      while (await conn.read(buf) !== null);
+      conn.close();
+    })();
+
+    const client = Deno.createHttpClient({ readTimeout: 100 });
+    await assertRejects(
+      () => fetch("http://127.0.0.1:4520/", { client }),
+      TypeError,
+      "Timed out waiting for the response",
+    );
+    client.close();
+    await server;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchCustomClientRetry(): Promise<void> {
+    const listener = Deno.listen({ hostname: "127.0.0.1", port: 4521 });
+    const methods: string[] = [];
+    const server = (async () => {
+      for await (const conn of listener) {
+        (async () => {
+          for await (const { request, respondWith } of Deno.serveHttp(conn)) {
+            methods.push(request.method);
+            const status = methods.length % 2 === 1 ? 503 : 200;
+            await respondWith(new Response("", { status }));
+          }
+        })();
+      }
+    })();
+
+    const client = Deno.createHttpClient({
+      retry: { retries: 2, delay: 10, statusCodes: [503] },
+    });
+    const res = await fetch("http://127.0.0.1:4521/", { client });
+    assertEquals(res.status, 200);
+    await res.text();
+
+    // Requests that aren't idempotent are never retried.
+    const post = await fetch("http://127.0.0.1:4521/", {
+      client,
+      method: "POST",
+      body: "foo",
+    });
+    assertEquals(post.status, 503);
+    await post.text();
+    assertEquals(methods, ["GET", "GET", "POST"]);
+
+    client.close();
+    listener.close();
+    await server;
+  },
+);
+
+Deno.test(function createHttpClientRequiresAProtocol() {
+  assertThrows(
+    () => Deno.createHttpClient({ http1: false, http2: false }),
+    TypeError,
+    "Either `http1` or `http2` needs to be true",
+  );
+});
+
 Deno.test(
   {
     permissions: { net: true },
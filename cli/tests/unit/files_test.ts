@@ -824,3 +824,66 @@ Deno.test(
     assertEquals(res, "hello \uFFFD");
   },
 );
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function fsFileReadAtWriteAt() {
+    const path = await Deno.makeTempFile();
+    await Deno.writeTextFile(path, "hello world");
+    const file = await Deno.open(path, { read: true, write: true });
+    const encoder = new TextEncoder();
+    const decoder = new TextDecoder();
+
+    const buf = new Uint8Array(5);
+    const [a, b] = await Promise.all([
+      file.readAt(buf, 6),
+      file.readAt(new Uint8Array(5), 0),
+    ]);
+    assertEquals(a, 5);
+    assertEquals(b, 5);
+    assertEquals(decoder.decode(buf), "world");
+    assertEquals(await file.readAt(buf, 11), null);
+
+    assertEquals(await file.writeAt(encoder.encode("WORLD"), 6), 5);
+    assertEquals(file.writeAtSync(encoder.encode("HELLO"), 0), 5);
+    assertEquals(file.readAtSync(buf, 0), 5);
+    assertEquals(decoder.decode(buf), "HELLO");
+
+    if (Deno.build.os !== "windows") {
+      // The cursor was not moved.
+      assertEquals(file.seekSync(0, Deno.SeekMode.Current), 0);
+    }
+    file.close();
+    assertEquals(await Deno.readTextFile(path), "HELLO WORLD");
+    await Deno.remove(path);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function fsFileReadvWritev() {
+    const path = await Deno.makeTempFile();
+    const file = await Deno.open(path, { read: true, write: true });
+    const encoder = new TextEncoder();
+    const decoder = new TextDecoder();
+
+    const written = await file.writev(
+      [encoder.encode("hello"), encoder.encode(" "), encoder.encode("world")],
+      2,
+    );
+    assertEquals(written, 11);
+    assertEquals(
+      file.writevSync([encoder.encode("--")], 0),
+      2,
+    );
+
+    const head = new Uint8Array(7);
+    const tail = new Uint8Array(10);
+    assertEquals(await file.readv([head, tail], 0), 13);
+    assertEquals(decoder.decode(head), "--hello");
+    assertEquals(decoder.decode(tail.subarray(0, 6)), " world");
+    assertEquals(file.readvSync([head], 13), null);
+    file.close();
+    await Deno.remove(path);
+  },
+);
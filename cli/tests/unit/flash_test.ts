@@ -611,6 +611,40 @@ Deno.test({ permissions: { net: true } }, async function httpServerWebSocket() {
   await server;
 });
 
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerWebSocketStream() {
+    const ac = new AbortController();
+    const listeningPromise = deferred();
+    const server = Deno.serve({
+      handler: (request) => {
+        const { response, socket } = Deno.upgradeWebSocketStream(request);
+        socket.connection.then(({ readable, writable }) =>
+          readable.pipeTo(writable)
+        );
+        return response;
+      },
+      port: 4501,
+      signal: ac.signal,
+      onListen: onListen(listeningPromise),
+      onError: createOnErrorCb(ac),
+    });
+
+    await listeningPromise;
+    const ws = new WebSocketStream("ws://localhost:4501");
+    const { readable, writable } = await ws.connection;
+    const writer = writable.getWriter();
+    const reader = readable.getReader();
+    await writer.write("foo");
+    assertEquals((await reader.read()).value, "foo");
+    ws.close({ code: 1000 });
+    await ws.closed;
+
+    ac.abort();
+    await server;
+  },
+);
+
 Deno.test(
   { permissions: { net: true } },
   async function httpVeryLargeRequest() {
@@ -1848,6 +1882,104 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function httpServerWithMutualTls() {
+    const ac = new AbortController();
+    const listeningPromise = deferred();
+    const hostname = "127.0.0.1";
+    const port = 4512;
+    const cert = Deno.readTextFileSync("cli/tests/testdata/tls/localhost.crt");
+    const key = Deno.readTextFileSync("cli/tests/testdata/tls/localhost.key");
+    const caCert = Deno.readTextFileSync("cli/tests/testdata/tls/RootCA.pem");
+
+    const server = Deno.serve({
+      handler: (request) => {
+        const certs = Deno.peerCertificates(request);
+        return new Response(certs ? certs[0].subjectAltNames[0] : "anonymous");
+      },
+      hostname,
+      port,
+      signal: ac.signal,
+      onListen: onListen(listeningPromise),
+      onError: createOnErrorCb(ac),
+      cert,
+      key,
+      caCerts: [caCert],
+      requestClientCert: true,
+    });
+
+    await listeningPromise;
+    const client = Deno.createHttpClient({
+      caCerts: [caCert],
+      certChain: cert,
+      privateKey: key,
+    });
+    const resp = await fetch(`https://localhost:${port}/`, {
+      client,
+      headers: { "connection": "close" },
+    });
+    assertEquals(await resp.text(), "DNS:localhost");
+
+    const anonymousClient = Deno.createHttpClient({ caCerts: [caCert] });
+    const anonymousResp = await fetch(`https://localhost:${port}/`, {
+      client: anonymousClient,
+      headers: { "connection": "close" },
+    });
+    assertEquals(await anonymousResp.text(), "anonymous");
+
+    client.close();
+    anonymousClient.close();
+    ac.abort();
+    await server;
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function httpServerUpdateCertificates() {
+    const ac = new AbortController();
+    const listeningPromise = deferred<Deno.ServeListenInfo>();
+    const port = 4513;
+    const cert = Deno.readTextFileSync("cli/tests/testdata/tls/localhost.crt");
+    const key = Deno.readTextFileSync("cli/tests/testdata/tls/localhost.key");
+    const caCerts = [
+      Deno.readTextFileSync("cli/tests/testdata/tls/RootCA.pem"),
+    ];
+
+    const server = Deno.serve({
+      handler: () => new Response("Hello World"),
+      hostname: "localhost",
+      port,
+      signal: ac.signal,
+      onListen: (info) => listeningPromise.resolve(info),
+      onError: createOnErrorCb(ac),
+      cert,
+      key,
+    });
+
+    const { updateCertificates } = await listeningPromise;
+    assert(updateCertificates);
+    updateCertificates({
+      cert: Deno.readTextFileSync("cli/tests/testdata/tls/localhost_alt.crt"),
+      key: Deno.readTextFileSync("cli/tests/testdata/tls/localhost_alt.key"),
+    });
+
+    const conn = await Deno.connectTls({
+      hostname: "localhost",
+      port,
+      caCerts,
+    });
+    const { peerCertificates } = await conn.handshake();
+    assert(peerCertificates);
+    assert(peerCertificates[0].subject.includes("CN=localhost.alt"));
+    conn.close();
+
+    ac.abort();
+    await server;
+  },
+);
+
 Deno.test(
   { permissions: { net: true, write: true, read: true } },
   async function httpServerRequestCLTE() {
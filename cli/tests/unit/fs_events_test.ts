@@ -107,3 +107,56 @@ Deno.test(
     assertEquals(events, []);
   },
 );
+
+Deno.test(
+  {
+    permissions: { read: true, write: true },
+    ignore: Deno.build.os !== "linux",
+  },
+  async function watchFsRenameDebounced() {
+    const testDir = await makeTempDir();
+    const oldPath = testDir + "/old.txt";
+    const newPath = testDir + "/new.txt";
+    Deno.writeFileSync(oldPath, new Uint8Array([0, 1, 2]));
+    await delay(100);
+    const iter = Deno.watchFs(testDir, { debounce: 100 });
+
+    Deno.renameSync(oldPath, newPath);
+
+    for await (const event of iter) {
+      assertEquals(event.kind, "rename");
+      assertEquals(event.paths, [oldPath, newPath]);
+      break;
+    }
+    iter.close();
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function watchFsExclude() {
+    const testDir = await makeTempDir();
+    const iter = Deno.watchFs(testDir, { exclude: ["*.log"] });
+    const eventsPromise = getTwoEvents(iter);
+
+    Deno.writeFileSync(testDir + "/ignored.log", new Uint8Array([0, 1, 2]));
+    Deno.writeFileSync(testDir + "/file1.txt", new Uint8Array([0, 1, 2]));
+    Deno.writeFileSync(testDir + "/file2.txt", new Uint8Array([0, 1, 2]));
+
+    const events = await eventsPromise;
+    iter.close();
+    for (const event of events) {
+      assert(event.paths.every((path) => !path.endsWith(".log")));
+    }
+  },
+);
+
+Deno.test({ permissions: { read: true } }, function watchFsInvalidGlob() {
+  assertThrows(
+    () => {
+      Deno.watchFs(".", { include: ["[invalid"] });
+    },
+    TypeError,
+    'Invalid glob "[invalid"',
+  );
+});
@@ -795,6 +795,157 @@ Deno.test({ permissions: { net: true } }, async function httpServerWebSocket() {
   await promise;
 });
 
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerWebSocketStream() {
+    const promise = (async () => {
+      const listener = Deno.listen({ port: 4501 });
+      const conn = await listener.accept();
+      listener.close();
+      const httpConn = Deno.serveHttp(conn);
+      const reqEvent = await httpConn.nextRequest();
+      assert(reqEvent);
+      const { request, respondWith } = reqEvent;
+      const { response, socket } = Deno.upgradeWebSocketStream(request, {
+        readableHighWaterMark: 4,
+      });
+      await respondWith(response);
+      const { readable, writable } = await socket.connection;
+      await socket.ping();
+      await readable.pipeTo(writable);
+    })();
+
+    const ws = new WebSocketStream("ws://localhost:4501");
+    const { readable, writable } = await ws.connection;
+    const writer = writable.getWriter();
+    const reader = readable.getReader();
+    await writer.write("foo");
+    assertEquals((await reader.read()).value, "foo");
+    await ws.ping();
+    ws.close({ code: 1000 });
+    await ws.closed;
+    await promise;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerWebSocketStreamBackpressure() {
+    const serverReadSome = deferred();
+    const clientChecked = deferred();
+    const promise = (async () => {
+      const listener = Deno.listen({ port: 4501 });
+      const conn = await listener.accept();
+      listener.close();
+      const httpConn = Deno.serveHttp(conn);
+      const reqEvent = await httpConn.nextRequest();
+      assert(reqEvent);
+      const { request, respondWith } = reqEvent;
+      const { response, socket } = Deno.upgradeWebSocketStream(request, {
+        readableHighWaterMark: 1,
+        writableHighWaterMark: 3,
+      });
+      await respondWith(response);
+      const { readable, writable } = await socket.connection;
+      const writer = writable.getWriter();
+      assertEquals(writer.desiredSize, 3);
+      writer.releaseLock();
+      const reader = readable.getReader();
+      for (let i = 0; i < 3; i++) {
+        assertEquals((await reader.read()).value, `${i}`);
+      }
+      // At most one more message was read from the connection, so the
+      // client's ping behind the other two hasn't been answered yet.
+      serverReadSome.resolve();
+      await clientChecked;
+      while (!(await reader.read()).done);
+    })();
+
+    const ws = new WebSocketStream("ws://localhost:4501");
+    const { writable } = await ws.connection;
+    const writer = writable.getWriter();
+    for (let i = 0; i < 5; i++) {
+      await writer.write(`${i}`);
+    }
+    let pong = false;
+    const ping = ws.ping().then(() => pong = true);
+    await serverReadSome;
+    assert(!pong);
+    clientChecked.resolve();
+    await ping;
+    ws.close({ code: 1000 });
+    await ws.closed;
+    await promise;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerWebSocketStreamIdleTimeoutPing() {
+    const promise = (async () => {
+      const listener = Deno.listen({ port: 4501 });
+      const conn = await listener.accept();
+      listener.close();
+      const httpConn = Deno.serveHttp(conn);
+      const reqEvent = await httpConn.nextRequest();
+      assert(reqEvent);
+      const { request, respondWith } = reqEvent;
+      const { response, socket } = Deno.upgradeWebSocketStream(request, {
+        idleTimeout: 1,
+      });
+      await respondWith(response);
+      const { readable, writable } = await socket.connection;
+      await readable.pipeTo(writable);
+    })();
+
+    const ws = new WebSocketStream("ws://localhost:4501");
+    const { readable, writable } = await ws.connection;
+    // The client answers the server's pings, which keeps the connection open.
+    await delay(2000);
+    const writer = writable.getWriter();
+    const reader = readable.getReader();
+    await writer.write("foo");
+    assertEquals((await reader.read()).value, "foo");
+    ws.close({ code: 1000 });
+    await ws.closed;
+    await promise;
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function httpServerWebSocketStreamIdleTimeoutClose() {
+    const promise = (async () => {
+      const listener = Deno.listen({ port: 4501 });
+      const conn = await listener.accept();
+      listener.close();
+      const httpConn = Deno.serveHttp(conn);
+      const reqEvent = await httpConn.nextRequest();
+      assert(reqEvent);
+      const { request, respondWith } = reqEvent;
+      const { response, socket } = Deno.upgradeWebSocketStream(request, {
+        idleTimeout: 1,
+      });
+      await respondWith(response);
+      await socket.connection;
+      assertEquals(await socket.closed, {
+        code: 1001,
+        reason: "No response from ping frame.",
+      });
+    })();
+
+    // A client that never answers pings.
+    const conn = await Deno.connect({ port: 4501 });
+    const body = "GET / HTTP/1.1\r\nHost: localhost:4501\r\n" +
+      "Upgrade: websocket\r\nConnection: Upgrade\r\n" +
+      "Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n" +
+      "Sec-WebSocket-Version: 13\r\n\r\n";
+    await conn.write(new TextEncoder().encode(body));
+    await promise;
+    conn.close();
+  },
+);
+
 Deno.test(function httpUpgradeWebSocket() {
   const request = new Request("https://deno.land/", {
     headers: {
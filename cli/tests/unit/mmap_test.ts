@@ -0,0 +1,87 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { assertEquals, assertThrows } from "./test_util.ts";
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  function mmapReadOnly() {
+    const path = Deno.makeTempFileSync();
+    Deno.writeTextFileSync(path, "hello world");
+    const map = Deno.mmap(path, { offset: 6 });
+    assertEquals(new TextDecoder().decode(map.buffer), "world");
+    // Writes to a read only mapping stay private.
+    new Uint8Array(map.buffer)[0] = 87;
+    assertEquals(Deno.readTextFileSync(path), "hello world");
+    map.unmap();
+    assertEquals(map.buffer.byteLength, 0);
+    Deno.removeSync(path);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function mmapWritable() {
+    const path = Deno.makeTempFileSync();
+    Deno.writeTextFileSync(path, "hello world");
+    const file = Deno.openSync(path, { read: true, write: true });
+    const map = Deno.mmap(file, { length: 5, writable: true });
+    new Uint8Array(map.buffer).set(new TextEncoder().encode("HELLO"));
+    await map.flush();
+    assertEquals(Deno.readTextFileSync(path), "HELLO world");
+    map.unmap();
+    file.close();
+    Deno.removeSync(path);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  function mmapPastEndOfFile() {
+    const path = Deno.makeTempFileSync();
+    Deno.writeTextFileSync(path, "hello");
+    assertThrows(
+      () => Deno.mmap(path, { offset: 2, length: 4 }),
+      RangeError,
+      "Cannot map 4 bytes at offset 2, the file is 5 bytes",
+    );
+    Deno.removeSync(path);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  function mmapEmptyFile() {
+    const path = Deno.makeTempFileSync();
+    const map = Deno.mmap(path, { writable: true });
+    assertEquals(map.buffer.byteLength, 0);
+    map.flushSync();
+    map.unmap();
+    Deno.removeSync(path);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  function mmapFlushAfterUnmap() {
+    const path = Deno.makeTempFileSync();
+    Deno.writeTextFileSync(path, "hello");
+    const map = Deno.mmap(path, { writable: true });
+    map.unmap();
+    assertThrows(() => map.flushSync(), TypeError, "unmapped");
+    Deno.removeSync(path);
+  },
+);
+
+Deno.test({ permissions: { read: false } }, function mmapPerm() {
+  assertThrows(() => {
+    Deno.mmap("README.md");
+  }, Deno.errors.PermissionDenied);
+});
+
+Deno.test(
+  { permissions: { read: true, write: false } },
+  function mmapWritablePerm() {
+    assertThrows(() => {
+      Deno.mmap("README.md", { writable: true });
+    }, Deno.errors.PermissionDenied);
+  },
+);
@@ -357,6 +357,87 @@ Deno.test(
   },
 );
 
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { read: true, write: true },
+  },
+  async function netUnixPeerCredentials() {
+    const filePath = tmpUnixSocketPath();
+    const listener = Deno.listen({ path: filePath, transport: "unix" });
+    const accepted = listener.accept();
+    const conn = await Deno.connect({ path: filePath, transport: "unix" });
+    const server = await accepted as Deno.UnixConn;
+
+    const creds = server.peerCredentials();
+    assertEquals(creds.uid, Deno.uid());
+    assertEquals(creds.gid, Deno.gid());
+    if (Deno.build.os === "linux") {
+      assertEquals(creds.pid, Deno.pid);
+    }
+
+    server.close();
+    conn.close();
+    listener.close();
+  },
+);
+
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { read: true, write: true, net: true },
+  },
+  async function netUnixSendRecvFds() {
+    const filePath = tmpUnixSocketPath();
+    const listener = Deno.listen({ path: filePath, transport: "unix" });
+    const accepted = listener.accept();
+    const conn = await Deno.connect({ path: filePath, transport: "unix" });
+    const server = await accepted as Deno.UnixConn;
+
+    const tempFile = await Deno.makeTempFile();
+    const file = await Deno.open(tempFile, { read: true, write: true });
+    await file.write(new TextEncoder().encode("hello"));
+    const tcpListener = Deno.listen({ port: 3536 });
+
+    const nwritten = await conn.sendFds(
+      [file, tcpListener],
+      new Uint8Array([42]),
+    );
+    assertEquals(nwritten, 1);
+
+    const buf = new Uint8Array(1);
+    const { nread, resources } = await server.recvFds(buf);
+    assertEquals(nread, 1);
+    assertEquals(buf[0], 42);
+    assertEquals(resources.length, 2);
+
+    const [receivedFile, receivedListener] = resources;
+    assert(receivedFile instanceof Deno.FsFile);
+    await receivedFile.seek(0, Deno.SeekMode.Start);
+    const contents = new Uint8Array(5);
+    assertEquals(await receivedFile.read(contents), 5);
+    assertEquals(new TextDecoder().decode(contents), "hello");
+
+    const tcp = receivedListener as Deno.Listener;
+    assertEquals(tcp.addr.transport, "tcp");
+    assertEquals((tcp.addr as Deno.NetAddr).port, 3536);
+    // The handed off listener accepts connections on its own.
+    tcpListener.close();
+    const client = await Deno.connect({ port: 3536 });
+    const serverConn = await tcp.accept();
+    serverConn.close();
+    client.close();
+
+    receivedFile.close();
+    tcp.close();
+    file.close();
+    server.close();
+    conn.close();
+    listener.close();
+    await Deno.remove(tempFile);
+  },
+);
+
 Deno.test(
   { permissions: { net: true } },
   async function netUdpSendReceive() {
@@ -387,6 +468,59 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { net: true } },
+  function netUdpMulticastOptions() {
+    const socket = Deno.listenDatagram({
+      hostname: "0.0.0.0",
+      port: 3537,
+      transport: "udp",
+    });
+    socket.joinMulticastV4("239.255.0.42", "0.0.0.0");
+    socket.setMulticastTTL(2);
+    socket.setMulticastLoopback(false);
+    socket.setBroadcast(false);
+    socket.setRecvBufferSize(128 * 1024);
+    socket.leaveMulticast("239.255.0.42");
+    socket.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  function netUdpMulticastInvalidGroup() {
+    const socket = Deno.listenDatagram({ port: 3538, transport: "udp" });
+    assertThrows(
+      () => socket.joinMulticastV4("127.0.0.1"),
+      TypeError,
+      "127.0.0.1 is not a multicast address",
+    );
+    assertThrows(
+      () => socket.joinMulticastV4("not an address"),
+      TypeError,
+      "Invalid multicast address",
+    );
+    assertThrows(
+      () => socket.joinMulticastV6("239.255.0.42"),
+      TypeError,
+      "IPv4 multicast groups are joined by interface address",
+    );
+    socket.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: ["127.0.0.1:3539"] } },
+  function netUdpMulticastRequiresNetPermission() {
+    const socket = Deno.listenDatagram({ port: 3539, transport: "udp" });
+    assertThrows(
+      () => socket.joinMulticastV4("239.255.0.42"),
+      Deno.errors.PermissionDenied,
+    );
+    socket.close();
+  },
+);
+
 Deno.test(
   { permissions: { net: true }, ignore: true },
   async function netUdpSendReceiveBroadcast() {
@@ -1098,3 +1232,137 @@ Deno.test({
   }, Deno.errors.AddrInUse);
   listener1.close();
 });
+
+Deno.test(
+  { permissions: { net: true } },
+  async function netTcpConnectResolveOverride() {
+    const listener = Deno.listen({ hostname: "127.0.0.1", port: 3500 });
+    const accepted = listener.accept();
+
+    const conn = await Deno.connect({
+      hostname: "api.local",
+      port: 3500,
+      resolve: { "API.local": ["127.0.0.1"] },
+    });
+    assertEquals(conn.remoteAddr.hostname, "127.0.0.1");
+    assertEquals(conn.remoteAddr.port, 3500);
+
+    conn.close();
+    (await accepted).close();
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: ["api.local"] } },
+  async function netTcpConnectResolveOverridePermissions() {
+    await assertRejects(
+      () =>
+        Deno.connect({
+          hostname: "api.local",
+          port: 3500,
+          resolve: { "api.local": "127.0.0.1" },
+        }),
+      Deno.errors.PermissionDenied,
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function netTcpConnectResolveInvalidAddress() {
+    await assertRejects(
+      () =>
+        Deno.connect({
+          hostname: "api.local",
+          port: 3500,
+          resolve: { "api.local": "not an address" },
+        }),
+      TypeError,
+      'Invalid address "not an address" for host "api.local"',
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function netTcpConnectTimeout() {
+    // TEST-NET-1 addresses are not routed, so the connection either hangs
+    // until the timeout or fails right away on hosts without a default route.
+    const start = Date.now();
+    await assertRejects(() =>
+      Deno.connect({ hostname: "192.0.2.1", port: 80, timeout: 100 })
+    );
+    assert(Date.now() - start < 2000);
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function netTcpConnectAbortSignal() {
+    const listener = Deno.listen({ hostname: "127.0.0.1", port: 3500 });
+
+    const controller = new AbortController();
+    const reason = new Error("stop");
+    controller.abort(reason);
+    const error = await assertRejects(() =>
+      Deno.connect({ port: 3500, signal: controller.signal })
+    );
+    assertEquals(error, reason);
+
+    // A signal that isn't aborted doesn't get in the way.
+    const conn = await Deno.connect({
+      port: 3500,
+      signal: new AbortController().signal,
+    });
+    conn.close();
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function netTcpConnSetOption() {
+    const listener = Deno.listen({ hostname: "127.0.0.1", port: 3500 });
+    const accepted = listener.accept();
+    const conn = await Deno.connect({ port: 3500 });
+
+    conn.setOption("noDelay", true);
+    assertEquals(conn.getOption("noDelay"), true);
+    conn.setOption("keepAlive", true);
+    assertEquals(conn.getOption("keepAlive"), true);
+    conn.setOption("linger", 2000);
+    assertEquals(conn.getOption("linger"), 2000);
+    conn.setOption("linger", null);
+    assertEquals(conn.getOption("linger"), null);
+    conn.setOption("recvBufferSize", 65536);
+    assert(conn.getOption("recvBufferSize") >= 65536);
+    if (Deno.build.os !== "windows") {
+      conn.setOption("keepAliveTime", 30_000);
+      assertEquals(conn.getOption("keepAliveTime"), 30_000);
+      conn.setOption("tos", 0x10);
+      assertEquals(conn.getOption("tos"), 0x10);
+    }
+
+    conn.close();
+    (await accepted).close();
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function netTcpListenSocketOptions() {
+    const listener = Deno.listen({
+      hostname: "127.0.0.1",
+      port: 3500,
+      backlog: 1024,
+      reuseAddress: false,
+    });
+    const accepted = listener.accept();
+    const conn = await Deno.connect({ port: 3500 });
+    conn.close();
+    (await accepted).close();
+    listener.close();
+  },
+);
@@ -48,6 +48,28 @@ Deno.test({ permissions: { env: true } }, function avoidEmptyNamedEnv() {
   assertThrows(() => Deno.env.delete("a\0a"), TypeError);
 });
 
+Deno.test(
+  { permissions: { env: ["DENO_WILDCARD_*"] } },
+  async function envWildcardPermission() {
+    Deno.env.set("DENO_WILDCARD_A", "A");
+    assertEquals(Deno.env.get("DENO_WILDCARD_A"), "A");
+    assertThrows(() => {
+      Deno.env.get("PATH");
+    }, Deno.errors.PermissionDenied);
+
+    const env = Deno.env.toObject();
+    assertEquals(env["DENO_WILDCARD_A"], "A");
+    assertEquals(env["PATH"], undefined);
+    Deno.env.delete("DENO_WILDCARD_A");
+
+    const status = await Deno.permissions.query({
+      name: "env",
+      variable: "DENO_WILDCARD_B",
+    });
+    assertEquals(status.state, "granted");
+  },
+);
+
 Deno.test({ permissions: { env: false } }, function envPermissionDenied1() {
   assertThrows(() => {
     Deno.env.toObject();
@@ -0,0 +1,379 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { assert, assertEquals, assertRejects } from "./test_util.ts";
+
+const cert = await Deno.readTextFile("cli/tests/testdata/tls/localhost.crt");
+const key = await Deno.readTextFile("cli/tests/testdata/tls/localhost.key");
+const caCerts = [await Deno.readTextFile("cli/tests/testdata/tls/RootCA.pem")];
+
+// A minimal authoritative name server for the `svc.test` zone, just enough
+// to answer the queries sent by `Deno.resolveDns()`.
+
+const TYPE_A = 1;
+const TYPE_HINFO = 13;
+const TYPE_TXT = 16;
+const TYPE_SRV = 33;
+const TYPE_ANY = 255;
+
+interface ZoneRecord {
+  type: number;
+  ttl: number;
+  rdata: Uint8Array;
+}
+
+function encodeName(name: string): Uint8Array {
+  const bytes: number[] = [];
+  for (const label of name.split(".").filter((l) => l.length > 0)) {
+    bytes.push(label.length, ...new TextEncoder().encode(label));
+  }
+  bytes.push(0);
+  return new Uint8Array(bytes);
+}
+
+function concat(...parts: Uint8Array[]): Uint8Array {
+  const out = new Uint8Array(parts.reduce((n, p) => n + p.length, 0));
+  let offset = 0;
+  for (const part of parts) {
+    out.set(part, offset);
+    offset += part.length;
+  }
+  return out;
+}
+
+function u16(n: number): Uint8Array {
+  return new Uint8Array([n >> 8, n & 0xff]);
+}
+
+function u32(n: number): Uint8Array {
+  return concat(u16(n >>> 16), u16(n & 0xffff));
+}
+
+function characterString(s: string): Uint8Array {
+  const bytes = new TextEncoder().encode(s);
+  return concat(new Uint8Array([bytes.length]), bytes);
+}
+
+const zone: Record<string, ZoneRecord[]> = {
+  "svc.test": [
+    { type: TYPE_A, ttl: 300, rdata: new Uint8Array([10, 0, 0, 1]) },
+    { type: TYPE_TXT, ttl: 60, rdata: characterString("v=1") },
+    {
+      type: TYPE_HINFO,
+      ttl: 60,
+      rdata: concat(characterString("x86"), characterString("linux")),
+    },
+  ],
+  "local.svc.test": [
+    { type: TYPE_A, ttl: 300, rdata: new Uint8Array([127, 0, 0, 1]) },
+  ],
+  "_http._tcp.svc.test": [
+    {
+      type: TYPE_SRV,
+      ttl: 42,
+      rdata: concat(u16(10), u16(5), u16(8080), encodeName("svc.test")),
+    },
+  ],
+};
+
+function answer(query: Uint8Array): Uint8Array {
+  const labels: string[] = [];
+  let offset = 12;
+  while (query[offset] !== 0) {
+    const length = query[offset];
+    labels.push(
+      new TextDecoder().decode(query.subarray(offset + 1, offset + 1 + length)),
+    );
+    offset += length + 1;
+  }
+  offset += 1;
+  const type = (query[offset] << 8) | query[offset + 1];
+  const question = query.subarray(12, offset + 4);
+
+  const records = (zone[labels.join(".").toLowerCase()] ?? []).filter((r) =>
+    type === TYPE_ANY || r.type === type
+  );
+  const answers = records.map((r) =>
+    concat(
+      u16(0xc00c),
+      u16(r.type),
+      u16(1),
+      u32(r.ttl),
+      u16(r.rdata.length),
+      r.rdata,
+    )
+  );
+  const header = concat(
+    query.subarray(0, 2),
+    u16(0x8580),
+    u16(1),
+    u16(answers.length),
+    u16(0),
+    u16(0),
+  );
+  return concat(header, question, ...answers);
+}
+
+function serveUdp(port: number): Deno.DatagramConn {
+  const socket = Deno.listenDatagram({
+    hostname: "127.0.0.1",
+    port,
+    transport: "udp",
+  });
+  (async () => {
+    try {
+      while (true) {
+        const [query, addr] = await socket.receive();
+        await socket.send(answer(query), addr);
+      }
+    } catch {
+      // The socket was closed.
+    }
+  })();
+  return socket;
+}
+
+async function readExact(conn: Deno.Conn, n: number) {
+  const buf = new Uint8Array(n);
+  let read = 0;
+  while (read < n) {
+    const nread = await conn.read(buf.subarray(read));
+    if (nread === null) return null;
+    read += nread;
+  }
+  return buf;
+}
+
+function serveTls(port: number): Deno.TlsListener {
+  const listener = Deno.listenTls({ hostname: "127.0.0.1", port, cert, key });
+  (async () => {
+    for await (const conn of listener) {
+      (async () => {
+        try {
+          while (true) {
+            const length = await readExact(conn, 2);
+            if (length === null) break;
+            const query = await readExact(conn, (length[0] << 8) | length[1]);
+            if (query === null) break;
+            const response = answer(query);
+            await conn.write(concat(u16(response.length), response));
+          }
+        } catch {
+          // The client went away.
+        }
+        conn.close();
+      })();
+    }
+  })();
+  return listener;
+}
+
+function serveHttps(port: number): Deno.TlsListener {
+  const listener = Deno.listenTls({
+    hostname: "127.0.0.1",
+    port,
+    cert,
+    key,
+    alpnProtocols: ["h2"],
+  });
+  (async () => {
+    for await (const conn of listener) {
+      (async () => {
+        try {
+          for await (const { request, respondWith } of Deno.serveHttp(conn)) {
+            assertEquals(new URL(request.url).pathname, "/dns-query");
+            const query = new Uint8Array(await request.arrayBuffer());
+            await respondWith(
+              new Response(answer(query), {
+                headers: { "content-type": "application/dns-message" },
+              }),
+            );
+          }
+        } catch {
+          // The client went away.
+        }
+      })();
+    }
+  })();
+  return listener;
+}
+
+Deno.test(
+  { permissions: { net: true } },
+  async function resolveDnsMultipleNameServers() {
+    const server = serveUdp(4560);
+    const silent = Deno.listenDatagram({
+      hostname: "127.0.0.1",
+      port: 4561,
+      transport: "udp",
+    });
+
+    const records = await Deno.resolveDns("svc.test", "A", {
+      nameServers: [
+        { ipAddr: "127.0.0.1", port: 4561 },
+        { ipAddr: "127.0.0.1", port: 4560 },
+      ],
+      timeout: 500,
+      attempts: 1,
+    });
+    assertEquals(records, ["10.0.0.1"]);
+
+    silent.close();
+    server.close();
+  },
+);
+
+Deno.test({ permissions: { net: true } }, async function resolveDnsTimeout() {
+  const silent = Deno.listenDatagram({
+    hostname: "127.0.0.1",
+    port: 4561,
+    transport: "udp",
+  });
+
+  const start = Date.now();
+  await assertRejects(() =>
+    Deno.resolveDns("svc.test", "A", {
+      nameServers: [{ ipAddr: "127.0.0.1", port: 4561, protocol: "udp" }],
+      timeout: 100,
+      attempts: 1,
+    })
+  );
+  assert(Date.now() - start < 5000);
+
+  silent.close();
+});
+
+Deno.test(
+  { permissions: { net: true } },
+  async function resolveDnsRecordsWithTtl() {
+    const server = serveUdp(4562);
+    const nameServers = [{ ipAddr: "127.0.0.1", port: 4562 }];
+
+    const srv = await Deno.resolveDnsRecords("_http._tcp.svc.test", "SRV", {
+      nameServers,
+    });
+    assertEquals(srv, [{
+      recordType: "SRV",
+      ttl: 42,
+      data: { priority: 10, weight: 5, port: 8080, target: "svc.test." },
+    }]);
+
+    // Records of types that can't be represented (HINFO) are skipped.
+    const any = await Deno.resolveDnsRecords("svc.test", "ANY", {
+      nameServers,
+    });
+    assertEquals(any, [
+      { recordType: "A", ttl: 300, data: "10.0.0.1" },
+      { recordType: "TXT", ttl: 60, data: ["v=1"] },
+    ]);
+
+    server.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true, read: true } },
+  async function resolveDnsOverTls() {
+    const listener = serveTls(4563);
+
+    const records = await Deno.resolveDns("svc.test", "A", {
+      nameServers: [{
+        ipAddr: "127.0.0.1",
+        port: 4563,
+        protocol: "tls",
+        tlsDnsName: "localhost",
+      }],
+      caCerts,
+    });
+    assertEquals(records, ["10.0.0.1"]);
+
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true, read: true } },
+  async function resolveDnsOverHttps() {
+    const listener = serveHttps(4564);
+
+    const records = await Deno.resolveDns("svc.test", "A", {
+      nameServers: [{
+        ipAddr: "127.0.0.1",
+        port: 4564,
+        protocol: "https",
+        tlsDnsName: "localhost",
+      }],
+      caCerts,
+    });
+    assertEquals(records, ["10.0.0.1"]);
+
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function resolveDnsEncryptedRequiresTlsDnsName() {
+    await assertRejects(
+      () =>
+        Deno.resolveDns("svc.test", "A", {
+          nameServers: [{ ipAddr: "127.0.0.1", port: 4565, protocol: "tls" }],
+        }),
+      TypeError,
+      "`tlsDnsName` must be specified",
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function resolveDnsAnyFails() {
+    await assertRejects(
+      () =>
+        // @ts-expect-error "ANY" is only a valid type for resolveDnsRecords
+        Deno.resolveDns("svc.test", "ANY", {
+          nameServers: [{ ipAddr: "127.0.0.1", port: 4565 }],
+        }),
+      TypeError,
+      'Record type "ANY" is only supported by Deno.resolveDnsRecords()',
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function connectWithCustomResolver() {
+    const server = serveUdp(4566);
+    const listener = Deno.listen({ hostname: "127.0.0.1", port: 4567 });
+    const accepted = listener.accept();
+
+    const conn = await Deno.connect({
+      hostname: "local.svc.test",
+      port: 4567,
+      resolver: { nameServers: [{ ipAddr: "127.0.0.1", port: 4566 }] },
+    });
+    assertEquals(conn.remoteAddr.hostname, "127.0.0.1");
+
+    conn.close();
+    (await accepted).close();
+    listener.close();
+    server.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchWithCustomResolver() {
+    const server = serveUdp(4566);
+    const client = Deno.createHttpClient({
+      resolver: { nameServers: [{ ipAddr: "127.0.0.1", port: 4566 }] },
+    });
+
+    const response = await fetch(
+      "http://local.svc.test:4545/assets/fixture.json",
+      { client },
+    );
+    assertEquals((await response.json()).name, "deno");
+
+    client.close();
+    server.close();
+  },
+);
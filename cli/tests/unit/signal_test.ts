@@ -179,6 +179,49 @@ Deno.test(
   },
 );
 
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { run: true },
+  },
+  async function signalListenerReceivesInfo() {
+    const received = deferred<Deno.SignalInfo>();
+    const listener = (info: Deno.SignalInfo) => received.resolve(info);
+    Deno.addSignalListener("SIGUSR1", listener);
+    try {
+      Deno.kill(Deno.pid, "SIGUSR1");
+      const info = await received;
+      assertEquals(info.signal, "SIGUSR1");
+      assertEquals(info.pid, Deno.pid);
+      assertEquals(typeof info.signo, "number");
+      assertEquals(typeof info.uid, "number");
+    } finally {
+      Deno.removeSignalListener("SIGUSR1", listener);
+    }
+  },
+);
+
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { run: true, read: true },
+  },
+  async function signalRestoreDefaultAfterLastListener() {
+    const script = `
+      const listener = () => {};
+      Deno.addSignalListener("SIGTERM", listener);
+      Deno.removeSignalListener("SIGTERM", listener, { restoreDefault: true });
+      Deno.kill(Deno.pid, "SIGTERM");
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+    `;
+    const { code, signal } = await new Deno.Command(Deno.execPath(), {
+      args: ["eval", "--unstable", script],
+    }).output();
+    assertEquals(signal, "SIGTERM");
+    assertEquals(code, 143);
+  },
+);
+
 // This tests that pending op_signal_poll doesn't block the runtime from exiting the process.
 Deno.test(
   {
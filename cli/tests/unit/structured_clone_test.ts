@@ -1,6 +1,11 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
-import { assert, assertEquals, assertThrows } from "./test_util.ts";
+import {
+  assert,
+  assertEquals,
+  assertThrows,
+  deferred,
+} from "./test_util.ts";
 
 // Basic tests for the structured clone algorithm. Mainly tests TypeScript
 // typings. Actual functionality is tested in WPT.
@@ -53,3 +58,107 @@ Deno.test("correct DataCloneError message", () => {
   // ab2 should not be detached after above failure
   structuredClone(ab2, { transfer: [ab2] });
 });
+
+Deno.test("structuredClone transfers ReadableStream", async () => {
+  const original = new ReadableStream({
+    start(controller) {
+      controller.enqueue("hello");
+      controller.enqueue("world");
+      controller.close();
+    },
+  });
+  const { stream } = structuredClone({ stream: original }, {
+    transfer: [original],
+  });
+  assert(stream instanceof ReadableStream);
+  assert(stream !== original);
+  assert(original.locked);
+
+  const chunks = [];
+  for await (const chunk of stream) {
+    chunks.push(chunk);
+  }
+  assertEquals(chunks, ["hello", "world"]);
+});
+
+Deno.test("structuredClone transfers WritableStream", async () => {
+  const chunks: string[] = [];
+  const closed = deferred<void>();
+  const original = new WritableStream<string>({
+    write(chunk) {
+      chunks.push(chunk);
+    },
+    close() {
+      closed.resolve();
+    },
+  });
+  const stream = structuredClone(original, { transfer: [original] });
+  assert(stream instanceof WritableStream);
+  assert(original.locked);
+
+  const writer = stream.getWriter();
+  await writer.write("hello");
+  await writer.close();
+  // Wait for the pipe in the sending realm to flush.
+  await closed;
+  assertEquals(chunks, ["hello"]);
+});
+
+Deno.test("structuredClone transfers TransformStream", async () => {
+  const original = new TransformStream<string, string>({
+    transform(chunk, controller) {
+      controller.enqueue(chunk.toUpperCase());
+    },
+  });
+  const [stream] = structuredClone([original], { transfer: [original] });
+  assert(stream instanceof TransformStream);
+
+  const writer = stream.writable.getWriter();
+  const reader = stream.readable.getReader();
+  writer.write("hello");
+  assertEquals(await reader.read(), { value: "HELLO", done: false });
+  writer.close();
+  assertEquals(await reader.read(), { value: undefined, done: true });
+});
+
+Deno.test("structuredClone transfers streams in any object", () => {
+  class Holder {
+    constructor(public stream: ReadableStream) {}
+  }
+  const original = new ReadableStream();
+  const holder = new Holder(original);
+  const map = new Map([["stream", original]]);
+  const [clonedHolder, clonedMap] = structuredClone([holder, map], {
+    transfer: [original],
+  });
+  assert(clonedHolder.stream instanceof ReadableStream);
+  assert(clonedHolder.stream !== original);
+  assert(clonedMap.get("stream") === clonedHolder.stream);
+  // The original object graph is left untouched.
+  assert(holder.stream === original);
+  assert(map.get("stream") === original);
+});
+
+Deno.test("structuredClone throws on duplicate streams", () => {
+  const stream = new ReadableStream();
+  assertThrows(
+    () => {
+      structuredClone(stream, { transfer: [stream, stream] });
+    },
+    DOMException,
+    "Stream at index 1 is transferred more than once",
+  );
+  assert(!stream.locked);
+});
+
+Deno.test("structuredClone throws on locked streams", () => {
+  const stream = new ReadableStream();
+  stream.getReader();
+  assertThrows(
+    () => {
+      structuredClone(stream, { transfer: [stream] });
+    },
+    DOMException,
+    "Can not transfer locked ReadableStream",
+  );
+});
@@ -450,6 +450,272 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function tlsServerRequireClientCert() {
+    const hostname = "localhost";
+    const port = 3540;
+    const listener = Deno.listenTls({
+      hostname,
+      port,
+      cert,
+      key,
+      caCerts,
+      requireClientCert: true,
+    });
+    const [serverConn, clientConn] = await Promise.all([
+      listener.accept(),
+      Deno.connectTls({
+        hostname,
+        port,
+        caCerts,
+        certChain: cert,
+        privateKey: key,
+      }),
+    ]);
+    const [serverHS, clientHS] = await Promise.all([
+      serverConn.handshake(),
+      clientConn.handshake(),
+    ]);
+
+    assert(serverHS.peerCertificates);
+    assertEquals(serverHS.peerCertificates.length, 1);
+    const [clientCert] = serverHS.peerCertificates;
+    assert(clientCert.subject.includes("CN=localhost.local"));
+    assert(clientCert.issuer.includes("CN=Example-Root-CA"));
+    assertEquals(clientCert.subjectAltNames, ["DNS:localhost"]);
+    assert(/^([0-9A-F]{2}:){31}[0-9A-F]{2}$/.test(clientCert.fingerprint256));
+    assert(/^[0-9A-F]+$/.test(clientCert.serialNumber));
+    assert(clientHS.peerCertificates);
+    assertEquals(
+      clientHS.peerCertificates[0].fingerprint256,
+      clientCert.fingerprint256,
+    );
+
+    serverConn.close();
+    clientConn.close();
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function tlsServerRequireClientCertRejectsAnonymous() {
+    const hostname = "localhost";
+    const port = 3541;
+    const listener = Deno.listenTls({
+      hostname,
+      port,
+      cert,
+      key,
+      caCerts,
+      requireClientCert: true,
+    });
+    const [serverConn, clientConn] = await Promise.all([
+      listener.accept(),
+      Deno.connectTls({ hostname, port, caCerts }),
+    ]);
+    await Promise.all([
+      assertRejects(() => serverConn.handshake()),
+      clientConn.handshake().catch(() => {}),
+    ]);
+
+    serverConn.close();
+    clientConn.close();
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function tlsHandshakeSessionDetails() {
+    const hostname = "localhost";
+    const port = getPort();
+    const listener = Deno.listenTls({ hostname, port, cert, key });
+    const [serverConn, clientConn] = await Promise.all([
+      listener.accept(),
+      Deno.connectTls({ hostname, port, caCerts }),
+    ]);
+    const [serverHS, clientHS] = await Promise.all([
+      serverConn.handshake(),
+      clientConn.handshake(),
+    ]);
+
+    assertEquals(serverHS.protocolVersion, "TLSv1.3");
+    assertEquals(clientHS.protocolVersion, "TLSv1.3");
+    assert(clientHS.cipherSuite?.startsWith("TLS13_"));
+    assertEquals(serverHS.cipherSuite, clientHS.cipherSuite);
+    assertEquals(serverHS.serverName, "localhost");
+    assertEquals(clientHS.serverName, "localhost");
+    assertStrictEquals(serverHS.peerCertificates, null);
+
+    assert(clientHS.peerCertificates);
+    const [serverCert] = clientHS.peerCertificates;
+    const pem = cert.replace(/-----[A-Z ]+-----|\s/g, "");
+    const der = Uint8Array.from(atob(pem), (c) => c.charCodeAt(0));
+    assertEquals(serverCert.der, der);
+    const digest = new Uint8Array(
+      await crypto.subtle.digest("SHA-256", serverCert.der),
+    );
+    assertEquals(
+      serverCert.fingerprint256,
+      Array.from(digest, (b) => b.toString(16).padStart(2, "0"))
+        .join(":")
+        .toUpperCase(),
+    );
+
+    serverConn.close();
+    clientConn.close();
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function tlsServerRequestClientCertIsOptional() {
+    const hostname = "localhost";
+    const port = 3542;
+    const listener = Deno.listenTls({
+      hostname,
+      port,
+      cert,
+      key,
+      caCerts,
+      requestClientCert: true,
+    });
+    const [serverConn, clientConn] = await Promise.all([
+      listener.accept(),
+      Deno.connectTls({ hostname, port, caCerts }),
+    ]);
+    const [serverHS] = await Promise.all([
+      serverConn.handshake(),
+      clientConn.handshake(),
+    ]);
+    assertStrictEquals(serverHS.peerCertificates, null);
+
+    serverConn.close();
+    clientConn.close();
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  function tlsServerClientCertRequiresCaCerts() {
+    assertThrows(
+      () => {
+        Deno.listenTls({
+          hostname: "localhost",
+          port: 3543,
+          cert,
+          key,
+          requireClientCert: true,
+        });
+      },
+      TypeError,
+      "`caCerts` must be specified",
+    );
+  },
+);
+
+const altCert = await Deno.readTextFile(
+  "cli/tests/testdata/tls/localhost_alt.crt",
+);
+const altKey = await Deno.readTextFile(
+  "cli/tests/testdata/tls/localhost_alt.key",
+);
+
+async function presentedSubject(listener: Deno.TlsListener, port: number) {
+  const [serverConn, clientConn] = await Promise.all([
+    listener.accept(),
+    Deno.connectTls({ hostname: "localhost", port, caCerts }),
+  ]);
+  const [, { peerCertificates }] = await Promise.all([
+    serverConn.handshake(),
+    clientConn.handshake(),
+  ]);
+  serverConn.close();
+  clientConn.close();
+  assert(peerCertificates);
+  return peerCertificates[0].subject;
+}
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function tlsServerSniCertificates() {
+    const port = 3544;
+    const listener = Deno.listenTls({
+      hostname: "localhost",
+      port,
+      cert,
+      key,
+      sniCertificates: { "LOCALHOST": { cert: altCert, key: altKey } },
+    });
+    assert(
+      (await presentedSubject(listener, port)).includes("CN=localhost.alt"),
+    );
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function tlsServerSniCertificatesWithoutDefault() {
+    const port = 3545;
+    const listener = Deno.listenTls({
+      hostname: "localhost",
+      port,
+      sniCertificates: { "localhost": { cert: altCert, key: altKey } },
+    });
+    assert(
+      (await presentedSubject(listener, port)).includes("CN=localhost.alt"),
+    );
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, net: true } },
+  async function tlsServerUpdateCertificates() {
+    const hostname = "localhost";
+    const port = 3546;
+    const listener = Deno.listenTls({ hostname, port, cert, key });
+    const [serverConn, clientConn] = await Promise.all([
+      listener.accept(),
+      Deno.connectTls({ hostname, port, caCerts }),
+    ]);
+    await Promise.all([serverConn.handshake(), clientConn.handshake()]);
+
+    listener.updateCertificates({ cert: altCert, key: altKey });
+    assert(
+      (await presentedSubject(listener, port)).includes("CN=localhost.alt"),
+    );
+
+    // Connections accepted before the update keep working.
+    await clientConn.write(encoder.encode("ping"));
+    const buf = new Uint8Array(4);
+    assertEquals(await serverConn.read(buf), 4);
+    assertEquals(decoder.decode(buf), "ping");
+
+    assertThrows(
+      () => listener.updateCertificates({ cert }),
+      TypeError,
+      "Both `cert` and `key` must be specified.",
+    );
+    assertThrows(
+      () => listener.updateCertificates({ cert: "bad", key }),
+      Deno.errors.InvalidData,
+    );
+    assert(
+      (await presentedSubject(listener, port)).includes("CN=localhost.alt"),
+    );
+
+    serverConn.close();
+    clientConn.close();
+    listener.close();
+  },
+);
+
 Deno.test(
   { permissions: { read: true, net: true } },
   async function tlsServerStreamHalfCloseSendOneByte() {
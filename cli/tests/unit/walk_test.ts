@@ -0,0 +1,144 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { assertEquals, assertRejects } from "./test_util.ts";
+
+async function makeTree(): Promise<string> {
+  const root = await Deno.makeTempDir();
+  await Deno.mkdir(`${root}/a/b`, { recursive: true });
+  await Deno.mkdir(`${root}/node_modules/dep`, { recursive: true });
+  await Deno.writeTextFile(`${root}/mod.ts`, "");
+  await Deno.writeTextFile(`${root}/a/one.ts`, "");
+  await Deno.writeTextFile(`${root}/a/b/two.ts`, "");
+  await Deno.writeTextFile(`${root}/a/b/notes.md`, "hello");
+  await Deno.writeTextFile(`${root}/node_modules/dep/index.ts`, "");
+  return root;
+}
+
+async function collect(
+  iterator: AsyncIterable<Deno.WalkEntry>,
+  root: string,
+): Promise<string[]> {
+  const paths = [];
+  for await (const entry of iterator) {
+    paths.push(entry.path.slice(root.length).replaceAll("\\", "/"));
+  }
+  return paths.sort();
+}
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function walkAllEntries() {
+    const root = await makeTree();
+    const paths = await collect(Deno.walk(root), root);
+    assertEquals(paths, [
+      "",
+      "/a",
+      "/a/b",
+      "/a/b/notes.md",
+      "/a/b/two.ts",
+      "/a/one.ts",
+      "/mod.ts",
+      "/node_modules",
+      "/node_modules/dep",
+      "/node_modules/dep/index.ts",
+    ]);
+    await Deno.remove(root, { recursive: true });
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function walkWithOptions() {
+    const root = await makeTree();
+    const paths = await collect(
+      Deno.walk(root, {
+        includeDirs: false,
+        maxDepth: 2,
+        exclude: ["node_modules/"],
+      }),
+      root,
+    );
+    assertEquals(paths, ["/a/one.ts", "/mod.ts"]);
+    await Deno.remove(root, { recursive: true });
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function walkGitignore() {
+    const root = await makeTree();
+    await Deno.writeTextFile(`${root}/.gitignore`, "node_modules\n*.md\n");
+    const paths = await collect(
+      Deno.walk(root, { includeDirs: false, gitignore: true }),
+      root,
+    );
+    assertEquals(paths, ["/.gitignore", "/a/b/two.ts", "/a/one.ts", "/mod.ts"]);
+    await Deno.remove(root, { recursive: true });
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function walkStat() {
+    const root = await makeTree();
+    for await (const entry of Deno.walk(root, { stat: true })) {
+      assertEquals(entry.info?.isFile, entry.isFile);
+      if (entry.name === "notes.md") {
+        assertEquals(entry.info?.size, 5);
+      }
+    }
+    await Deno.remove(root, { recursive: true });
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function globPatterns() {
+    const root = await makeTree();
+    const paths = await collect(
+      Deno.glob(["**/*.ts", "a/b/*.md"], {
+        root,
+        exclude: ["node_modules"],
+      }),
+      root,
+    );
+    assertEquals(paths, [
+      "/a/b/notes.md",
+      "/a/b/two.ts",
+      "/a/one.ts",
+      "/mod.ts",
+    ]);
+    await Deno.remove(root, { recursive: true });
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function globMissingBaseDir() {
+    const root = await makeTree();
+    const paths = await collect(
+      Deno.glob(["missing/**/*.ts", "a/missing.ts", "mod.ts"], { root }),
+      root,
+    );
+    assertEquals(paths, ["/mod.ts"]);
+    await Deno.remove(root, { recursive: true });
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function globInvalidPattern() {
+    const root = await makeTree();
+    await assertRejects(
+      () => collect(Deno.glob("a/[", { root }), root),
+      TypeError,
+      'Invalid glob "a/["',
+    );
+    await Deno.remove(root, { recursive: true });
+  },
+);
+
+Deno.test({ permissions: { read: false } }, async function walkPerm() {
+  await assertRejects(async () => {
+    await Deno.walk("tests/").next();
+  }, Deno.errors.PermissionDenied);
+});
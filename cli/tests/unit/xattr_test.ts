@@ -0,0 +1,91 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { assertEquals, assertRejects, assertThrows } from "./test_util.ts";
+
+Deno.test(
+  {
+    permissions: { read: true, write: true },
+    ignore: Deno.build.os !== "linux",
+  },
+  function xattrSync() {
+    const path = Deno.makeTempFileSync();
+    assertEquals(Deno.getXattrSync(path, "user.deno"), null);
+    Deno.setXattrSync(path, "user.deno", "hello");
+    Deno.setXattrSync(path, "user.bytes", new Uint8Array([1, 2, 3]));
+    assertEquals(
+      new TextDecoder().decode(Deno.getXattrSync(path, "user.deno")!),
+      "hello",
+    );
+    assertEquals(
+      Deno.getXattrSync(path, "user.bytes"),
+      new Uint8Array([1, 2, 3]),
+    );
+    assertEquals(
+      Deno.listXattrSync(path).filter((name) => name.startsWith("user."))
+        .sort(),
+      ["user.bytes", "user.deno"],
+    );
+    Deno.removeXattrSync(path, "user.deno");
+    assertEquals(Deno.getXattrSync(path, "user.deno"), null);
+    Deno.removeSync(path);
+  },
+);
+
+Deno.test(
+  {
+    permissions: { read: true, write: true },
+    ignore: Deno.build.os !== "linux",
+  },
+  async function xattrAsync() {
+    const path = await Deno.makeTempFile();
+    await Deno.setXattr(path, "user.deno", "hello");
+    assertEquals(
+      new TextDecoder().decode((await Deno.getXattr(path, "user.deno"))!),
+      "hello",
+    );
+    assertEquals(
+      (await Deno.listXattr(path)).filter((name) => name.startsWith("user.")),
+      ["user.deno"],
+    );
+
+    const copy = path + ".copy";
+    await Deno.copyFile(path, copy, { preserve: true });
+    assertEquals(
+      new TextDecoder().decode((await Deno.getXattr(copy, "user.deno"))!),
+      "hello",
+    );
+
+    await Deno.removeXattr(path, "user.deno");
+    assertEquals(await Deno.getXattr(path, "user.deno"), null);
+    await Deno.remove(path);
+    await Deno.remove(copy);
+  },
+);
+
+Deno.test(
+  {
+    permissions: { read: true, write: true },
+    ignore: Deno.build.os !== "linux",
+  },
+  async function xattrNotFound() {
+    await assertRejects(
+      () => Deno.getXattr("/nonexistent/file", "user.deno"),
+      Deno.errors.NotFound,
+      "getxattr '/nonexistent/file'",
+    );
+  },
+);
+
+Deno.test({ permissions: { read: false } }, function getXattrPerm() {
+  assertThrows(() => {
+    Deno.getXattrSync("README.md", "user.deno");
+  }, Deno.errors.PermissionDenied);
+});
+
+Deno.test(
+  { permissions: { read: true, write: false } },
+  async function setXattrPerm() {
+    await assertRejects(async () => {
+      await Deno.setXattr("README.md", "user.deno", "hello");
+    }, Deno.errors.PermissionDenied);
+  },
+);
@@ -1294,7 +1294,9 @@ declare namespace Deno {
      * console.log(myEnv.TEST_VAR);  // outputs "A"
      * ```
      *
-     * Requires `allow-env` permission.
+     * Requires `allow-env` permission. If access was only granted to specific
+     * variables or patterns (e.g. `--allow-env=HOME,AWS_*`), only those
+     * variables are returned.
      *
      * @tags allow-env
      */
@@ -3218,6 +3220,9 @@ declare namespace Deno {
    * by default creating a new file if needed, else overwriting. Fails if target
    * path is a directory or is unwritable.
    *
+   * Where the file system supports it, the copy shares the data of the source
+   * file instead of duplicating it (`clonefile` on macOS, `FICLONE` on Linux).
+   *
    * ```ts
    * await Deno.copyFile("from.txt", "to.txt");
    * ```
@@ -3664,6 +3669,10 @@ declare namespace Deno {
    * }
    * ```
    *
+   * If events are produced faster than they are consumed, some are dropped
+   * and an event with the `"rescan"` flag is yielded instead, signaling that
+   * the watched paths should be rescanned.
+   *
    * Call `watcher.close()` to stop watching.
    *
    * ```ts
@@ -4376,7 +4385,8 @@ declare namespace Deno {
    * @category Permissions */
   export interface EnvPermissionDescriptor {
     name: "env";
-    /** Optional environment variable name (e.g. `PATH`). */
+    /** Optional environment variable name (e.g. `PATH`), or a pattern with a
+     * trailing wildcard matching a family of variables (e.g. `AWS_*`). */
     variable?: string;
   }
 
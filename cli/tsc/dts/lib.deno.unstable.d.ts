@@ -748,6 +748,69 @@ declare namespace Deno {
     certChain?: string;
     /** PEM formatted (RSA or PKCS8) private key of client certificate. */
     privateKey?: string;
+    /** Pins host names to addresses, bypassing name resolution, like curl's
+     * `--resolve`. The port of the request URL is kept. Requests to an
+     * overridden host also need net permission for its addresses.
+     *
+     * ```ts
+     * const client = Deno.createHttpClient({
+     *   resolve: { "api.local": "127.0.0.1" },
+     * });
+     * const res = await fetch("http://api.local:8080/", { client });
+     * ```
+     */
+    resolve?: Record<string, string | string[]>;
+    /** Resolves host names not listed in `resolve` with a custom resolver
+     * instead of the system one. */
+    resolver?: ResolverOptions;
+    /** Time in milliseconds to wait for a connection to be established. */
+    connectTimeout?: number;
+    /** Time in milliseconds to wait for the response headers, and then for
+     * each chunk of the response body. When retrying, it applies to every
+     * attempt. */
+    readTimeout?: number;
+    /** Time in milliseconds from sending a request until its response body
+     * has been fully read. When retrying, it applies to every attempt. */
+    timeout?: number;
+    /** The maximum number of idle connections kept open per host. */
+    poolMaxIdlePerHost?: number;
+    /** Time in milliseconds after which idle connections are closed. The
+     * default is 90 seconds. */
+    poolIdleTimeout?: number;
+    /** Whether HTTP/1.1 may be used. If `false`, HTTP/2 is used for all
+     * requests, also for `http:` URLs. Defaults to `true`. */
+    http1?: boolean;
+    /** Whether HTTP/2 may be used. If `false`, it isn't offered during the
+     * TLS handshake. Defaults to `true`. */
+    http2?: boolean;
+    /** Retries idempotent requests (`GET`, `HEAD`, `OPTIONS`, `TRACE`, `PUT`
+     * and `DELETE`) that fail. Requests with a stream as body are never
+     * retried. */
+    retry?: RetryOptions;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The retry policy of a {@linkcode Deno.HttpClient}, see
+   * {@linkcode Deno.CreateHttpClientOptions}.
+   *
+   * ```ts
+   * const client = Deno.createHttpClient({
+   *   retry: { retries: 3, statusCodes: [502, 503] },
+   * });
+   * ```
+   *
+   * @category Fetch API
+   */
+  export interface RetryOptions {
+    /** The number of retries after the first attempt. */
+    retries: number;
+    /** Delay in milliseconds before the first retry, doubled for every
+     * following one up to a maximum of 30 seconds. Defaults to `100`. */
+    delay?: number;
+    /** Response statuses that are retried in addition to connection errors
+     * and timeouts. The response of the last attempt is returned. */
+    statusCodes?: number[];
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -824,6 +887,45 @@ declare namespace Deno {
     close(): void;
     /** Return the address of the instance. */
     readonly addr: Addr;
+    /** Joins the IPv4 multicast group `address` on the interface with the
+     * given local address. By default the operating system picks the
+     * interface. Only supported on `"udp"` sockets.
+     *
+     * Requires `allow-net` permission for the group address.
+     *
+     * ```ts
+     * const socket = Deno.listenDatagram({
+     *   hostname: "0.0.0.0",
+     *   port: 5353,
+     *   transport: "udp",
+     *   reuseAddress: true,
+     * });
+     * socket.joinMulticastV4("224.0.0.251");
+     * ```
+     */
+    joinMulticastV4(address: string, networkInterface?: string): void;
+    /** Joins the IPv6 multicast group `address` on the interface with the
+     * given index. By default the operating system picks the interface. Only
+     * supported on `"udp"` sockets.
+     *
+     * Requires `allow-net` permission for the group address.
+     */
+    joinMulticastV6(address: string, interfaceIndex?: number): void;
+    /** Leaves a multicast group joined with `joinMulticastV4()` or
+     * `joinMulticastV6()`, using the same interface it was joined on. */
+    leaveMulticast(address: string, networkInterface?: string | number): void;
+    /** Sets the time to live (hop limit for IPv6) of outgoing multicast
+     * packets. Defaults to `1`, which keeps them on the local network. */
+    setMulticastTTL(ttl: number): void;
+    /** Sets whether multicast packets sent by this socket are looped back to
+     * the local host. Enabled by default. */
+    setMulticastLoopback(loopback: boolean): void;
+    /** Sets whether the socket may send to broadcast addresses
+     * (`SO_BROADCAST`). Enabled by default. */
+    setBroadcast(broadcast: boolean): void;
+    /** Sets the size of the operating system's receive buffer for the socket
+     * (`SO_RCVBUF`). */
+    setRecvBufferSize(size: number): void;
     [Symbol.asyncIterator](): AsyncIterableIterator<[Uint8Array, Addr]>;
   }
 
@@ -843,6 +945,87 @@ declare namespace Deno {
      *
      * @default {false} */
     reusePort?: boolean;
+    /** The maximum length of the queue of pending connections. The operating
+     * system may cap it, for example to `net.core.somaxconn` on Linux.
+     *
+     * @default {128} */
+    backlog?: number;
+    /** Whether the SO_REUSEADDR flag is set on the listener, which allows
+     * binding to an address whose previous connections are still in
+     * `TIME_WAIT`. On Windows it also allows binding to a port that is in
+     * use, so it is off there by default.
+     *
+     * @default {true} on Unix, {false} on Windows */
+    reuseAddress?: boolean;
+    /** For IPv6 addresses, whether only IPv6 connections are accepted
+     * (IPV6_V6ONLY). When `false`, listening on `"::"` also accepts IPv4
+     * connections. Ignored for IPv4 addresses. Defaults to the operating
+     * system's setting. */
+    ipv6Only?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The name and value type of each option that can be set with
+   * {@linkcode Deno.TcpConn.setOption}. Durations are in milliseconds, but
+   * most platforms only honor whole seconds for the keepalive and linger
+   * options.
+   *
+   * @category Network
+   */
+  export interface TcpOptions {
+    /** Disables Nagle's algorithm (TCP_NODELAY). */
+    noDelay: boolean;
+    /** Enables keepalive probes (SO_KEEPALIVE). */
+    keepAlive: boolean;
+    /** Idle time before the first keepalive probe is sent (TCP_KEEPIDLE).
+     * Setting it doesn't enable keepalive. */
+    keepAliveTime: number;
+    /** Time between keepalive probes (TCP_KEEPINTVL). */
+    keepAliveInterval: number;
+    /** Number of unanswered probes before the connection is dropped
+     * (TCP_KEEPCNT). */
+    keepAliveRetries: number;
+    /** How long closing waits for unsent data to be sent (SO_LINGER), or
+     * `null` to not wait. */
+    linger: number | null;
+    /** The type of service field of outgoing packets (IP_TOS), or the
+     * traffic class on IPv6 sockets (IPV6_TCLASS). Can't be set on IPv6
+     * sockets on Windows. */
+    tos: number;
+    /** The size of the send buffer (SO_SNDBUF). */
+    sendBufferSize: number;
+    /** The size of the receive buffer (SO_RCVBUF). */
+    recvBufferSize: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Network
+   */
+  export interface TcpConn {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Sets a socket option of the connection.
+     *
+     * ```ts
+     * const conn = await Deno.connect({ hostname: "example.com", port: 80 });
+     * conn.setOption("keepAlive", true);
+     * conn.setOption("keepAliveTime", 30_000);
+     * conn.setOption("linger", 0);
+     * ```
+     *
+     * Throws {@linkcode Deno.errors.NotSupported} for options that the
+     * platform doesn't support.
+     */
+    setOption<K extends keyof TcpOptions>(name: K, value: TcpOptions[K]): void;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Returns the current value of a socket option of the connection. The
+     * operating system may have adjusted the value that was set, for example
+     * Linux doubles buffer sizes.
+     */
+    getOption<K extends keyof TcpOptions>(name: K): TcpOptions[K];
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -935,6 +1118,70 @@ declare namespace Deno {
     options: UnixListenOptions & { transport: "unixpacket" },
   ): DatagramConn;
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A custom resolver used instead of the system one by
+   * {@linkcode Deno.connect} and {@linkcode Deno.createHttpClient}.
+   *
+   * @category Network
+   */
+  export interface ResolverOptions {
+    /** Name servers to query, tried in order. If empty, the system
+     * configuration is used. */
+    nameServers?: NameServerOptions[];
+    /** Additional root certificates in PEM format used to verify `"tls"` and
+     * `"https"` name servers. */
+    caCerts?: string[];
+    /** Time in milliseconds to wait for each answer.
+     *
+     * @default {5000} */
+    timeout?: number;
+    /** How many times a query is sent before giving up.
+     *
+     * @default {2} */
+    attempts?: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Network
+   */
+  export interface ConnectOptions {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Pins host names to addresses, bypassing name resolution, like curl's
+     * `--resolve`. Host names are matched case-insensitively. Requires net
+     * permission for all of the given addresses.
+     *
+     * ```ts
+     * const conn = await Deno.connect({
+     *   hostname: "api.local",
+     *   port: 8080,
+     *   resolve: { "api.local": "127.0.0.1" },
+     * });
+     * ```
+     */
+    resolve?: Record<string, string | string[]>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Resolves host names not listed in `resolve` with a custom resolver
+     * instead of the system one.
+     */
+    resolver?: ResolverOptions;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Time in milliseconds after which connecting fails with
+     * {@linkcode Deno.errors.TimedOut}, covering both name resolution and
+     * all connection attempts.
+     */
+    timeout?: number;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Aborts connecting, rejecting with the signal's reason.
+     */
+    signal?: AbortSignal;
+  }
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * @category Network
@@ -949,6 +1196,10 @@ declare namespace Deno {
    * Connects to the hostname (default is "127.0.0.1") and port on the named
    * transport (default is "tcp"), and resolves to the connection (`Conn`).
    *
+   * If the hostname resolves to several addresses, they are tried in turn
+   * alternating between IPv6 and IPv4, starting a new attempt every 250ms
+   * while earlier ones are pending ("Happy Eyeballs", RFC 8305).
+   *
    * ```ts
    * const conn1 = await Deno.connect({ port: 80 });
    * const conn2 = await Deno.connect({ hostname: "192.0.2.1", port: 80 });
@@ -983,6 +1234,89 @@ declare namespace Deno {
    */
   export function connect(options: UnixConnectOptions): Promise<UnixConn>;
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Credentials of the process on the other end of a unix socket, as
+   * captured when the connection was established.
+   *
+   * @category Network
+   */
+  export interface UnixPeerCredentials {
+    /** The process id of the peer. `null` on platforms that don't report
+     * it. */
+    pid: number | null;
+    /** The effective user id of the peer. */
+    uid: number;
+    /** The effective group id of the peer. */
+    gid: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A resource that can be passed to another process over a unix socket.
+   *
+   * @category Network
+   */
+  export type TransferableResource =
+    | FsFile
+    | Conn
+    | Listener
+    | DatagramConn;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Network
+   */
+  export interface UnixRecvFdsResult {
+    /** The number of bytes read into the buffer, or `null` at EOF. */
+    nread: number | null;
+    /** The resources received along with the data. Files become
+     * {@linkcode Deno.FsFile}s and sockets the matching connection, listener
+     * or datagram type. */
+    resources: TransferableResource[];
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Network
+   */
+  export interface UnixConn {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Returns the credentials of the peer process (`SO_PEERCRED`).
+     */
+    peerCredentials(): UnixPeerCredentials;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Passes the file descriptors of the given resources to the peer
+     * (`SCM_RIGHTS`), along with `data`, which must not be empty and
+     * defaults to a single zero byte. The resources remain open in this
+     * process. Resolves to the number of bytes of `data` written.
+     *
+     * ```ts
+     * const listener = Deno.listen({ port: 8080 });
+     * await conn.sendFds([listener]);
+     * ```
+     */
+    sendFds(
+      resources: (TransferableResource | number)[],
+      data?: Uint8Array,
+    ): Promise<number>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Reads data into `buffer`, which defaults to a single byte, together
+     * with any resources sent by the peer with `sendFds()`.
+     *
+     * ```ts
+     * const { resources: [listener] } = await conn.recvFds();
+     * for await (const conn of listener as Deno.Listener) {
+     *   // ...
+     * }
+     * ```
+     */
+    recvFds(buffer?: Uint8Array): Promise<UnixRecvFdsResult>;
+  }
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * @category Network
@@ -1005,6 +1339,18 @@ declare namespace Deno {
      * TLS handshake.
      */
     alpnProtocols?: string[];
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Time in milliseconds after which connecting fails with
+     * {@linkcode Deno.errors.TimedOut}, covering name resolution and all
+     * connection attempts but not the TLS handshake.
+     */
+    timeout?: number;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Aborts connecting, rejecting with the signal's reason.
+     */
+    signal?: AbortSignal;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -1018,6 +1364,53 @@ declare namespace Deno {
      * If no ALPN protocol selected, returns `null`.
      */
     alpnProtocol: string | null;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * The negotiated protocol version, e.g. `"TLSv1.3"`.
+     */
+    protocolVersion: string | null;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * The negotiated cipher suite, e.g. `"TLS13_AES_256_GCM_SHA384"`.
+     */
+    cipherSuite: string | null;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * The SNI hostname: on a client the name that was asked for, on a server
+     * the name the client asked for. `null` when no name was sent, e.g. when
+     * connecting to an IP address.
+     */
+    serverName: string | null;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * The certificate chain presented by the peer, starting with the peer's
+     * own certificate. On a server this is `null` unless the client presented
+     * a certificate that was verified against `caCerts`.
+     */
+    peerCertificates: PeerCertificate[] | null;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A certificate presented by the peer of a TLS connection.
+   *
+   * @category Network
+   */
+  export interface PeerCertificate {
+    /** The subject distinguished name, e.g. `"C=US, CN=localhost"`. */
+    subject: string;
+    /** The issuer distinguished name. */
+    issuer: string;
+    /** Subject alternative names, e.g. `"DNS:localhost"` or
+     * `"IP Address:127.0.0.1"`. */
+    subjectAltNames: string[];
+    /** The serial number as uppercase hex. */
+    serialNumber: string;
+    /** The SHA-256 fingerprint of the DER encoded certificate as colon
+     * separated uppercase hex. */
+    fingerprint256: string;
+    /** The DER encoded certificate. */
+    der: Uint8Array;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -1066,6 +1459,85 @@ declare namespace Deno {
      * TLS handshake.
      */
     alpnProtocols?: string[];
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * A list of root certificates in PEM format used to verify client
+     * certificates. Required when `requestClientCert` or `requireClientCert`
+     * is set.
+     */
+    caCerts?: string[];
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Ask clients for a certificate. Clients without one are still accepted,
+     * but a certificate that is presented must be signed by one of `caCerts`.
+     *
+     * @default {false}
+     */
+    requestClientCert?: boolean;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Reject clients that don't present a certificate signed by one of
+     * `caCerts`.
+     *
+     * @default {false}
+     */
+    requireClientCert?: boolean;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Certificates to present to clients by the SNI hostname they ask for.
+     * Names may start with a wildcard label, e.g. `*.example.com`. Clients
+     * asking for any other hostname get `cert`/`key`, which may be omitted
+     * when this map is given.
+     */
+    sniCertificates?: Record<string, TlsKeyPair>;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A certificate chain and its private key, both in PEM format.
+   *
+   * @category Network
+   */
+  export interface TlsKeyPair {
+    cert: string;
+    key: string;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The full set of certificates a TLS server presents. At least a default
+   * `cert`/`key` pair or one entry in `sniCertificates` is required.
+   *
+   * @category Network
+   */
+  export interface TlsCertificates {
+    /** Default certificate chain in PEM format. */
+    cert?: string;
+    /** Default private key in PEM format. */
+    key?: string;
+    /** Certificates by SNI hostname, see
+     * {@linkcode ListenTlsOptions.sniCertificates}. */
+    sniCertificates?: Record<string, TlsKeyPair>;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Network
+   */
+  export interface TlsListener {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Replaces the certificates presented to new connections, e.g. after
+     * they have been renewed. Connections that were already accepted are not
+     * affected.
+     *
+     * ```ts
+     * const listener = Deno.listenTls({ port: 443, cert, key });
+     * // ...later
+     * listener.updateCertificates({ cert: newCert, key: newKey });
+     * ```
+     */
+    updateCertificates(certificates: TlsCertificates): void;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -1084,37 +1556,666 @@ declare namespace Deno {
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Acquire an advisory file-system lock for the provided file.
+   * A name server to send queries to, see
+   * {@linkcode ResolveDnsOptions.nameServers}.
+   *
+   * @category Network
+   */
+  export interface NameServerOptions {
+    /** The IP address of the name server. */
+    ipAddr: string;
+    /** The port the query will be sent to. Defaults to 53 for `"udp"` and
+     * `"tcp"`, 853 for `"tls"` and 443 for `"https"`. */
+    port?: number;
+    /** The transport used to reach the name server. `"tls"` is DNS-over-TLS
+     * and `"https"` is DNS-over-HTTPS (`/dns-query`, HTTP/2).
+     *
+     * @default {"udp"} */
+    protocol?: "udp" | "tcp" | "tls" | "https";
+    /** The name the server's certificate is verified against. Required for
+     * `"tls"` and `"https"`. */
+    tlsDnsName?: string;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Network
+   */
+  export interface ResolveDnsOptions {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Name servers to query, tried in order. Combined with `nameServer` if
+     * both are given.
+     */
+    nameServers?: NameServerOptions[];
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Additional root certificates in PEM format used to verify `"tls"` and
+     * `"https"` name servers.
+     */
+    caCerts?: string[];
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Time in milliseconds to wait for each answer.
+     *
+     * @default {5000}
+     */
+    timeout?: number;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * How many times a query is sent before giving up.
+     *
+     * @default {2}
+     */
+    attempts?: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Maps each record type to the shape of its data.
+   *
+   * @category Network
+   */
+  export interface DnsRecordData {
+    A: string;
+    AAAA: string;
+    ANAME: string;
+    CAA: CAARecord;
+    CNAME: string;
+    MX: MXRecord;
+    NAPTR: NAPTRRecord;
+    NS: string;
+    PTR: string;
+    SOA: SOARecord;
+    SRV: SRVRecord;
+    TXT: string[];
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A resolved record with its type and time to live.
+   *
+   * @category Network
+   */
+  export type DnsRecordWithTtl<T extends RecordType = RecordType> =
+    T extends RecordType ? {
+        recordType: T;
+        /** Seconds the record may be cached for. */
+        ttl: number;
+        data: DnsRecordData[T];
+      }
+      : never;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Like {@linkcode Deno.resolveDns}, but resolves each record with its type
+   * and TTL. With `"ANY"` all records the name server returns for the name
+   * are resolved; records of types not listed in {@linkcode RecordType} are
+   * skipped. Note that many public resolvers refuse `ANY` queries.
+   *
+   * ```ts
+   * const records = await Deno.resolveDnsRecords("_http._tcp.local", "SRV", {
+   *   nameServers: [{ ipAddr: "10.0.0.2" }],
+   * });
+   * for (const { data, ttl } of records) {
+   *   console.log(data.target, data.port, `valid for ${ttl}s`);
+   * }
+   * ```
+   *
+   * Requires `allow-net` permission.
+   *
+   * @tags allow-net
+   * @category Network
+   */
+  export function resolveDnsRecords<T extends RecordType>(
+    query: string,
+    recordType: T,
+    options?: ResolveDnsOptions,
+  ): Promise<DnsRecordWithTtl<T>[]>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @tags allow-net
+   * @category Network
+   */
+  export function resolveDnsRecords(
+    query: string,
+    recordType: "ANY",
+    options?: ResolveDnsOptions,
+  ): Promise<DnsRecordWithTtl[]>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Acquire an advisory file-system lock for the provided file.
+   *
+   * @param [exclusive=false]
+   * @category File System
+   */
+  export function flock(rid: number, exclusive?: boolean): Promise<void>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Acquire an advisory file-system lock synchronously for the provided file.
+   *
+   * @param [exclusive=false]
+   * @category File System
+   */
+  export function flockSync(rid: number, exclusive?: boolean): void;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Release an advisory file-system lock for the provided file.
+   *
+   * @category File System
+   */
+  export function funlock(rid: number): Promise<void>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Release an advisory file-system lock for the provided file synchronously.
+   *
+   * @category File System
+   */
+  export function funlockSync(rid: number): void;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * These are unstable options which can be used with {@linkcode Deno.watchFs}.
+   *
+   * @category File System
+   */
+  export interface UnstableWatchFsOptions {
+    /** Watch directories and all their sub directories.
+     *
+     * @default {true} */
+    recursive?: boolean;
+    /** Collect events for this many milliseconds before reporting them.
+     * Duplicate events within the window are reported once, and the halves
+     * of a rename are folded into a single `"rename"` event. */
+    debounce?: number;
+    /** Only report events for paths matching one of these globs. Relative
+     * globs are resolved against the current working directory, and a glob
+     * without a path separator matches file names in any directory. */
+    include?: string[];
+    /** Do not report events for paths matching one of these globs. Takes
+     * precedence over `include`. */
+    exclude?: string[];
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A file system event yielded by a watcher created with
+   * {@linkcode UnstableWatchFsOptions}.
+   *
+   * @category File System
+   */
+  export interface UnstableFsEvent extends Omit<FsEvent, "kind"> {
+    /** The kind/type of the file system event. `"rename"` events are only
+     * reported with the `debounce` option, and their `paths` hold the old
+     * path followed by the new path. */
+    kind: FsEvent["kind"] | "rename";
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category File System
+   */
+  export interface UnstableFsWatcher extends AsyncIterable<UnstableFsEvent> {
+    /** The resource id. */
+    readonly rid: number;
+    /** Stops watching the file system and closes the watcher resource. */
+    close(): void;
+    [Symbol.asyncIterator](): AsyncIterableIterator<UnstableFsEvent>;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Watch for file system events against one or more `paths`, with the
+   * unstable options to debounce and filter the reported events.
+   *
+   * ```ts
+   * const watcher = Deno.watchFs("src", {
+   *   debounce: 100,
+   *   include: ["*.ts"],
+   *   exclude: ["src/generated/**"],
+   * });
+   * for await (const event of watcher) {
+   *   console.log(">>>> event", event);
+   * }
+   * ```
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read
+   * @category File System
+   */
+  export function watchFs(
+    paths: string | string[],
+    options: UnstableWatchFsOptions,
+  ): UnstableFsWatcher;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * An entry yielded by {@linkcode Deno.walk} and {@linkcode Deno.glob}.
+   *
+   * @category File System
+   */
+  export interface WalkEntry extends DirEntry {
+    /** The path of the entry, starting with the walked root. */
+    path: string;
+    /** How many directories deep the entry is below the root. */
+    depth: number;
+    /** Information about the entry, only present when the `stat` option is
+     * set. */
+    info?: FileInfo;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category File System
+   */
+  export interface WalkOptions {
+    /** The maximum depth below the root to descend to. Unlimited by default. */
+    maxDepth?: number;
+    /** @default {true} */
+    includeFiles?: boolean;
+    /** @default {true} */
+    includeDirs?: boolean;
+    /** @default {true} */
+    includeSymlinks?: boolean;
+    /** Descend into symlinked directories and report the type of the
+     * symlink's target.
+     *
+     * @default {false} */
+    followSymlinks?: boolean;
+    /** Stat every entry and include the result as `info`.
+     *
+     * @default {false} */
+    stat?: boolean;
+    /** Gitignore-style patterns, relative to the root, of entries to skip.
+     * Excluded directories are not descended into. */
+    exclude?: string[];
+    /** Also skip entries ignored by `.gitignore` files. Only the files inside
+     * the walked directory are read, not those of its parents.
+     *
+     * @default {false} */
+    gitignore?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category File System
+   */
+  export interface GlobOptions extends WalkOptions {
+    /** The directory relative patterns are resolved against. Defaults to the
+     * current working directory. */
+    root?: string | URL;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Recursively walks the directory tree below `root`, including `root`
+   * itself. Entries are read on a separate thread and delivered in batches.
+   *
+   * ```ts
+   * for await (const entry of Deno.walk("src", { exclude: ["*.snap"] })) {
+   *   console.log(entry.path);
+   * }
+   * ```
+   *
+   * Requires `allow-read` permission for `root`.
+   *
+   * @tags allow-read
+   * @category File System
+   */
+  export function walk(
+    root: string | URL,
+    options?: WalkOptions,
+  ): AsyncIterableIterator<WalkEntry>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Yields the entries matching any of the glob `patterns`. Only the
+   * directories the patterns start with are walked. The yielded paths start
+   * with `options.root`, or are absolute when it is not given.
+   *
+   * ```ts
+   * for await (const entry of Deno.glob(["src/**\/*.ts", "*.json"])) {
+   *   console.log(entry.path);
+   * }
+   * ```
+   *
+   * Requires `allow-read` permission for the directory each pattern starts
+   * with.
+   *
+   * @tags allow-read
+   * @category File System
+   */
+  export function glob(
+    patterns: string | string[],
+    options?: GlobOptions,
+  ): AsyncIterableIterator<WalkEntry>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category File System
+   */
+  export interface MmapOptions {
+    /** Offset into the file the mapping starts at.
+     *
+     * @default {0} */
+    offset?: number;
+    /** Number of bytes to map. Defaults to the rest of the file. The mapping
+     * cannot extend past the end of the file. */
+    length?: number;
+    /** Write changes to the buffer back to the file. Otherwise the mapping is
+     * copy-on-write, and changes are only visible to this process.
+     *
+     * @default {false} */
+    writable?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A file mapped into memory with {@linkcode Deno.mmap}.
+   *
+   * @category File System
+   */
+  export class MemoryMap {
+    /** The mapped bytes. Detached once the mapping is unmapped. */
+    readonly buffer: ArrayBuffer;
+    /** Writes changes of a writable mapping back to the file. */
+    flush(): Promise<void>;
+    /** Synchronously writes changes of a writable mapping back to the
+     * file. */
+    flushSync(): void;
+    /** Detaches {@linkcode MemoryMap.buffer} and unmaps the file. Otherwise
+     * the file is unmapped when the buffer is garbage collected. */
+    unmap(): void;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Maps a file into memory without copying it into the JavaScript heap.
+   * `file` is either an open file or a path, which is opened for reading, and
+   * for writing when `options.writable` is set.
+   *
+   * ```ts
+   * const map = Deno.mmap("./index.bin");
+   * const header = new DataView(map.buffer, 0, 16);
+   * ```
+   *
+   * Truncating the file while it is mapped crashes the process when the
+   * truncated pages are accessed.
+   *
+   * Requires `allow-read` permission, and `allow-write` permission for
+   * writable mappings of a path.
+   *
+   * @tags allow-read, allow-write
+   * @category File System
+   */
+  export function mmap(
+    file: string | URL | FsFile | number,
+    options?: MmapOptions,
+  ): MemoryMap;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options which can be set when using {@linkcode Deno.copyFile} and
+   * {@linkcode Deno.copyFileSync}.
+   *
+   * @category File System */
+  export interface CopyFileOptions {
+    /** Also copy the access and modification times and, on Unix, the
+     * extended attributes (including POSIX ACLs) of the source file.
+     * Attributes the target file system does not support, or that need
+     * privileges to set, are skipped.
+     *
+     * @default {false} */
+    preserve?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Copies a file like {@linkcode Deno.copyFile}, optionally preserving its
+   * timestamps and extended attributes.
+   *
+   * ```ts
+   * await Deno.copyFile("from.txt", "to.txt", { preserve: true });
+   * ```
+   *
+   * Requires `allow-read` permission on `fromPath`.
+   *
+   * Requires `allow-write` permission on `toPath`.
+   *
+   * @tags allow-read, allow-write
+   * @category File System
+   */
+  export function copyFile(
+    fromPath: string | URL,
+    toPath: string | URL,
+    options?: CopyFileOptions,
+  ): Promise<void>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Synchronously copies a file like {@linkcode Deno.copyFileSync},
+   * optionally preserving its timestamps and extended attributes.
+   *
+   * ```ts
+   * Deno.copyFileSync("from.txt", "to.txt", { preserve: true });
+   * ```
+   *
+   * Requires `allow-read` permission on `fromPath`.
+   *
+   * Requires `allow-write` permission on `toPath`.
+   *
+   * @tags allow-read, allow-write
+   * @category File System
+   */
+  export function copyFileSync(
+    fromPath: string | URL,
+    toPath: string | URL,
+    options?: CopyFileOptions,
+  ): void;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Returns the value of the extended attribute `name` of a file, or `null`
+   * if it is not set. Symbolic links are not followed. POSIX ACLs can be read
+   * through the `system.posix_acl_access` attribute on Linux.
+   *
+   * ```ts
+   * const value = await Deno.getXattr("./file.txt", "user.checksum");
+   * ```
+   *
+   * Not supported on Windows.
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read
+   * @category File System
+   */
+  export function getXattr(
+    path: string | URL,
+    name: string,
+  ): Promise<Uint8Array | null>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Synchronously returns the value of the extended attribute `name` of a
+   * file, or `null` if it is not set. Symbolic links are not followed.
+   *
+   * Not supported on Windows.
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read
+   * @category File System
+   */
+  export function getXattrSync(
+    path: string | URL,
+    name: string,
+  ): Uint8Array | null;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Sets the extended attribute `name` of a file. Strings are stored UTF-8
+   * encoded. Symbolic links are not followed.
+   *
+   * ```ts
+   * await Deno.setXattr("./file.txt", "user.checksum", "abc123");
+   * ```
+   *
+   * Not supported on Windows.
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write
+   * @category File System
+   */
+  export function setXattr(
+    path: string | URL,
+    name: string,
+    value: Uint8Array | string,
+  ): Promise<void>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Synchronously sets the extended attribute `name` of a file. Strings are
+   * stored UTF-8 encoded. Symbolic links are not followed.
+   *
+   * Not supported on Windows.
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write
+   * @category File System
+   */
+  export function setXattrSync(
+    path: string | URL,
+    name: string,
+    value: Uint8Array | string,
+  ): void;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Lists the names of the extended attributes of a file. Symbolic links are
+   * not followed.
    *
-   * @param [exclusive=false]
+   * Not supported on Windows.
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read
    * @category File System
    */
-  export function flock(rid: number, exclusive?: boolean): Promise<void>;
+  export function listXattr(path: string | URL): Promise<string[]>;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Acquire an advisory file-system lock synchronously for the provided file.
+   * Synchronously lists the names of the extended attributes of a file.
+   * Symbolic links are not followed.
    *
-   * @param [exclusive=false]
+   * Not supported on Windows.
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read
    * @category File System
    */
-  export function flockSync(rid: number, exclusive?: boolean): void;
+  export function listXattrSync(path: string | URL): string[];
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Release an advisory file-system lock for the provided file.
+   * Removes the extended attribute `name` of a file. Symbolic links are not
+   * followed.
    *
+   * Not supported on Windows.
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write
    * @category File System
    */
-  export function funlock(rid: number): Promise<void>;
+  export function removeXattr(path: string | URL, name: string): Promise<void>;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Release an advisory file-system lock for the provided file synchronously.
+   * Synchronously removes the extended attribute `name` of a file. Symbolic
+   * links are not followed.
    *
+   * Not supported on Windows.
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write
    * @category File System
    */
-  export function funlockSync(rid: number): void;
+  export function removeXattrSync(path: string | URL, name: string): void;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category File System
+   */
+  export interface FsFile {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Reads up to `p.byteLength` bytes into `p`, starting at `offset` in the
+     * file. Resolves to the number of bytes read, or `null` at the end of the
+     * file.
+     *
+     * Unlike {@linkcode FsFile.read}, this does not move the cursor of the
+     * file, so concurrent calls do not interfere with each other. On Windows
+     * the cursor is moved and put back afterwards, which a concurrent
+     * {@linkcode FsFile.read} or {@linkcode FsFile.write} may observe.
+     */
+    readAt(p: Uint8Array, offset: number): Promise<number | null>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Synchronous version of {@linkcode FsFile.readAt}.
+     */
+    readAtSync(p: Uint8Array, offset: number): number | null;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Writes up to `p.byteLength` bytes of `p` at `offset` in the file,
+     * without moving the cursor of the file, see {@linkcode FsFile.readAt}.
+     * Resolves to the number of bytes written.
+     */
+    writeAt(p: Uint8Array, offset: number): Promise<number>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Synchronous version of {@linkcode FsFile.writeAt}.
+     */
+    writeAtSync(p: Uint8Array, offset: number): number;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Fills `buffers` in order with the contents of the file starting at
+     * `offset`, in a single operation. Stops at the end of the file, and
+     * resolves to the total number of bytes read, or `null` if `offset` is at
+     * the end of the file. Does not move the cursor of the file, see
+     * {@linkcode FsFile.readAt}.
+     */
+    readv(buffers: Uint8Array[], offset: number): Promise<number | null>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Synchronous version of {@linkcode FsFile.readv}.
+     */
+    readvSync(buffers: Uint8Array[], offset: number): number | null;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Writes all of `buffers` back to back, starting at `offset` in the file,
+     * in a single operation. Does not move the cursor of the file, see
+     * {@linkcode FsFile.readAt}. Resolves to the total number of bytes
+     * written.
+     */
+    writev(buffers: Uint8Array[], offset: number): Promise<number>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Synchronous version of {@linkcode FsFile.writev}.
+     */
+    writevSync(buffers: Uint8Array[], offset: number): number;
+  }
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
@@ -1145,7 +2246,22 @@ declare namespace Deno {
     onError?: (error: unknown) => Response | Promise<Response>;
 
     /** The callback which is called when the server starts listening. */
-    onListen?: (params: { hostname: string; port: number }) => void;
+    onListen?: (params: ServeListenInfo) => void;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Passed to {@linkcode ServeOptions.onListen} once the server is listening.
+   *
+   * @category HTTP Server
+   */
+  export interface ServeListenInfo {
+    hostname: string;
+    port: number;
+    /** Replaces the certificates presented to new connections of a TLS
+     * server, e.g. after they have been renewed. Connections that were
+     * already accepted are not affected. Only set for TLS servers. */
+    updateCertificates?: (certificates: TlsCertificates) => void;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -1160,6 +2276,28 @@ declare namespace Deno {
 
     /** Cert chain in PEM format */
     key: string;
+
+    /** A list of root certificates in PEM format used to verify client
+     * certificates. Required when `requestClientCert` or `requireClientCert`
+     * is set. */
+    caCerts?: string[];
+
+    /** Ask clients for a certificate. Clients without one are still served,
+     * but a certificate that is presented must be signed by one of `caCerts`.
+     *
+     * @default {false} */
+    requestClientCert?: boolean;
+
+    /** Reject clients that don't present a certificate signed by one of
+     * `caCerts`.
+     *
+     * @default {false} */
+    requireClientCert?: boolean;
+
+    /** Certificates to present by the SNI hostname the client asks for.
+     * Names may start with a wildcard label, e.g. `*.example.com`. Clients
+     * asking for any other hostname get `cert`/`key`. */
+    sniCertificates?: Record<string, TlsKeyPair>;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -1406,6 +2544,381 @@ declare namespace Deno {
    * @category HTTP Server
    */
   export function upgradeHttpRaw(request: Request): [Deno.Conn, Uint8Array];
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options which can be set when performing a
+   * {@linkcode Deno.upgradeWebSocketStream} upgrade of a {@linkcode Request}.
+   *
+   * @category Web Sockets
+   */
+  export interface UpgradeWebSocketStreamOptions
+    extends UpgradeWebSocketOptions {
+    /** The number of messages that are read ahead of the consumer of
+     * `readable`. Once reached, no more frames are read from the connection
+     * until the consumer catches up.
+     *
+     * The default is `1`. */
+    readableHighWaterMark?: number;
+    /** The number of messages `writable` queues before signalling
+     * backpressure to its writer.
+     *
+     * The default is `1`. */
+    writableHighWaterMark?: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The object that is returned from a {@linkcode Deno.upgradeWebSocketStream}
+   * request.
+   *
+   * @category Web Sockets
+   */
+  export interface WebSocketStreamUpgrade {
+    /** The response object that should be passed to `respondWith()`, or
+     * returned from the {@linkcode Deno.serve} handler, for the upgrade to be
+     * successful. */
+    response: Response;
+    /** The {@linkcode WebSocketStream} to communicate with the client. Its
+     * `connection` resolves once the response has been sent. */
+    socket: WebSocketStream;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Upgrade an incoming HTTP request to a WebSocket, like
+   * {@linkcode Deno.upgradeWebSocket}, but exposing the connection as a
+   * {@linkcode WebSocketStream}. Unlike the event based {@linkcode WebSocket},
+   * messages are only read from the connection when `readable` is read from,
+   * so a slow consumer applies backpressure to the client.
+   *
+   * ```ts
+   * Deno.serve((req) => {
+   *   const { socket, response } = Deno.upgradeWebSocketStream(req);
+   *   socket.connection.then(async ({ readable, writable }) => {
+   *     await readable.pipeTo(writable);
+   *   });
+   *   return response;
+   * });
+   * ```
+   *
+   * The idle timeout is only counted while a read is pending, so time spent
+   * by the consumer between reads never closes the connection.
+   *
+   * @category Web Sockets
+   */
+  export function upgradeWebSocketStream(
+    request: Request,
+    options?: UpgradeWebSocketStreamOptions,
+  ): WebSocketStreamUpgrade;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Returns the verified certificate chain the client presented on the
+   * connection the request arrived on, or `null` if the client didn't present
+   * one. Client certificates are only requested when the server was started
+   * with `requestClientCert` or `requireClientCert`.
+   *
+   * ```ts
+   * Deno.serve((req) => {
+   *   const [cert] = Deno.peerCertificates(req) ?? [];
+   *   return new Response(cert ? `Hello, ${cert.subject}` : "Who are you?");
+   * }, { cert, key, caCerts: [ca], requestClientCert: true });
+   * ```
+   *
+   * This method can only be called on requests originating the
+   * {@linkcode Deno.serve} server.
+   *
+   * @category HTTP Server
+   */
+  export function peerCertificates(request: Request): PeerCertificate[] | null;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Limits on the resources a worker may use, set through
+   * `new Worker(url, { deno: { resourceLimits } })`.
+   *
+   * @category Web Workers
+   */
+  export interface WorkerResourceLimits {
+    /** Maximum size of the worker's heap in megabytes. When the heap limit
+     * is approached the worker is terminated instead of crashing the
+     * process. */
+    maxHeapSizeMb?: number;
+    /** Maximum CPU time the worker's thread may consume, in milliseconds.
+     * Only supported on Linux. */
+    maxCpuTimeMs?: number;
+    /** Maximum time the worker may run for, in milliseconds. */
+    maxWallTimeMs?: number;
+    /** Maximum number of resources (files, sockets, etc.) the worker may have
+     * open at the same time, including its stdio resources. The limit is
+     * checked between turns of the worker's event loop, so resources opened
+     * synchronously may briefly exceed it before the worker is terminated. */
+    maxResources?: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The size of a pseudo-terminal, in character cells.
+   *
+   * @category Sub Process
+   */
+  export interface PtySize {
+    rows: number;
+    columns: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Sub Process
+   */
+  export interface CommandOptions {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Attach the child to a new pseudo-terminal instead of using `stdin`,
+     * `stdout` and `stderr`. The child becomes the leader of a new session
+     * with the pty as its controlling terminal, so interactive programs and
+     * colored output work as they would in a terminal. Its input is written
+     * to {@linkcode ChildProcess.stdin} and everything it prints is read from
+     * {@linkcode ChildProcess.stdout}; `stderr` is not available separately.
+     * Setting `stdin`, `stdout` or `stderr` along with `pty` throws a
+     * `TypeError`.
+     *
+     * `true` opens a pty of 24 rows and 80 columns. Not supported on Windows
+     * or by {@linkcode Deno.Command.outputSync}.
+     *
+     * @default {false}
+     */
+    pty?: boolean | PtySize;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Open a message channel to the child. Values are serialized with the
+     * structured clone algorithm, and a serialized message may be at most
+     * 64 MiB. The child receives them through {@linkcode Deno.onmessage} and
+     * replies with {@linkcode Deno.postMessage}.
+     *
+     * Not supported on Windows.
+     *
+     * @default {false}
+     */
+    ipc?: boolean;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Run the child in a new session and process group, and do not kill it
+     * when the {@linkcode Deno.ChildProcess} is garbage collected or this
+     * process exits. Implies `processGroup`.
+     *
+     * @default {false}
+     */
+    detached?: boolean;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Make the child the leader of a new process group, so that it and every
+     * process it spawns can be signalled at once with
+     * `child.kill(signo, { group: true })`.
+     *
+     * @default {false}
+     */
+    processGroup?: boolean;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Resource limits to apply to the child. Not supported on Windows.
+     */
+    limits?: ResourceLimits;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Resource limits of a child process, see `setrlimit(2)`. Both the soft and
+   * the hard limit are set to the given value.
+   *
+   * @category Sub Process
+   */
+  export interface ResourceLimits {
+    /** CPU time in seconds, after which the child receives `SIGXCPU`. */
+    cpu?: number;
+    /** Maximum size of the virtual address space, in bytes. */
+    addressSpace?: number;
+    /** Maximum number of open file descriptors. */
+    openFiles?: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Sub Process
+   */
+  export interface ChildProcessKillOptions {
+    /** Signal the child's whole process group rather than only the child.
+     * Requires the child to be spawned with `processGroup` or `detached`.
+     * Throws once the child has exited, since the id of its process group
+     * may have been reused by then.
+     *
+     * Not supported on Windows, where it throws
+     * {@linkcode Deno.errors.NotSupported}.
+     *
+     * @default {false}
+     */
+    group?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Sub Process
+   */
+  export interface ChildProcess {
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Resizes the pseudo-terminal of a child spawned with the `pty` option,
+     * delivering `SIGWINCH` to it.
+     */
+    resizePty(size: PtySize): void;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Kills the child, or its whole process group when `options.group` is
+     * set.
+     */
+    kill(signo: Signal | undefined, options: ChildProcessKillOptions): void;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Sends a message over the IPC channel of a child spawned with the `ipc`
+     * option. Throws a `RangeError` if the serialized message is larger than
+     * 64 MiB. The returned promise resolves once the message was written, and
+     * rejects if it couldn't be, e.g. because the child exited.
+     */
+    postMessage(message: unknown): Promise<void>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Handler for messages the child sends with {@linkcode Deno.postMessage}.
+     */
+    onmessage: ((event: MessageEvent) => void) | null;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Handler for messages from the child that can't be deserialized. Also
+     * called, and the channel closed, when reading from the channel fails.
+     */
+    onmessageerror: ((event: MessageEvent) => void) | null;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Closes the IPC channel. The child observes end of input on its side of
+     * the channel.
+     */
+    disconnect(): void;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Sends a message to the parent process over the IPC channel this process
+   * was spawned with. Throws a `TypeError` if there is no such channel, and a
+   * `RangeError` if the serialized message is larger than 64 MiB. The
+   * returned promise resolves once the message was written, and rejects if it
+   * couldn't be, e.g. because the parent went away.
+   *
+   * @category Sub Process
+   */
+  export function postMessage(message: unknown): Promise<void>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Handler for messages the parent process sends over the IPC channel this
+   * process was spawned with. Once a handler is set, the process stays alive
+   * until the parent disconnects.
+   *
+   * @category Sub Process
+   */
+  export let onmessage: ((event: MessageEvent) => void) | null;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Handler for messages from the parent process that can't be deserialized.
+   * Also called, and the channel closed, when reading from the channel fails.
+   *
+   * @category Sub Process
+   */
+  export let onmessageerror: ((event: MessageEvent) => void) | null;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Details about a signal delivery, passed to listeners registered with
+   * {@linkcode Deno.addSignalListener}.
+   *
+   * @category Runtime Environment
+   */
+  export interface SignalInfo {
+    /** The name of the delivered signal. */
+    signal: Signal;
+    /** The platform specific number of the delivered signal. */
+    signo: number;
+    /** The id of the process that sent the signal, or of the child that
+     * changed state for `"SIGCHLD"`. `null` when the signal was not sent by a
+     * process, e.g. `"SIGWINCH"` raised by the terminal, and always on
+     * Windows. */
+    pid: number | null;
+    /** The real user id of the sending process, or `null` together with
+     * `pid`. */
+    uid: number | null;
+    /** The raw `si_code` of the delivery, or `null` on Windows. */
+    code: number | null;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Registers the given function as a listener of the given signal event.
+   * The listener receives details about each delivery.
+   *
+   * ```ts
+   * Deno.addSignalListener("SIGUSR1", ({ pid }) => {
+   *   console.log(`SIGUSR1 from ${pid}`);
+   * });
+   * ```
+   *
+   * _Note_: Pending deliveries of the same signal may be coalesced, in which
+   * case the listener only sees the details of one of them.
+   *
+   * _Note_: Listening to `"SIGCHLD"` does not reap the child. Children spawned
+   * with {@linkcode Deno.Command} are reaped by the runtime, and their exit
+   * status is reported by {@linkcode Deno.ChildProcess.status}; since a
+   * coalesced delivery names only one of the children, check the status of
+   * every child of interest.
+   *
+   * @category Runtime Environment
+   */
+  export function addSignalListener(
+    signal: Signal,
+    handler: (info: SignalInfo) => void,
+  ): void;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * @category Runtime Environment
+   */
+  export interface RemoveSignalListenerOptions {
+    /** Once no listeners are left for the signal, take its default action
+     * again (for example terminating the process on `"SIGTERM"`) instead of
+     * ignoring further deliveries. Has no effect on Windows.
+     *
+     * @default {false}
+     */
+    restoreDefault?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Removes the given signal listener that has been registered with
+   * {@linkcode Deno.addSignalListener}.
+   *
+   * ```ts
+   * const listener = () => {};
+   * Deno.addSignalListener("SIGTERM", listener);
+   * Deno.removeSignalListener("SIGTERM", listener, { restoreDefault: true });
+   * ```
+   *
+   * @category Runtime Environment
+   */
+  export function removeSignalListener(
+    signal: Signal,
+    handler: (info: SignalInfo) => void,
+    options?: RemoveSignalListenerOptions,
+  ): void;
 }
 
 /** **UNSTABLE**: New API, yet to be vetted.
@@ -1459,6 +2972,10 @@ declare interface WorkerOptions {
   deno?: {
     /** Set to `"none"` to disable all the permissions in the worker. */
     permissions?: Deno.PermissionOptions;
+    /** Limits on the resources the worker may use. If any of them is
+     * exceeded the worker is terminated and an `error` event describing the
+     * exceeded limit is dispatched on the `Worker` object. */
+    resourceLimits?: Deno.WorkerResourceLimits;
   };
 }
 
@@ -1470,6 +2987,12 @@ declare interface WebSocketStreamOptions {
   protocols?: string[];
   signal?: AbortSignal;
   headers?: HeadersInit;
+  /** The number of messages that are read ahead of the consumer of
+   * `readable`. The default is `1`. */
+  readableHighWaterMark?: number;
+  /** The number of messages `writable` queues before signalling
+   * backpressure. The default is `1`. */
+  writableHighWaterMark?: number;
 }
 
 /** **UNSTABLE**: New API, yet to be vetted.
@@ -1503,4 +3026,106 @@ declare class WebSocketStream {
   connection: Promise<WebSocketConnection>;
   closed: Promise<WebSocketCloseInfo>;
   close(closeInfo?: WebSocketCloseInfo): void;
+  /** Sends a ping frame. The returned promise resolves once the next pong
+   * frame has been read from `readable`'s connection, and rejects if the
+   * connection is closed before that. */
+  ping(): Promise<void>;
+}
+
+/** **UNSTABLE**: New API, yet to be vetted.
+ *
+ * @category Fetch API
+ */
+declare interface EventSourceInit {
+  withCredentials?: boolean;
+  /** Additional headers to send with each request, for example for
+   * authorization. `Accept` and `Last-Event-ID` are always set by the
+   * `EventSource` itself. This is a Deno extension. */
+  headers?: HeadersInit;
+  /** An HTTP client to use for the requests. This is a Deno extension. */
+  client?: Deno.HttpClient;
+}
+
+/** **UNSTABLE**: New API, yet to be vetted.
+ *
+ * @category Fetch API
+ */
+declare interface EventSourceEventMap {
+  error: Event;
+  message: MessageEvent;
+  open: Event;
+}
+
+/** **UNSTABLE**: New API, yet to be vetted.
+ *
+ * A client for [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html).
+ * It keeps an HTTP request open and dispatches a `MessageEvent` for every
+ * event the server sends. If the connection drops, it is reestablished after
+ * the reconnection time (3 seconds, or what the server sent in a `retry:`
+ * field), sending the id of the last event in the `Last-Event-ID` header.
+ *
+ * ```ts
+ * const source = new EventSource("https://example.com/events", {
+ *   headers: { authorization: "Bearer token" },
+ * });
+ * source.onmessage = (e) => console.log(e.lastEventId, e.data);
+ * source.addEventListener("update", (e) => console.log(e.data));
+ * ```
+ *
+ * @tags allow-net
+ * @category Fetch API
+ */
+declare class EventSource extends EventTarget {
+  constructor(url: string | URL, eventSourceInitDict?: EventSourceInit);
+
+  static readonly CONNECTING: number;
+  static readonly OPEN: number;
+  static readonly CLOSED: number;
+
+  onerror: ((this: EventSource, ev: Event) => any) | null;
+  onmessage: ((this: EventSource, ev: MessageEvent) => any) | null;
+  onopen: ((this: EventSource, ev: Event) => any) | null;
+  /** Returns the state of the connection, one of `CONNECTING`, `OPEN` and
+   * `CLOSED`. */
+  readonly readyState: number;
+  /** Returns the URL providing the event stream. */
+  readonly url: string;
+  /** Returns `true` if the credentials mode for the connection requests is
+   * `"include"`, and `false` otherwise. */
+  readonly withCredentials: boolean;
+  /** Aborts any request and stops reconnecting. */
+  close(): void;
+  readonly CONNECTING: number;
+  readonly OPEN: number;
+  readonly CLOSED: number;
+  addEventListener<K extends keyof EventSourceEventMap>(
+    type: K,
+    listener: (this: EventSource, ev: EventSourceEventMap[K]) => any,
+    options?: boolean | AddEventListenerOptions,
+  ): void;
+  addEventListener(
+    type: string,
+    listener: (this: EventSource, event: MessageEvent) => any,
+    options?: boolean | AddEventListenerOptions,
+  ): void;
+  addEventListener(
+    type: string,
+    listener: EventListenerOrEventListenerObject,
+    options?: boolean | AddEventListenerOptions,
+  ): void;
+  removeEventListener<K extends keyof EventSourceEventMap>(
+    type: K,
+    listener: (this: EventSource, ev: EventSourceEventMap[K]) => any,
+    options?: boolean | EventListenerOptions,
+  ): void;
+  removeEventListener(
+    type: string,
+    listener: (this: EventSource, event: MessageEvent) => any,
+    options?: boolean | EventListenerOptions,
+  ): void;
+  removeEventListener(
+    type: string,
+    listener: EventListenerOrEventListenerObject,
+    options?: boolean | EventListenerOptions,
+  ): void;
 }
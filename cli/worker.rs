@@ -547,6 +547,7 @@ async fn create_main_worker_internal(
     shared_array_buffer_store: Some(ps.shared_array_buffer_store.clone()),
     compiled_wasm_module_store: Some(ps.compiled_wasm_module_store.clone()),
     stdio,
+    resource_limits: Default::default(),
   };
 
   let mut worker = MainWorker::bootstrap_from_options(
@@ -712,6 +713,7 @@ fn create_web_worker_callback(
       compiled_wasm_module_store: Some(ps.compiled_wasm_module_store.clone()),
       stdio: stdio.clone(),
       cache_storage_dir,
+      resource_limits: args.resource_limits,
     };
 
     WebWorker::bootstrap_from_options(
@@ -777,6 +779,7 @@ mod tests {
       shared_array_buffer_store: None,
       compiled_wasm_module_store: None,
       stdio: Default::default(),
+      resource_limits: Default::default(),
     };
 
     MainWorker::bootstrap_from_options(main_module, permissions, options)
@@ -327,6 +327,33 @@ impl<'a> v8::ValueSerializerImpl for SerializeDeserialize<'a> {
     }
   }
 
+  fn has_custom_host_object(&mut self, _isolate: &mut v8::Isolate) -> bool {
+    self.host_objects.is_some()
+  }
+
+  /// Objects in `host_objects` are written as host objects even if they are
+  /// plain JS objects, e.g. transferred streams, so they are serialized as a
+  /// reference instead of being cloned. Objects with internal fields remain
+  /// host objects, as they are without a custom check.
+  fn is_host_object<'s>(
+    &mut self,
+    scope: &mut v8::HandleScope<'s>,
+    object: v8::Local<'s, v8::Object>,
+  ) -> Option<bool> {
+    if object.internal_field_count() > 0 {
+      return Some(true);
+    }
+    if let Some(host_objects) = self.host_objects {
+      for i in 0..host_objects.length() {
+        let value = host_objects.get_index(scope, i).unwrap();
+        if value == object {
+          return Some(true);
+        }
+      }
+    }
+    Some(false)
+  }
+
   fn write_host_object<'s>(
     &mut self,
     scope: &mut v8::HandleScope<'s>,
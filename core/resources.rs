@@ -233,6 +233,16 @@ impl ResourceTable {
     self.add_rc(Rc::new(resource))
   }
 
+  /// Returns the number of resources in the table.
+  pub fn len(&self) -> usize {
+    self.index.len()
+  }
+
+  /// Returns `true` if the table holds no resources.
+  pub fn is_empty(&self) -> bool {
+    self.index.is_empty()
+  }
+
   /// Inserts a `Rc`-wrapped resource into the resource table.
   ///
   /// The resource type is erased at runtime and must be statically known
@@ -12,6 +12,8 @@
 
 const core = globalThis.Deno.core;
 const ops = core.ops;
+const primordials = globalThis.__bootstrap.primordials;
+const { ArrayIsArray, ObjectKeys } = primordials;
 
 /**
  * @param {Deno.CreateHttpClientOptions} options
@@ -19,6 +21,14 @@ const ops = core.ops;
  */
 function createHttpClient(options) {
   options.caCerts ??= [];
+  if (options.resolve !== undefined) {
+    const resolve = {};
+    for (const host of ObjectKeys(options.resolve)) {
+      const addrs = options.resolve[host];
+      resolve[host] = ArrayIsArray(addrs) ? addrs : [addrs];
+    }
+    options = { ...options, resolve };
+  }
   return new HttpClient(
     ops.op_fetch_custom_client(
       options,
@@ -0,0 +1,414 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+/// <reference path="../../core/internal.d.ts" />
+/// <reference path="../web/internal.d.ts" />
+/// <reference path="../web/lib.deno_web.d.ts" />
+/// <reference path="./internal.d.ts" />
+/// <reference path="./lib.deno_fetch.d.ts" />
+/// <reference lib="esnext" />
+
+import * as webidl from "internal:deno_webidl/00_webidl.js";
+import { URL } from "internal:deno_url/00_url.js";
+import DOMException from "internal:deno_web/01_dom_exception.js";
+import {
+  defineEventHandler,
+  Event,
+  EventTarget,
+  MessageEvent,
+} from "internal:deno_web/02_event.js";
+import {
+  AbortController,
+  add,
+  remove,
+} from "internal:deno_web/03_abort_signal.js";
+import { essence, parseMimeType } from "internal:deno_web/01_mimesniff.js";
+import { TextDecoderStream } from "internal:deno_web/08_text_encoding.js";
+import { getLocationHref } from "internal:deno_web/12_location.js";
+import { Headers } from "internal:deno_fetch/20_headers.js";
+import { HttpClientPrototype } from "internal:deno_fetch/22_http_client.js";
+import { fetch } from "internal:deno_fetch/26_fetch.js";
+const primordials = globalThis.__bootstrap.primordials;
+const {
+  NumberParseInt,
+  ObjectDefineProperties,
+  ObjectPrototypeIsPrototypeOf,
+  Promise,
+  RegExpPrototypeTest,
+  StringPrototypeCharCodeAt,
+  StringPrototypeIncludes,
+  StringPrototypeIndexOf,
+  StringPrototypeSlice,
+  StringPrototypeStartsWith,
+  Symbol,
+  SymbolFor,
+  TypeErrorPrototype,
+} = primordials;
+
+webidl.converters.EventSourceInit = webidl.createDictionaryConverter(
+  "EventSourceInit",
+  [
+    {
+      key: "withCredentials",
+      converter: webidl.converters.boolean,
+      defaultValue: false,
+    },
+    // Deno extensions, not part of the spec.
+    { key: "headers", converter: webidl.converters.HeadersInit },
+    { key: "client", converter: webidl.converters.any },
+  ],
+);
+
+const CONNECTING = 0;
+const OPEN = 1;
+const CLOSED = 2;
+
+/** Reconnection time in milliseconds until the server sends `retry:`. */
+const DEFAULT_RECONNECTION_TIME = 3000;
+
+const LF = 10;
+const CR = 13;
+const DIGITS_RE = /^[0-9]+$/;
+
+const _readyState = Symbol("[[readyState]]");
+const _url = Symbol("[[url]]");
+const _withCredentials = Symbol("[[withCredentials]]");
+const _headers = Symbol("[[headers]]");
+const _client = Symbol("[[client]]");
+const _abortController = Symbol("[[abortController]]");
+const _lastEventId = Symbol("[[lastEventId]]");
+const _reconnectionTime = Symbol("[[reconnectionTime]]");
+const _loop = Symbol("[[loop]]");
+const _connect = Symbol("[[connect]]");
+
+class EventSource extends EventTarget {
+  [_readyState] = CONNECTING;
+  get readyState() {
+    webidl.assertBranded(this, EventSourcePrototype);
+    return this[_readyState];
+  }
+
+  get CONNECTING() {
+    webidl.assertBranded(this, EventSourcePrototype);
+    return CONNECTING;
+  }
+  get OPEN() {
+    webidl.assertBranded(this, EventSourcePrototype);
+    return OPEN;
+  }
+  get CLOSED() {
+    webidl.assertBranded(this, EventSourcePrototype);
+    return CLOSED;
+  }
+
+  [_url];
+  get url() {
+    webidl.assertBranded(this, EventSourcePrototype);
+    return this[_url];
+  }
+
+  [_withCredentials];
+  get withCredentials() {
+    webidl.assertBranded(this, EventSourcePrototype);
+    return this[_withCredentials];
+  }
+
+  [_headers];
+  [_client];
+  [_abortController] = new AbortController();
+  [_lastEventId] = "";
+  [_reconnectionTime] = DEFAULT_RECONNECTION_TIME;
+
+  constructor(url, eventSourceInitDict = {}) {
+    super();
+    this[webidl.brand] = webidl.brand;
+    const prefix = "Failed to construct 'EventSource'";
+    webidl.requiredArguments(arguments.length, 1, { prefix });
+    url = webidl.converters.USVString(url, {
+      prefix,
+      context: "Argument 1",
+    });
+    eventSourceInitDict = webidl.converters.EventSourceInit(
+      eventSourceInitDict,
+      {
+        prefix,
+        context: "Argument 2",
+      },
+    );
+
+    try {
+      url = new URL(url, getLocationHref()).href;
+    } catch (e) {
+      throw new DOMException(e.message, "SyntaxError");
+    }
+
+    this[_url] = url;
+    this[_withCredentials] = eventSourceInitDict.withCredentials;
+    // Validated here so that invalid headers throw from the constructor
+    // rather than failing the connection.
+    this[_headers] = new Headers(eventSourceInitDict.headers);
+    const client = eventSourceInitDict.client;
+    if (
+      client !== undefined && client !== null &&
+      !ObjectPrototypeIsPrototypeOf(HttpClientPrototype, client)
+    ) {
+      throw webidl.makeException(
+        TypeError,
+        "is not of type 'Deno.HttpClient'.",
+        { prefix, context: "'client' of 'EventSourceInit' (Argument 2)" },
+      );
+    }
+    this[_client] = client;
+
+    this[_loop]();
+  }
+
+  close() {
+    webidl.assertBranded(this, EventSourcePrototype);
+    this[_abortController].abort();
+    this[_readyState] = CLOSED;
+  }
+
+  async [_loop]() {
+    while (this[_readyState] !== CLOSED) {
+      let reestablish;
+      try {
+        reestablish = await this[_connect]();
+      } catch (error) {
+        // Network errors are TypeErrors, and the connection is reestablished
+        // after them. Any other error, e.g. a missing net permission, or the
+        // abort by `close()`, fails the connection.
+        reestablish = ObjectPrototypeIsPrototypeOf(TypeErrorPrototype, error);
+      }
+      if (this[_readyState] === CLOSED) {
+        return;
+      }
+      if (!reestablish) {
+        // Fail the connection.
+        this[_readyState] = CLOSED;
+        this.dispatchEvent(new Event("error"));
+        return;
+      }
+
+      this[_readyState] = CONNECTING;
+      this.dispatchEvent(new Event("error"));
+      const signal = this[_abortController].signal;
+      await new Promise((resolve) => {
+        const onAbort = () => {
+          clearTimeout(timeout);
+          resolve();
+        };
+        const timeout = setTimeout(() => {
+          signal[remove](onAbort);
+          resolve();
+        }, this[_reconnectionTime]);
+        signal[add](onAbort);
+      });
+    }
+  }
+
+  /**
+   * Performs one request and processes the event stream until it ends.
+   * @returns {Promise<boolean>} whether to reestablish the connection
+   */
+  async [_connect]() {
+    const headers = new Headers(this[_headers]);
+    headers.set("accept", "text/event-stream");
+    if (this[_lastEventId] !== "") {
+      headers.set("last-event-id", this[_lastEventId]);
+    }
+
+    const response = await fetch(this[_url], {
+      headers,
+      signal: this[_abortController].signal,
+      client: this[_client],
+    });
+
+    const contentType = response.headers.get("content-type");
+    const mimeType = contentType !== null ? parseMimeType(contentType) : null;
+    if (
+      response.status !== 200 || mimeType === null ||
+      essence(mimeType) !== "text/event-stream"
+    ) {
+      await response.body?.cancel();
+      return false;
+    }
+
+    if (this[_readyState] === CLOSED) {
+      await response.body?.cancel();
+      return false;
+    }
+    this[_readyState] = OPEN;
+    this.dispatchEvent(new Event("open"));
+
+    const origin = new URL(response.url).origin;
+    const parser = new EventStreamParser((field, value) => {
+      switch (field) {
+        case "id":
+          this[_lastEventId] = value;
+          break;
+        case "retry":
+          this[_reconnectionTime] = value;
+          break;
+        case "dispatch":
+          if (this[_readyState] !== CLOSED) {
+            this.dispatchEvent(
+              new MessageEvent(value.type || "message", {
+                data: value.data,
+                origin,
+                lastEventId: this[_lastEventId],
+              }),
+            );
+          }
+          break;
+      }
+    });
+
+    const reader = response.body
+      .pipeThrough(new TextDecoderStream())
+      .getReader();
+    while (true) {
+      const { value, done } = await reader.read();
+      if (done) {
+        break;
+      }
+      parser.push(value);
+    }
+    return true;
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect) {
+    return `${this.constructor.name} ${
+      inspect({
+        readyState: this.readyState,
+        url: this.url,
+        withCredentials: this.withCredentials,
+      })
+    }`;
+  }
+}
+
+ObjectDefineProperties(EventSource, {
+  CONNECTING: {
+    value: CONNECTING,
+  },
+  OPEN: {
+    value: OPEN,
+  },
+  CLOSED: {
+    value: CLOSED,
+  },
+});
+
+defineEventHandler(EventSource.prototype, "open");
+defineEventHandler(EventSource.prototype, "message");
+defineEventHandler(EventSource.prototype, "error");
+
+webidl.configurePrototype(EventSource);
+const EventSourcePrototype = EventSource.prototype;
+
+/**
+ * Incremental parser for the `text/event-stream` format, see
+ * https://html.spec.whatwg.org/multipage/server-sent-events.html#event-stream-interpretation
+ */
+class EventStreamParser {
+  #buffer = "";
+  #skipLF = false;
+  #data = "";
+  #type = "";
+  #lastEventId = "";
+  #onField;
+
+  /**
+   * @param {(field: "id" | "retry" | "dispatch", value: any) => void} onField
+   */
+  constructor(onField) {
+    this.#onField = onField;
+  }
+
+  /** @param {string} chunk decoded text, with the BOM already stripped */
+  push(chunk) {
+    let start = 0;
+    if (this.#skipLF && StringPrototypeCharCodeAt(chunk, 0) === LF) {
+      start = 1;
+    }
+    this.#skipLF = false;
+
+    for (let i = start; i < chunk.length; i++) {
+      const c = StringPrototypeCharCodeAt(chunk, i);
+      if (c !== LF && c !== CR) {
+        continue;
+      }
+      const line = this.#buffer + StringPrototypeSlice(chunk, start, i);
+      this.#buffer = "";
+      this.#processLine(line);
+      if (c === CR) {
+        if (i + 1 === chunk.length) {
+          this.#skipLF = true;
+        } else if (StringPrototypeCharCodeAt(chunk, i + 1) === LF) {
+          i++;
+        }
+      }
+      start = i + 1;
+    }
+    this.#buffer += StringPrototypeSlice(chunk, start);
+  }
+
+  #processLine(line) {
+    if (line === "") {
+      this.#dispatch();
+      return;
+    }
+    if (StringPrototypeStartsWith(line, ":")) {
+      return;
+    }
+
+    let field = line;
+    let value = "";
+    const colon = StringPrototypeIndexOf(line, ":");
+    if (colon !== -1) {
+      field = StringPrototypeSlice(line, 0, colon);
+      value = StringPrototypeSlice(
+        line,
+        StringPrototypeCharCodeAt(line, colon + 1) === 32
+          ? colon + 2
+          : colon + 1,
+      );
+    }
+
+    switch (field) {
+      case "event":
+        this.#type = value;
+        break;
+      case "data":
+        this.#data += value + "\n";
+        break;
+      case "id":
+        if (!StringPrototypeIncludes(value, "\0")) {
+          this.#lastEventId = value;
+        }
+        break;
+      case "retry":
+        if (RegExpPrototypeTest(DIGITS_RE, value)) {
+          this.#onField("retry", NumberParseInt(value, 10));
+        }
+        break;
+    }
+  }
+
+  #dispatch() {
+    const data = this.#data;
+    const type = this.#type;
+    this.#data = "";
+    this.#type = "";
+    this.#onField("id", this.#lastEventId);
+    if (data === "") {
+      return;
+    }
+    this.#onField("dispatch", {
+      type,
+      data: StringPrototypeSlice(data, 0, -1),
+    });
+  }
+}
+
+export { EventSource, EventStreamParser };
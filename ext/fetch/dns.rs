@@ -0,0 +1,30 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use deno_net::resolver::TokioAsyncResolver;
+use reqwest::dns::Addrs;
+use reqwest::dns::Name;
+use reqwest::dns::Resolve;
+use reqwest::dns::Resolving;
+use std::net::SocketAddr;
+
+/// Resolves host names for a `Deno.HttpClient` created with a custom
+/// `resolver`.
+pub struct CustomResolver(pub TokioAsyncResolver);
+
+impl Resolve for CustomResolver {
+  fn resolve(&self, name: Name) -> Resolving {
+    let resolver = self.0.clone();
+    Box::pin(async move {
+      let lookup = resolver.lookup_ip(name.as_str()).await?;
+      // The port is replaced by the one of the request URL.
+      let addrs: Addrs = Box::new(
+        lookup
+          .iter()
+          .map(|ip| SocketAddr::new(ip, 0))
+          .collect::<Vec<_>>()
+          .into_iter(),
+      );
+      Ok(addrs)
+    })
+  }
+}
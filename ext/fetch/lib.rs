@@ -1,7 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 mod byte_stream;
+mod dns;
 mod fs_fetch_handler;
+mod retry;
 
 use data_url::DataUrl;
 use deno_core::error::type_error;
@@ -29,6 +31,9 @@ use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::ZeroCopyBuf;
+use deno_net::resolver::HostOverrides;
+use deno_net::resolver::ResolverOptions;
+use deno_net::resolver::TokioAsyncResolver;
 use deno_tls::rustls::RootCertStore;
 use deno_tls::Proxy;
 use http::header::CONTENT_LENGTH;
@@ -43,6 +48,7 @@ use reqwest::header::USER_AGENT;
 use reqwest::redirect::Policy;
 use reqwest::Body;
 use reqwest::Client;
+use reqwest::ClientBuilder;
 use reqwest::Method;
 use reqwest::RequestBuilder;
 use reqwest::Response;
@@ -51,11 +57,15 @@ use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::cmp::min;
+use std::collections::HashMap;
 use std::convert::From;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 // Re-export reqwest and data_url
@@ -65,6 +75,7 @@ pub use reqwest;
 pub use fs_fetch_handler::FsFetchHandler;
 
 use crate::byte_stream::MpscByteStream;
+use crate::retry::RetryOptions;
 
 #[derive(Clone)]
 pub struct Options {
@@ -105,6 +116,7 @@ where
       "23_request.js",
       "23_response.js",
       "26_fetch.js",
+      "27_eventsource.js",
     ))
     .ops(vec![
       op_fetch::decl::<FP>(),
@@ -206,13 +218,19 @@ pub fn op_fetch<FP>(
 where
   FP: FetchPermissions + 'static,
 {
-  let client = if let Some(rid) = client_rid {
-    let r = state.resource_table.get::<HttpClientResource>(rid)?;
-    r.client.clone()
-  } else {
-    let client = state.borrow::<reqwest::Client>();
-    client.clone()
-  };
+  let (client, read_timeout, retry_options, overrides) =
+    if let Some(rid) = client_rid {
+      let r = state.resource_table.get::<HttpClientResource>(rid)?;
+      (
+        r.client.clone(),
+        r.read_timeout,
+        r.retry.clone(),
+        Some(r.overrides.clone()),
+      )
+    } else {
+      let client = state.borrow::<reqwest::Client>();
+      (client.clone(), None, None, None)
+    };
 
   let method = Method::from_bytes(&method)?;
   let url = Url::parse(&url)?;
@@ -239,7 +257,9 @@ where
       let file_fetch_handler = file_fetch_handler.clone();
       let (request, maybe_request_body, maybe_cancel_handle) =
         file_fetch_handler.fetch_file(state, url);
-      let request_rid = state.resource_table.add(FetchRequestResource(request));
+      let request_rid = state
+        .resource_table
+        .add(FetchRequestResource(request, None));
       let maybe_request_body_rid =
         maybe_request_body.map(|r| state.resource_table.add(r));
       let maybe_cancel_handle_rid = maybe_cancel_handle
@@ -250,6 +270,18 @@ where
     "http" | "https" => {
       let permissions = state.borrow_mut::<FP>();
       permissions.check_net_url(&url, "fetch()")?;
+      // The client connects to the addresses the host is overridden with
+      // instead, so they need permission of their own.
+      let override_addrs = overrides
+        .as_ref()
+        .and_then(|overrides| overrides.get(url.host_str()?));
+      for ip in override_addrs.unwrap_or_default() {
+        let mut override_url = url.clone();
+        override_url
+          .set_ip_host(*ip)
+          .map_err(|_| type_error("Invalid URL"))?;
+        permissions.check_net_url(&override_url, "fetch()")?;
+      }
 
       // Make sure that we have a valid URI early, as reqwest's `RequestBuilder::send`
       // internally uses `expect_uri`, which panics instead of returning a usable `Result`.
@@ -325,17 +357,21 @@ where
       let cancel_handle = CancelHandle::new_rc();
       let cancel_handle_ = cancel_handle.clone();
 
+      let retry_options =
+        retry_options.filter(|_| retry::is_idempotent(&method));
       let fut = async move {
-        request
-          .send()
-          .or_cancel(cancel_handle_)
-          .await
-          .map(|res| res.map_err(|err| type_error(err.to_string())))
-      };
+        match retry_options {
+          Some(options) => {
+            retry::send_with_retries(request, read_timeout, &options).await
+          }
+          None => retry::send(request, read_timeout).await,
+        }
+      }
+      .or_cancel(cancel_handle_);
 
       let request_rid = state
         .resource_table
-        .add(FetchRequestResource(Box::pin(fut)));
+        .add(FetchRequestResource(Box::pin(fut), read_timeout));
 
       let cancel_handle_rid =
         state.resource_table.add(FetchCancelHandle(cancel_handle));
@@ -359,7 +395,7 @@ where
 
       let request_rid = state
         .resource_table
-        .add(FetchRequestResource(Box::pin(fut)));
+        .add(FetchRequestResource(Box::pin(fut), None));
 
       (request_rid, None, None)
     }
@@ -403,6 +439,7 @@ pub async fn op_fetch_send(
     .ok()
     .expect("multiple op_fetch_send ongoing");
 
+  let read_timeout = request.1;
   let res = match request.0.await {
     Ok(Ok(res)) => res,
     Ok(Err(err)) => return Err(type_error(err.to_string())),
@@ -422,6 +459,21 @@ pub async fn op_fetch_send(
   let stream: BytesStream = Box::pin(res.bytes_stream().map(|r| {
     r.map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
   }));
+  let stream: BytesStream = match read_timeout {
+    Some(read_timeout) => {
+      let stream =
+        Box::pin(tokio_stream::StreamExt::timeout(stream, read_timeout));
+      Box::pin(stream.map(|r| {
+        r.unwrap_or_else(|_| {
+          Err(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "Timed out reading the response body",
+          ))
+        })
+      }))
+    }
+    None => stream,
+  };
   let rid = state
     .borrow_mut()
     .resource_table
@@ -443,8 +495,11 @@ pub async fn op_fetch_send(
 
 type CancelableResponseResult = Result<Result<Response, AnyError>, Canceled>;
 
+/// A pending request, together with the time to wait for each chunk of the
+/// response body.
 struct FetchRequestResource(
   Pin<Box<dyn Future<Output = CancelableResponseResult>>>,
+  Option<Duration>,
 );
 
 impl Resource for FetchRequestResource {
@@ -573,6 +628,9 @@ impl Resource for FetchResponseBodyResource {
 
 struct HttpClientResource {
   client: Client,
+  read_timeout: Option<Duration>,
+  retry: Option<RetryOptions>,
+  overrides: Rc<HostOverrides>,
 }
 
 impl Resource for HttpClientResource {
@@ -582,8 +640,18 @@ impl Resource for HttpClientResource {
 }
 
 impl HttpClientResource {
-  fn new(client: Client) -> Self {
-    Self { client }
+  fn new(
+    client: Client,
+    read_timeout: Option<Duration>,
+    retry: Option<RetryOptions>,
+    overrides: HostOverrides,
+  ) -> Self {
+    Self {
+      client,
+      read_timeout,
+      retry,
+      overrides: Rc::new(overrides),
+    }
   }
 }
 
@@ -594,6 +662,24 @@ pub struct CreateHttpClientOptions {
   proxy: Option<Proxy>,
   cert_chain: Option<String>,
   private_key: Option<String>,
+  #[serde(default)]
+  resolve: HashMap<String, Vec<String>>,
+  resolver: Option<ResolverOptions>,
+  /// Timeouts in milliseconds.
+  connect_timeout: Option<u64>,
+  read_timeout: Option<u64>,
+  timeout: Option<u64>,
+  pool_max_idle_per_host: Option<usize>,
+  pool_idle_timeout: Option<u64>,
+  #[serde(default = "default_true")]
+  http1: bool,
+  #[serde(default = "default_true")]
+  http2: bool,
+  retry: Option<RetryOptions>,
+}
+
+fn default_true() -> bool {
+  true
 }
 
 #[op]
@@ -625,6 +711,24 @@ where
     }
   };
 
+  let resolver = match args.resolver {
+    Some(resolver_options) => {
+      let options = state.borrow::<Options>();
+      let (config, opts) = resolver_options.into_config(
+        options.root_cert_store.clone(),
+        options.unsafely_ignore_certificate_errors.clone(),
+      )?;
+      // Checks permission against the name servers which will be queried.
+      let permissions = state.borrow_mut::<FP>();
+      for ns in config.name_servers() {
+        let url = Url::parse(&format!("dns://{}", ns.socket_addr))?;
+        permissions.check_net_url(&url, "Deno.createHttpClient()")?;
+      }
+      Some(TokioAsyncResolver::tokio(config, opts)?)
+    }
+    None => None,
+  };
+
   let options = state.borrow::<Options>();
   let ca_certs = args
     .ca_certs
@@ -632,16 +736,55 @@ where
     .map(|cert| cert.into_bytes())
     .collect::<Vec<_>>();
 
-  let client = create_http_client(
+  let mut builder = create_http_client_builder(
     options.user_agent.clone(),
     options.root_cert_store.clone(),
     ca_certs,
     args.proxy,
     options.unsafely_ignore_certificate_errors.clone(),
     client_cert_chain_and_key,
+    args.http1,
+    args.http2,
   )?;
 
-  let rid = state.resource_table.add(HttpClientResource::new(client));
+  if let Some(timeout) = args.connect_timeout {
+    builder = builder.connect_timeout(Duration::from_millis(timeout));
+  }
+  if let Some(timeout) = args.timeout {
+    builder = builder.timeout(Duration::from_millis(timeout));
+  }
+  if let Some(max) = args.pool_max_idle_per_host {
+    builder = builder.pool_max_idle_per_host(max);
+  }
+  if let Some(timeout) = args.pool_idle_timeout {
+    builder = builder.pool_idle_timeout(Duration::from_millis(timeout));
+  }
+
+  if let Some(resolver) = resolver {
+    builder = builder.dns_resolver(Arc::new(dns::CustomResolver(resolver)));
+  }
+
+  // Overrides take precedence over the resolver. The port of the request URL
+  // is used, so any port here is ignored. Permission to connect to them is
+  // checked by each `fetch()`, once the port is known.
+  let overrides = HostOverrides::parse(args.resolve)?;
+  for (host, addrs) in overrides.iter() {
+    let addrs = addrs
+      .iter()
+      .map(|ip| SocketAddr::new(*ip, 0))
+      .collect::<Vec<_>>();
+    builder = builder.resolve_to_addrs(host, &addrs);
+  }
+
+  // unwrap here because it can only fail when native TLS is used.
+  let client = builder.build().unwrap();
+
+  let rid = state.resource_table.add(HttpClientResource::new(
+    client,
+    args.read_timeout.map(Duration::from_millis),
+    args.retry,
+    overrides,
+  ));
   Ok(rid)
 }
 
@@ -655,6 +798,34 @@ pub fn create_http_client(
   unsafely_ignore_certificate_errors: Option<Vec<String>>,
   client_cert_chain_and_key: Option<(String, String)>,
 ) -> Result<Client, AnyError> {
+  let builder = create_http_client_builder(
+    user_agent,
+    root_cert_store,
+    ca_certs,
+    proxy,
+    unsafely_ignore_certificate_errors,
+    client_cert_chain_and_key,
+    true,
+    true,
+  )?;
+  // unwrap here because it can only fail when native TLS is used.
+  Ok(builder.build().unwrap())
+}
+
+/// Like [`create_http_client`], but returns the builder so callers can
+/// configure the client further. `http1` and `http2` select the protocol
+/// versions that may be used, at least one of them must be enabled.
+#[allow(clippy::too_many_arguments)]
+fn create_http_client_builder(
+  user_agent: String,
+  root_cert_store: Option<RootCertStore>,
+  ca_certs: Vec<Vec<u8>>,
+  proxy: Option<Proxy>,
+  unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  client_cert_chain_and_key: Option<(String, String)>,
+  http1: bool,
+  http2: bool,
+) -> Result<ClientBuilder, AnyError> {
   let mut tls_config = deno_tls::create_client_config(
     root_cert_store,
     ca_certs,
@@ -662,7 +833,14 @@ pub fn create_http_client(
     client_cert_chain_and_key,
   )?;
 
-  tls_config.alpn_protocols = vec!["h2".into(), "http/1.1".into()];
+  tls_config.alpn_protocols = match (http1, http2) {
+    (true, true) => vec!["h2".into(), "http/1.1".into()],
+    (true, false) => vec!["http/1.1".into()],
+    (false, true) => vec!["h2".into()],
+    (false, false) => {
+      return Err(type_error("Either `http1` or `http2` needs to be true"));
+    }
+  };
 
   let mut headers = HeaderMap::new();
   headers.insert(USER_AGENT, user_agent.parse().unwrap());
@@ -671,6 +849,12 @@ pub fn create_http_client(
     .default_headers(headers)
     .use_preconfigured_tls(tls_config);
 
+  if !http1 {
+    builder = builder.http2_prior_knowledge();
+  } else if !http2 {
+    builder = builder.http1_only();
+  }
+
   if let Some(proxy) = proxy {
     let mut reqwest_proxy = reqwest::Proxy::all(&proxy.url)?;
     if let Some(basic_auth) = &proxy.basic_auth {
@@ -680,6 +864,5 @@ pub fn create_http_client(
     builder = builder.proxy(reqwest_proxy);
   }
 
-  // unwrap here because it can only fail when native TLS is used.
-  Ok(builder.build().unwrap())
+  Ok(builder)
 }
@@ -0,0 +1,120 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use reqwest::Method;
+use reqwest::RequestBuilder;
+use reqwest::Response;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::time::error::Elapsed;
+
+/// Retry policy of a `Deno.HttpClient`, only applied to idempotent requests.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryOptions {
+  /// Number of retries after the first attempt.
+  pub retries: u32,
+  /// Delay before the first retry in milliseconds, doubled for every
+  /// following one up to `MAX_RETRY_DELAY`.
+  #[serde(default = "default_delay")]
+  pub delay: u64,
+  /// Response statuses that are retried in addition to connection errors
+  /// and timeouts, e.g. 502 and 503.
+  #[serde(default)]
+  pub status_codes: Vec<u16>,
+}
+
+fn default_delay() -> u64 {
+  100
+}
+
+/// Upper bound of the backoff between two attempts.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// https://httpwg.org/specs/rfc9110.html#idempotent.methods
+pub fn is_idempotent(method: &Method) -> bool {
+  matches!(
+    *method,
+    Method::GET
+      | Method::HEAD
+      | Method::OPTIONS
+      | Method::TRACE
+      | Method::PUT
+      | Method::DELETE
+  )
+}
+
+/// Sends `request` once, failing if the response headers don't arrive
+/// within `read_timeout`.
+pub async fn send(
+  request: RequestBuilder,
+  read_timeout: Option<Duration>,
+) -> Result<Response, AnyError> {
+  into_result(send_attempt(request, read_timeout).await)
+}
+
+/// Sends `request`, retrying it according to `options`. `read_timeout`
+/// applies to every attempt. Requests with a streaming body can't be
+/// replayed and are only sent once. The response or error of the last
+/// attempt is returned.
+pub async fn send_with_retries(
+  request: RequestBuilder,
+  read_timeout: Option<Duration>,
+  options: &RetryOptions,
+) -> Result<Response, AnyError> {
+  let mut delay = Duration::from_millis(options.delay).min(MAX_RETRY_DELAY);
+  for _ in 0..options.retries {
+    let attempt = match request.try_clone() {
+      Some(attempt) => attempt,
+      None => break,
+    };
+    match send_attempt(attempt, read_timeout).await {
+      Ok(Ok(res)) if !options.status_codes.contains(&res.status().as_u16()) => {
+        return Ok(res);
+      }
+      Ok(Err(err)) if !err.is_connect() && !err.is_timeout() => {
+        return Err(type_error(err.to_string()));
+      }
+      _ => {}
+    }
+    tokio::time::sleep(delay).await;
+    delay = delay.saturating_mul(2).min(MAX_RETRY_DELAY);
+  }
+  send(request, read_timeout).await
+}
+
+async fn send_attempt(
+  request: RequestBuilder,
+  read_timeout: Option<Duration>,
+) -> Result<Result<Response, reqwest::Error>, Elapsed> {
+  match read_timeout {
+    Some(read_timeout) => {
+      tokio::time::timeout(read_timeout, request.send()).await
+    }
+    None => Ok(request.send().await),
+  }
+}
+
+fn into_result(
+  res: Result<Result<Response, reqwest::Error>, Elapsed>,
+) -> Result<Response, AnyError> {
+  match res {
+    Ok(res) => res.map_err(|err| type_error(err.to_string())),
+    Err(_) => Err(type_error("Timed out waiting for the response")),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn idempotent_methods() {
+    assert!(is_idempotent(&Method::GET));
+    assert!(is_idempotent(&Method::PUT));
+    assert!(is_idempotent(&Method::DELETE));
+    assert!(!is_idempotent(&Method::POST));
+    assert!(!is_idempotent(&Method::PATCH));
+  }
+}
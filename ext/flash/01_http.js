@@ -24,8 +24,14 @@ import {
   _serverHandleIdleTimeout,
   WebSocket,
 } from "internal:deno_websocket/01_websocket.js";
+import {
+  acceptServerWebSocketStream,
+  failServerWebSocketStream,
+  WebSocketStreamPrototype,
+} from "internal:deno_websocket/02_websocketstream.js";
 import { _ws } from "internal:deno_http/01_http.js";
 const {
+  ObjectKeys,
   ObjectPrototypeIsPrototypeOf,
   PromisePrototype,
   PromisePrototypeCatch,
@@ -411,7 +417,20 @@ async function handleResponse(
       }
     }
 
-    if (ws) {
+    if (ObjectPrototypeIsPrototypeOf(WebSocketStreamPrototype, ws)) {
+      let wsRid;
+      try {
+        wsRid = await core.opAsync("op_flash_upgrade_websocket", serverId, i);
+      } catch (err) {
+        failServerWebSocketStream(ws, err);
+        throw err;
+      }
+      acceptServerWebSocketStream(
+        ws,
+        wsRid,
+        resp.headers.get("sec-websocket-protocol"),
+      );
+    } else if (ws) {
       const wsRid = await core.opAsync(
         "op_flash_upgrade_websocket",
         serverId,
@@ -493,6 +512,19 @@ function createServe(opFn) {
       listenOpts.cert = options.cert;
       listenOpts.key = options.key;
     }
+    // An empty map doesn't serve any certificate, so it doesn't make the
+    // server use TLS.
+    if (
+      options.sniCertificates &&
+      ObjectKeys(options.sniCertificates).length > 0
+    ) {
+      listenOpts.sniCertificates = options.sniCertificates;
+    }
+    if (options.requestClientCert || options.requireClientCert) {
+      listenOpts.caCerts = options.caCerts ?? [];
+      listenOpts.requestClientCert = options.requestClientCert ?? false;
+      listenOpts.requireClientCert = options.requireClientCert ?? false;
+    }
 
     const serverId = opFn(listenOpts);
     const serverPromise = core.opAsync("op_flash_drive_server", serverId);
@@ -501,7 +533,16 @@ function createServe(opFn) {
       PromisePrototypeThen(
         core.opAsync("op_flash_wait_for_listening", serverId),
         (port) => {
-          onListen({ hostname: listenOpts.hostname, port });
+          const info = { hostname: listenOpts.hostname, port };
+          if (server.transport === "https") {
+            info.updateCertificates = ({ cert, key, sniCertificates = {} }) =>
+              ops.op_flash_update_certificates(serverId, {
+                cert,
+                key,
+                sniCertificates,
+              });
+          }
+          onListen(info);
         },
       ),
       () => {},
@@ -510,7 +551,10 @@ function createServe(opFn) {
 
     const server = {
       id: serverId,
-      transport: listenOpts.cert && listenOpts.key ? "https" : "http",
+      transport: (listenOpts.cert && listenOpts.key) ||
+          listenOpts.sniCertificates
+        ? "https"
+        : "http",
       hostname: listenOpts.hostname,
       port: listenOpts.port,
       closed: false,
@@ -766,4 +810,15 @@ function upgradeHttpRaw(req) {
   return [new TcpConn(connRid), new Uint8Array()];
 }
 
-export { createServe, upgradeHttpRaw };
+function peerCertificates(req) {
+  if (!req[_flash]) {
+    throw new TypeError(
+      "Peer certificates are only available for requests from `Deno.serve`.",
+    );
+  }
+
+  const { serverId, streamRid } = req[_flash];
+  return ops.op_flash_peer_certificates(serverId, streamRid);
+}
+
+export { createServe, peerCertificates, upgradeHttpRaw };
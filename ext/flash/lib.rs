@@ -21,8 +21,13 @@ use deno_core::OpState;
 use deno_core::StringOrBuffer;
 use deno_core::ZeroCopyBuf;
 use deno_core::V8_WRAPPER_OBJECT_INDEX;
-use deno_tls::load_certs;
-use deno_tls::load_private_keys;
+use deno_tls::create_client_cert_verifier;
+use deno_tls::peer_certificates_info;
+use deno_tls::rustls::server::ClientCertVerifier;
+use deno_tls::PeerCertificate;
+use deno_tls::SniCertResolver;
+use deno_tls::TlsCertificates;
+use deno_tls::TlsKeyPair;
 use http::header::HeaderName;
 use http::header::CONNECTION;
 use http::header::CONTENT_LENGTH;
@@ -44,7 +49,6 @@ use std::collections::HashMap;
 use std::ffi::c_void;
 use std::future::Future;
 use std::intrinsics::transmute;
-use std::io::BufReader;
 use std::io::Read;
 use std::io::Write;
 use std::mem::replace;
@@ -85,6 +89,7 @@ pub struct ServerContext {
   listening_rx: Option<mpsc::Receiver<u16>>,
   close_tx: mpsc::Sender<()>,
   cancel_handle: Rc<CancelHandle>,
+  cert_resolver: Option<Arc<SniCertResolver>>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -685,6 +690,49 @@ fn op_flash_addr(
   Ok((socket.addr.ip().to_string(), socket.addr.port()))
 }
 
+#[op]
+fn op_flash_update_certificates(
+  state: &mut OpState,
+  server_id: u32,
+  certificates: TlsCertificates,
+) -> Result<(), AnyError> {
+  check_unstable(state, "Deno.serve#updateCertificates");
+  let flash_ctx = state.borrow_mut::<FlashContext>();
+  let ctx = flash_ctx
+    .servers
+    .get(&server_id)
+    .ok_or_else(|| type_error("server closed"))?;
+  let cert_resolver = ctx
+    .cert_resolver
+    .as_ref()
+    .ok_or_else(|| type_error("Server is not serving TLS"))?;
+  cert_resolver.update_certificates(certificates)
+}
+
+#[op]
+fn op_flash_peer_certificates(
+  state: &mut OpState,
+  server_id: u32,
+  token: u32,
+) -> Result<Option<Vec<PeerCertificate>>, AnyError> {
+  check_unstable(state, "Deno.peerCertificates");
+  let flash_ctx = state.borrow_mut::<FlashContext>();
+  let ctx = flash_ctx
+    .servers
+    .get_mut(&server_id)
+    .ok_or_else(|| type_error("server closed"))?;
+  let req = &ctx
+    .requests
+    .get(&token)
+    .ok_or_else(|| type_error("request closed"))?;
+  match &req.socket().inner {
+    InnerStream::Tls(stream) => {
+      Ok(stream.conn.peer_certificates().map(peer_certificates_info))
+    }
+    InnerStream::Tcp(_) => Ok(None),
+  }
+}
+
 // Remember the first packet we read? It probably also has some body data. This op quickly copies it into
 // a buffer and sets up channels for streaming the rest.
 #[op]
@@ -871,6 +919,14 @@ pub struct ListenOpts {
   hostname: String,
   port: u16,
   reuseport: bool,
+  #[serde(default)]
+  ca_certs: Vec<String>,
+  #[serde(default)]
+  request_client_cert: bool,
+  #[serde(default)]
+  require_client_cert: bool,
+  #[serde(default)]
+  sni_certificates: HashMap<String, TlsKeyPair>,
 }
 
 fn run_server(
@@ -878,8 +934,8 @@ fn run_server(
   listening_tx: mpsc::Sender<u16>,
   mut close_rx: mpsc::Receiver<()>,
   addr: SocketAddr,
-  maybe_cert: Option<String>,
-  maybe_key: Option<String>,
+  maybe_cert_resolver: Option<Arc<SniCertResolver>>,
+  maybe_client_cert_verifier: Option<Arc<dyn ClientCertVerifier>>,
   reuseport: bool,
 ) -> Result<(), AnyError> {
   let domain = if addr.is_ipv4() {
@@ -911,17 +967,13 @@ fn run_server(
     .unwrap();
 
   let tls_context: Option<Arc<rustls::ServerConfig>> = {
-    if let Some(cert) = maybe_cert {
-      let key = maybe_key.unwrap();
-      let certificate_chain: Vec<rustls::Certificate> =
-        load_certs(&mut BufReader::new(cert.as_bytes()))?;
-      let private_key = load_private_keys(key.as_bytes())?.remove(0);
-
-      let config = rustls::ServerConfig::builder()
-        .with_safe_defaults()
-        .with_no_client_auth()
-        .with_single_cert(certificate_chain, private_key)
-        .expect("invalid key or certificate");
+    if let Some(cert_resolver) = maybe_cert_resolver {
+      let config = rustls::ServerConfig::builder().with_safe_defaults();
+      let config = match maybe_client_cert_verifier {
+        Some(verifier) => config.with_client_cert_verifier(verifier),
+        None => config.with_no_client_auth(),
+      };
+      let config = config.with_cert_resolver(cert_resolver);
       Some(Arc::new(config))
     } else {
       None
@@ -1249,6 +1301,40 @@ where
     .borrow_mut::<P>()
     .check_net(&(&opts.hostname, Some(opts.port)), "Deno.serve()")?;
 
+  let maybe_cert_resolver =
+    if opts.cert.is_some() || !opts.sni_certificates.is_empty() {
+      if !opts.sni_certificates.is_empty() {
+        check_unstable(state, "Deno.serve#sniCertificates");
+      }
+      Some(SniCertResolver::from_certificates(TlsCertificates {
+        cert: opts.cert,
+        key: opts.key,
+        sni_certificates: opts.sni_certificates,
+      })?)
+    } else {
+      None
+    };
+
+  let wants_client_cert = opts.request_client_cert || opts.require_client_cert;
+  let maybe_client_cert_verifier = if wants_client_cert {
+    check_unstable(state, "Deno.serve#clientCert");
+    if maybe_cert_resolver.is_none() {
+      return Err(type_error("Client certificates require `cert` and `key`."));
+    }
+    if opts.ca_certs.is_empty() {
+      return Err(type_error(
+        "`caCerts` must be specified to verify client certificates.",
+      ));
+    }
+    let ca_certs = opts.ca_certs.into_iter().map(|s| s.into_bytes()).collect();
+    Some(create_client_cert_verifier(
+      ca_certs,
+      opts.require_client_cert,
+    )?)
+  } else {
+    None
+  };
+
   let addr = resolve_addr_sync(&opts.hostname, opts.port)?
     .next()
     .ok_or_else(|| generic_error("No resolved address found"))?;
@@ -1264,10 +1350,9 @@ where
     close_tx,
     listening_rx: Some(listening_rx),
     cancel_handle: CancelHandle::new_rc(),
+    cert_resolver: maybe_cert_resolver.clone(),
   };
   let tx = ctx.tx.clone();
-  let maybe_cert = opts.cert;
-  let maybe_key = opts.key;
   let reuseport = opts.reuseport;
   let join_handle = tokio::task::spawn_blocking(move || {
     run_server(
@@ -1275,8 +1360,8 @@ where
       listening_tx,
       close_rx,
       addr,
-      maybe_cert,
-      maybe_key,
+      maybe_cert_resolver,
+      maybe_client_cert_verifier,
       reuseport,
     )
   });
@@ -1546,6 +1631,8 @@ pub fn init<P: FlashPermissions + 'static>(unstable: bool) -> Extension {
       op_flash_path::decl(),
       op_flash_headers::decl(),
       op_flash_addr::decl(),
+      op_flash_peer_certificates::decl(),
+      op_flash_update_certificates::decl(),
       op_flash_next::decl(),
       op_flash_next_server::decl(),
       op_flash_next_async::decl(),
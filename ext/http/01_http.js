@@ -30,6 +30,12 @@ import {
   _serverHandleIdleTimeout,
   WebSocket,
 } from "internal:deno_websocket/01_websocket.js";
+import {
+  acceptServerWebSocketStream,
+  createServerWebSocketStream,
+  failServerWebSocketStream,
+  WebSocketStreamPrototype,
+} from "internal:deno_websocket/02_websocketstream.js";
 import { TcpConn, UnixConn } from "internal:deno_net/01_net.js";
 import { TlsConn } from "internal:deno_net/02_tls.js";
 import {
@@ -358,7 +364,21 @@ function createRespondWith(
         deferred.resolve([conn, res.readBuf]);
       }
       const ws = resp[_ws];
-      if (ws) {
+      if (ObjectPrototypeIsPrototypeOf(WebSocketStreamPrototype, ws)) {
+        let wsRid;
+        try {
+          wsRid = await core.opAsync("op_http_upgrade_websocket", streamRid);
+        } catch (err) {
+          failServerWebSocketStream(ws, err);
+          throw err;
+        }
+        httpConn.close();
+        acceptServerWebSocketStream(
+          ws,
+          wsRid,
+          resp.headers.get("sec-websocket-protocol"),
+        );
+      } else if (ws) {
         const wsRid = await core.opAsync(
           "op_http_upgrade_websocket",
           streamRid,
@@ -393,7 +413,14 @@ const _ws = Symbol("[[associated_ws]]");
 const websocketCvf = buildCaseInsensitiveCommaValueFinder("websocket");
 const upgradeCvf = buildCaseInsensitiveCommaValueFinder("upgrade");
 
-function upgradeWebSocket(request, options = {}) {
+/**
+ * Validates the headers of a WebSocket upgrade request and creates the
+ * `101 Switching Protocols` response for it.
+ * @param {Request} request
+ * @param {string | undefined} protocol
+ * @returns {Response}
+ */
+function createWebSocketUpgradeResponse(request, protocol) {
   const upgrade = request.headers.get("upgrade");
   const upgradeHasWebSocketOption = upgrade !== null &&
     websocketCvf(upgrade);
@@ -430,20 +457,24 @@ function upgradeWebSocket(request, options = {}) {
 
   const protocolsStr = request.headers.get("sec-websocket-protocol") || "";
   const protocols = StringPrototypeSplit(protocolsStr, ", ");
-  if (protocols && options.protocol) {
-    if (ArrayPrototypeIncludes(protocols, options.protocol)) {
+  if (protocols && protocol) {
+    if (ArrayPrototypeIncludes(protocols, protocol)) {
       ArrayPrototypePush(r.headerList, [
         "sec-websocket-protocol",
-        options.protocol,
+        protocol,
       ]);
     } else {
       throw new TypeError(
-        `Protocol '${options.protocol}' not in the request's protocol list (non negotiable)`,
+        `Protocol '${protocol}' not in the request's protocol list (non negotiable)`,
       );
     }
   }
 
-  const response = fromInnerResponse(r, "immutable");
+  return fromInnerResponse(r, "immutable");
+}
+
+function upgradeWebSocket(request, options = {}) {
+  const response = createWebSocketUpgradeResponse(request, options.protocol);
 
   const socket = webidl.createBranded(WebSocket);
   setEventTargetData(socket);
@@ -455,6 +486,13 @@ function upgradeWebSocket(request, options = {}) {
   return { response, socket };
 }
 
+function upgradeWebSocketStream(request, options = {}) {
+  const response = createWebSocketUpgradeResponse(request, options.protocol);
+  const socket = createServerWebSocketStream(request.url, options);
+  response[_ws] = socket;
+  return { response, socket };
+}
+
 function upgradeHttp(req) {
   if (req[_flash]) {
     throw new TypeError(
@@ -539,4 +577,10 @@ function buildCaseInsensitiveCommaValueFinder(checkText) {
 internals.buildCaseInsensitiveCommaValueFinder =
   buildCaseInsensitiveCommaValueFinder;
 
-export { _ws, HttpConn, upgradeHttp, upgradeWebSocket };
+export {
+  _ws,
+  HttpConn,
+  upgradeHttp,
+  upgradeWebSocket,
+  upgradeWebSocketStream,
+};
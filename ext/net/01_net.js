@@ -11,9 +11,13 @@ import {
 import * as abortSignal from "internal:deno_web/03_abort_signal.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  ArrayIsArray,
+  ArrayPrototypeMap,
   Error,
+  ObjectKeys,
   ObjectPrototypeIsPrototypeOf,
   PromiseResolve,
+  StringPrototypeIncludes,
   SymbolAsyncIterator,
   SymbolFor,
   TypedArrayPrototypeSubarray,
@@ -31,7 +35,15 @@ function shutdown(rid) {
   return core.shutdown(rid);
 }
 
-async function resolveDns(query, recordType, options) {
+function resolveDns(query, recordType, options) {
+  return resolve(query, recordType, options, false);
+}
+
+function resolveDnsRecords(query, recordType, options) {
+  return resolve(query, recordType, options, true);
+}
+
+async function resolve(query, recordType, options, withTtl) {
   let cancelRid;
   let abortHandler;
   if (options?.signal) {
@@ -47,6 +59,7 @@ async function resolveDns(query, recordType, options) {
       query,
       recordType,
       options,
+      withTtl,
     });
   } finally {
     if (options?.signal) {
@@ -161,9 +174,82 @@ class TcpConn extends Conn {
   setKeepAlive(keepAlive = true) {
     return ops.op_set_keepalive(this.rid, keepAlive);
   }
+
+  setOption(name, value) {
+    ops.op_net_set_option_tcp(this.rid, { name, value });
+  }
+
+  getOption(name) {
+    return ops.op_net_get_option_tcp(this.rid, name).value;
+  }
+}
+
+// Wraps received file descriptors that aren't sockets. The runtime replaces
+// this so that they surface as `Deno.FsFile`.
+let fileFromRid = (rid) => ({ rid });
+
+function setFileFromRid(fn) {
+  fileFromRid = fn;
+}
+
+function fromReceivedResource(received) {
+  const { kind, rid } = received;
+  switch (kind) {
+    case "tcp":
+      received.localAddr.transport = "tcp";
+      received.remoteAddr.transport = "tcp";
+      return new TcpConn(rid, received.remoteAddr, received.localAddr);
+    case "tcpListener":
+      received.addr.transport = "tcp";
+      return new Listener(rid, received.addr);
+    case "udp":
+      received.addr.transport = "udp";
+      return new Datagram(rid, received.addr);
+    case "unix":
+      return new UnixConn(
+        rid,
+        { transport: "unix", path: received.remotePath },
+        { transport: "unix", path: received.localPath },
+      );
+    case "unixListener":
+      return new Listener(rid, { transport: "unix", path: received.path });
+    case "unixPacket":
+      return new Datagram(rid, {
+        transport: "unixpacket",
+        path: received.path,
+      });
+    case "file":
+      return fileFromRid(rid);
+    default:
+      throw new Error("unreachable");
+  }
 }
 
-class UnixConn extends Conn {}
+class UnixConn extends Conn {
+  peerCredentials() {
+    return ops.op_net_unix_peer_credentials(this.rid);
+  }
+
+  async sendFds(resources, data = new Uint8Array(1)) {
+    const rids = ArrayPrototypeMap(
+      resources,
+      (resource) => typeof resource === "number" ? resource : resource.rid,
+    );
+    return await core.opAsync("op_net_send_fds", this.rid, rids, data);
+  }
+
+  async recvFds(buffer = new Uint8Array(1)) {
+    const { 0: nread, 1: received } = await core.opAsync(
+      "op_net_recv_fds",
+      this.rid,
+      buffer,
+    );
+    return {
+      nread: nread === 0 && received.length === 0 ? null : nread,
+      resources: ArrayPrototypeMap(received, fromReceivedResource),
+    };
+  }
+}
 
 class Listener {
   #rid = 0;
@@ -329,6 +415,35 @@ class Datagram {
     }
   }
 
+  joinMulticastV4(address, networkInterface = "0.0.0.0") {
+    ops.op_net_join_multicast_udp(this.rid, address, networkInterface);
+  }
+
+  joinMulticastV6(address, interfaceIndex = 0) {
+    ops.op_net_join_multicast_udp(this.rid, address, interfaceIndex);
+  }
+
+  leaveMulticast(address, networkInterface) {
+    networkInterface ??= StringPrototypeIncludes(address, ":") ? 0 : "0.0.0.0";
+    ops.op_net_leave_multicast_udp(this.rid, address, networkInterface);
+  }
+
+  setMulticastTTL(ttl) {
+    ops.op_net_set_multicast_ttl_udp(this.rid, ttl);
+  }
+
+  setMulticastLoopback(loopback) {
+    ops.op_net_set_multicast_loopback_udp(this.rid, loopback);
+  }
+
+  setBroadcast(broadcast) {
+    ops.op_net_set_broadcast_udp(this.rid, broadcast);
+  }
+
+  setRecvBufferSize(size) {
+    ops.op_net_set_recv_buffer_size_udp(this.rid, size);
+  }
+
   close() {
     core.close(this.rid);
   }
@@ -356,7 +471,11 @@ function listen(args) {
       const { 0: rid, 1: addr } = ops.op_net_listen_tcp({
         hostname: args.hostname ?? "0.0.0.0",
         port: args.port,
-      }, args.reusePort);
+      }, args.reusePort, {
+        backlog: args.backlog,
+        reuseAddress: args.reuseAddress,
+        ipv6Only: args.ipv6Only,
+      });
       addr.transport = "tcp";
       return new Listener(rid, addr);
     }
@@ -401,15 +520,70 @@ function createListenDatagram(udpOpFn, unixOpFn) {
   };
 }
 
+/**
+ * Turns a `{ [host]: string | string[] }` map into `{ [host]: string[] }`.
+ * @param {Record<string, string | string[]> | undefined} resolve
+ * @returns {Record<string, string[]> | undefined}
+ */
+function normalizeResolve(resolve) {
+  if (resolve === undefined) {
+    return undefined;
+  }
+  const normalized = {};
+  for (const host of ObjectKeys(resolve)) {
+    const addrs = resolve[host];
+    normalized[host] = ArrayIsArray(addrs) ? addrs : [addrs];
+  }
+  return normalized;
+}
+
+/**
+ * Calls `fn` with the rid of a cancel handle that is closed when `signal`
+ * aborts, in which case the abort reason is thrown instead of the error.
+ * The handle is closed once `fn` settles, however the op ended.
+ * @template T
+ * @param {AbortSignal | undefined} signal
+ * @param {(cancelRid: number | undefined) => Promise<T>} fn
+ * @returns {Promise<T>}
+ */
+async function withAbortSignal(signal, fn) {
+  if (signal === undefined) {
+    return await fn(undefined);
+  }
+  signal.throwIfAborted();
+  const cancelRid = ops.op_cancel_handle();
+  const abortHandler = () => core.tryClose(cancelRid);
+  signal[abortSignal.add](abortHandler);
+  try {
+    return await fn(cancelRid);
+  } catch (error) {
+    signal.throwIfAborted();
+    throw error;
+  } finally {
+    signal[abortSignal.remove](abortHandler);
+    core.tryClose(cancelRid);
+  }
+}
+
 async function connect(args) {
   switch (args.transport ?? "tcp") {
     case "tcp": {
-      const { 0: rid, 1: localAddr, 2: remoteAddr } = await core.opAsync(
-        "op_net_connect_tcp",
-        {
-          hostname: args.hostname ?? "127.0.0.1",
-          port: args.port,
-        },
+      const { 0: rid, 1: localAddr, 2: remoteAddr } = await withAbortSignal(
+        args.signal,
+        (cancelRid) =>
+          core.opAsync(
+            "op_net_connect_tcp",
+            {
+              hostname: args.hostname ?? "127.0.0.1",
+              port: args.port,
+            },
+            {
+              resolve: normalizeResolve(args.resolve),
+              resolver: args.resolver,
+              timeout: args.timeout,
+              cancelRid,
+            },
+          ),
       );
       localAddr.transport = "tcp";
       remoteAddr.transport = "tcp";
@@ -439,7 +613,10 @@ export {
   listen,
   Listener,
   resolveDns,
+  resolveDnsRecords,
+  setFileFromRid,
   shutdown,
   TcpConn,
   UnixConn,
+  withAbortSignal,
 };
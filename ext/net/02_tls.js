@@ -2,7 +2,7 @@
 
 const core = globalThis.Deno.core;
 const ops = core.ops;
-import { Conn, Listener } from "internal:deno_net/01_net.js";
+import { Conn, Listener, withAbortSignal } from "internal:deno_net/01_net.js";
 const primordials = globalThis.__bootstrap.primordials;
 const { TypeError } = primordials;
 
@@ -29,14 +29,28 @@ async function connectTls({
   certChain = undefined,
   privateKey = undefined,
   alpnProtocols = undefined,
+  timeout = undefined,
+  signal = undefined,
 }) {
   if (transport !== "tcp") {
     throw new TypeError(`Unsupported transport: '${transport}'`);
   }
-  const { 0: rid, 1: localAddr, 2: remoteAddr } = await core.opAsync(
-    "op_net_connect_tls",
-    { hostname, port },
-    { certFile, caCerts, certChain, privateKey, alpnProtocols },
+  const { 0: rid, 1: localAddr, 2: remoteAddr } = await withAbortSignal(
+    signal,
+    (cancelRid) =>
+      core.opAsync(
+        "op_net_connect_tls",
+        { hostname, port },
+        {
+          certFile,
+          caCerts,
+          certChain,
+          privateKey,
+          alpnProtocols,
+          timeout,
+          cancelRid,
+        },
+      ),
   );
   localAddr.transport = "tcp";
   remoteAddr.transport = "tcp";
@@ -53,6 +67,14 @@ class TlsListener extends Listener {
     remoteAddr.transport = "tcp";
     return new TlsConn(rid, remoteAddr, localAddr);
   }
+
+  updateCertificates({ cert, key, sniCertificates = {} }) {
+    ops.op_net_update_certificates_tls(this.rid, {
+      cert,
+      key,
+      sniCertificates,
+    });
+  }
 }
 
 function listenTls({
@@ -65,13 +87,28 @@ function listenTls({
   transport = "tcp",
   alpnProtocols = undefined,
   reusePort = false,
+  caCerts = [],
+  requestClientCert = false,
+  requireClientCert = false,
+  sniCertificates = {},
 }) {
   if (transport !== "tcp") {
     throw new TypeError(`Unsupported transport: '${transport}'`);
   }
   const { 0: rid, 1: localAddr } = ops.op_net_listen_tls(
     { hostname, port },
-    { cert, certFile, key, keyFile, alpnProtocols, reusePort },
+    {
+      cert,
+      certFile,
+      key,
+      keyFile,
+      alpnProtocols,
+      reusePort,
+      caCerts,
+      requestClientCert,
+      requireClientCert,
+      sniCertificates,
+    },
   );
   return new TlsListener(rid, localAddr);
 }
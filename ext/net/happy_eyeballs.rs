@@ -0,0 +1,143 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Connection establishment following "Happy Eyeballs" (RFC 8305), so that a
+//! broken route for one address family doesn't stall the connection.
+
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::StreamExt;
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// How long to wait for an attempt before starting the next one in
+/// parallel, the recommended value from RFC 8305 section 5.
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Connects to the first address that accepts the connection. Attempts are
+/// started in the order given by [`interleave`], each one either when the
+/// previous one failed or after [`CONNECTION_ATTEMPT_DELAY`], whichever comes
+/// first. Pending attempts are dropped once one succeeds.
+pub async fn connect(addrs: Vec<SocketAddr>) -> io::Result<TcpStream> {
+  let mut addrs = interleave(addrs);
+  let mut attempts = FuturesUnordered::new();
+  let mut last_err = None;
+
+  loop {
+    match addrs.pop_front() {
+      Some(addr) => attempts.push(TcpStream::connect(addr)),
+      None if attempts.is_empty() => {
+        return Err(last_err.unwrap_or_else(|| {
+          io::Error::new(io::ErrorKind::Other, "No resolved address found")
+        }));
+      }
+      None => {}
+    }
+
+    let delay = tokio::time::sleep(CONNECTION_ATTEMPT_DELAY);
+    tokio::pin!(delay);
+    loop {
+      tokio::select! {
+        Some(result) = attempts.next() => match result {
+          Ok(stream) => return Ok(stream),
+          Err(err) => {
+            last_err = Some(err);
+            break;
+          }
+        },
+        _ = &mut delay, if !addrs.is_empty() => break,
+      }
+    }
+  }
+}
+
+/// Orders addresses by alternating between address families, starting with
+/// the family of the first address and otherwise keeping the resolver's
+/// order (RFC 8305 section 4).
+pub fn interleave(addrs: Vec<SocketAddr>) -> VecDeque<SocketAddr> {
+  let prefer_ipv6 = addrs.first().map(|a| a.is_ipv6()).unwrap_or(false);
+  let (mut preferred, mut other): (VecDeque<_>, VecDeque<_>) = addrs
+    .into_iter()
+    .partition(|addr| addr.is_ipv6() == prefer_ipv6);
+
+  let mut ordered = VecDeque::with_capacity(preferred.len() + other.len());
+  loop {
+    match (preferred.pop_front(), other.pop_front()) {
+      (None, None) => break,
+      (a, b) => ordered.extend(a.into_iter().chain(b)),
+    }
+  }
+  ordered
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::net::TcpListener;
+
+  fn addr(s: &str) -> SocketAddr {
+    s.parse().unwrap()
+  }
+
+  #[test]
+  fn interleave_alternates_families() {
+    let ordered = interleave(vec![
+      addr("[::1]:1"),
+      addr("[::2]:1"),
+      addr("[::3]:1"),
+      addr("127.0.0.1:1"),
+      addr("127.0.0.2:1"),
+    ]);
+    assert_eq!(
+      ordered.into_iter().collect::<Vec<_>>(),
+      vec![
+        addr("[::1]:1"),
+        addr("127.0.0.1:1"),
+        addr("[::2]:1"),
+        addr("127.0.0.2:1"),
+        addr("[::3]:1"),
+      ]
+    );
+  }
+
+  #[test]
+  fn interleave_starts_with_first_family() {
+    let ordered = interleave(vec![
+      addr("127.0.0.1:1"),
+      addr("127.0.0.2:1"),
+      addr("[::1]:1"),
+    ]);
+    assert_eq!(
+      ordered.into_iter().collect::<Vec<_>>(),
+      vec![addr("127.0.0.1:1"), addr("[::1]:1"), addr("127.0.0.2:1")]
+    );
+  }
+
+  #[tokio::test]
+  async fn connect_falls_back_to_next_address() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let good = listener.local_addr().unwrap();
+    // Nothing listens on the port of a dropped listener, so the first
+    // attempt is refused.
+    let refused = TcpListener::bind("127.0.0.1:0")
+      .await
+      .unwrap()
+      .local_addr()
+      .unwrap();
+
+    let stream = connect(vec![refused, good]).await.unwrap();
+    assert_eq!(stream.peer_addr().unwrap(), good);
+  }
+
+  #[tokio::test]
+  async fn connect_reports_last_error() {
+    let refused = TcpListener::bind("127.0.0.1:0")
+      .await
+      .unwrap()
+      .local_addr()
+      .unwrap();
+    let err = connect(vec![refused]).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+  }
+}
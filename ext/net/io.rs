@@ -1,8 +1,13 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use crate::tcp_options::get_tcp_option;
+use crate::tcp_options::set_tcp_option;
+use crate::tcp_options::TcpOption;
+use crate::tcp_options::TcpOptionName;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::AsyncMutFuture;
+use deno_core::AsyncRef;
 use deno_core::AsyncRefCell;
 use deno_core::AsyncResult;
 use deno_core::CancelHandle;
@@ -58,6 +63,12 @@ where
     RcRef::map(self, |r| &r.wr).borrow_mut()
   }
 
+  /// Borrows the write half for inspecting the underlying socket. Fails
+  /// while a write is in progress.
+  pub fn wr_try_borrow(self: &Rc<Self>) -> Option<AsyncRef<W>> {
+    RcRef::map(self, |r| &r.wr).try_borrow()
+  }
+
   pub fn cancel_handle(self: &Rc<Self>) -> RcRef<CancelHandle> {
     RcRef::map(self, |r| &r.cancel_handle)
   }
@@ -121,11 +132,22 @@ impl TcpStreamResource {
       .map_socket(Box::new(move |socket| Ok(socket.set_keepalive(keepalive)?)))
   }
 
+  pub fn set_option(self: Rc<Self>, option: TcpOption) -> Result<(), AnyError> {
+    self.map_socket(Box::new(move |socket| set_tcp_option(&socket, option)))
+  }
+
+  pub fn get_option(
+    self: Rc<Self>,
+    name: TcpOptionName,
+  ) -> Result<TcpOption, AnyError> {
+    self.map_socket(Box::new(move |socket| get_tcp_option(&socket, name)))
+  }
+
   #[allow(clippy::type_complexity)]
-  fn map_socket(
+  fn map_socket<R>(
     self: Rc<Self>,
-    map: Box<dyn FnOnce(SockRef) -> Result<(), AnyError>>,
-  ) -> Result<(), AnyError> {
+    map: Box<dyn FnOnce(SockRef) -> Result<R, AnyError>>,
+  ) -> Result<R, AnyError> {
     if let Some(wr) = RcRef::map(self, |r| &r.wr).try_borrow() {
       let stream = wr.as_ref().as_ref();
       let socket = socket2::SockRef::from(stream);
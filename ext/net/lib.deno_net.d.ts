@@ -193,6 +193,10 @@ declare namespace Deno {
    * Connects to the hostname (default is "127.0.0.1") and port on the named
    * transport (default is "tcp"), and resolves to the connection (`Conn`).
    *
+   * If the hostname resolves to several addresses, they are tried in turn
+   * alternating between IPv6 and IPv4, starting a new attempt every 250ms
+   * while earlier ones are pending ("Happy Eyeballs", RFC 8305).
+   *
    * ```ts
    * const conn1 = await Deno.connect({ port: 80 });
    * const conn2 = await Deno.connect({ hostname: "192.0.2.1", port: 80 });
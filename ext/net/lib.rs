@@ -1,11 +1,14 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+pub mod happy_eyeballs;
 pub mod io;
 pub mod ops;
 pub mod ops_tls;
 #[cfg(unix)]
 pub mod ops_unix;
 pub mod resolve_addr;
+pub mod resolver;
+pub mod tcp_options;
 
 use deno_core::error::AnyError;
 use deno_core::include_js_files;
@@ -1,13 +1,22 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use crate::happy_eyeballs;
 use crate::io::TcpStreamResource;
 use crate::resolve_addr::resolve_addr;
 use crate::resolve_addr::resolve_addr_sync;
+use crate::resolver::resolve_host;
+use crate::resolver::HostOverrides;
+use crate::resolver::NameServer;
+use crate::resolver::ResolverOptions;
+use crate::tcp_options::TcpOption;
+use crate::tcp_options::TcpOptionName;
 use crate::NetPermissions;
 use deno_core::error::bad_resource;
 use deno_core::error::custom_error;
 use deno_core::error::generic_error;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
+use deno_core::futures::Future;
 use deno_core::op;
 use deno_core::CancelFuture;
 
@@ -15,33 +24,37 @@ use deno_core::AsyncRefCell;
 use deno_core::ByteString;
 use deno_core::CancelHandle;
 use deno_core::CancelTryFuture;
+use deno_core::Canceled;
 use deno_core::OpDecl;
 use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::ZeroCopyBuf;
+use deno_tls::rustls::RootCertStore;
+use deno_tls::PeerCertificate;
 use serde::Deserialize;
 use serde::Serialize;
 use socket2::Domain;
 use socket2::Protocol;
+use socket2::SockRef;
 use socket2::Socket;
 use socket2::Type;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
 use std::net::SocketAddr;
 use std::rc::Rc;
+use std::time::Duration;
 use tokio::net::TcpListener;
-use tokio::net::TcpStream;
 use tokio::net::UdpSocket;
 use trust_dns_proto::rr::rdata::caa::Value;
 use trust_dns_proto::rr::record_data::RData;
 use trust_dns_proto::rr::record_type::RecordType;
-use trust_dns_resolver::config::NameServerConfigGroup;
 use trust_dns_resolver::config::ResolverConfig;
-use trust_dns_resolver::config::ResolverOpts;
 use trust_dns_resolver::error::ResolveErrorKind;
-use trust_dns_resolver::system_conf;
 use trust_dns_resolver::AsyncResolver;
 
 pub fn init<P: NetPermissions + 'static>() -> Vec<OpDecl> {
@@ -67,9 +80,23 @@ pub fn init<P: NetPermissions + 'static>() -> Vec<OpDecl> {
     op_net_send_udp::decl::<P>(),
     #[cfg(unix)]
     crate::ops_unix::op_net_send_unixpacket::decl::<P>(),
+    #[cfg(unix)]
+    crate::ops_unix::op_net_unix_peer_credentials::decl(),
+    #[cfg(unix)]
+    crate::ops_unix::op_net_send_fds::decl(),
+    #[cfg(unix)]
+    crate::ops_unix::op_net_recv_fds::decl(),
     op_dns_resolve::decl::<P>(),
+    op_net_join_multicast_udp::decl::<P>(),
+    op_net_leave_multicast_udp::decl(),
+    op_net_set_multicast_ttl_udp::decl(),
+    op_net_set_multicast_loopback_udp::decl(),
+    op_net_set_broadcast_udp::decl(),
+    op_net_set_recv_buffer_size_udp::decl(),
     op_set_nodelay::decl(),
     op_set_keepalive::decl(),
+    op_net_set_option_tcp::decl(),
+    op_net_get_option_tcp::decl(),
   ]
 }
 
@@ -77,6 +104,19 @@ pub fn init<P: NetPermissions + 'static>() -> Vec<OpDecl> {
 #[serde(rename_all = "camelCase")]
 pub struct TlsHandshakeInfo {
   pub alpn_protocol: Option<ByteString>,
+  /// Left out of what `Deno.TlsConn.handshake()` returns unless the
+  /// `--unstable` flag was provided.
+  #[serde(flatten)]
+  pub details: Option<TlsHandshakeDetails>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsHandshakeDetails {
+  pub protocol_version: Option<String>,
+  pub cipher_suite: Option<String>,
+  pub server_name: Option<String>,
+  pub peer_certificates: Option<Vec<PeerCertificate>>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -185,10 +225,56 @@ where
   Ok(nwritten)
 }
 
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectOptions {
+  #[serde(default)]
+  resolve: HashMap<String, Vec<String>>,
+  resolver: Option<ResolverOptions>,
+  timeout: Option<u64>,
+  cancel_rid: Option<ResourceId>,
+}
+
+/// Runs a connection attempt, failing with `TimedOut` once `timeout`
+/// milliseconds have elapsed and with `Interrupted` when the cancel handle
+/// `cancel_rid` is closed by an aborted signal. The handle is owned, and
+/// closed, by the caller in JS.
+pub(crate) async fn with_connect_deadline<T>(
+  state: &Rc<RefCell<OpState>>,
+  timeout: Option<u64>,
+  cancel_rid: Option<ResourceId>,
+  fut: impl Future<Output = Result<T, AnyError>>,
+) -> Result<T, AnyError> {
+  let fut = async move {
+    match timeout {
+      Some(timeout) => {
+        tokio::time::timeout(Duration::from_millis(timeout), fut)
+          .await
+          .map_err(|_| custom_error("TimedOut", "Connection timed out"))?
+      }
+      None => fut.await,
+    }
+  };
+
+  let cancel_rid = match cancel_rid {
+    Some(cancel_rid) => cancel_rid,
+    None => return fut.await,
+  };
+  // The handle is closed as soon as the signal is aborted, which may have
+  // happened before the op got to run.
+  let cancel_handle = state
+    .borrow()
+    .resource_table
+    .get::<CancelHandle>(cancel_rid)
+    .map_err(|_| Canceled)?;
+  fut.or_cancel(cancel_handle).await?
+}
+
 #[op]
 pub async fn op_net_connect_tcp<NP>(
   state: Rc<RefCell<OpState>>,
   addr: IpAddr,
+  options: Option<ConnectOptions>,
 ) -> Result<(ResourceId, IpAddr, IpAddr), AnyError>
 where
   NP: NetPermissions + 'static,
@@ -200,11 +286,46 @@ where
       .check_net(&(&addr.hostname, Some(addr.port)), "Deno.connect()")?;
   }
 
-  let addr = resolve_addr(&addr.hostname, addr.port)
-    .await?
-    .next()
-    .ok_or_else(|| generic_error("No resolved address found"))?;
-  let tcp_stream = TcpStream::connect(&addr).await?;
+  let options = options.unwrap_or_default();
+  if !options.resolve.is_empty() || options.resolver.is_some() {
+    super::check_unstable2(&state, "Deno.connect#resolve");
+  }
+  if options.timeout.is_some() || options.cancel_rid.is_some() {
+    super::check_unstable2(&state, "Deno.connect#timeout");
+  }
+  let overrides = HostOverrides::parse(options.resolve)?;
+  {
+    // The overriding addresses are connected to instead of the host, so they
+    // need permission of their own.
+    let mut state_ = state.borrow_mut();
+    let permissions = state_.borrow_mut::<NP>();
+    for (_, addrs) in overrides.iter() {
+      for ip in addrs {
+        permissions
+          .check_net(&(ip.to_string(), Some(addr.port)), "Deno.connect()")?;
+      }
+    }
+  }
+  let resolver = match options.resolver {
+    Some(resolver_options) => {
+      let (root_cert_store, unsafely_ignore_certificate_errors) =
+        tls_options(&state.borrow());
+      let (config, opts) = resolver_options
+        .into_config(root_cert_store, unsafely_ignore_certificate_errors)?;
+      check_name_servers::<NP>(&state, &config, "Deno.connect()")?;
+      Some(AsyncResolver::tokio(config, opts)?)
+    }
+    None => None,
+  };
+
+  let tcp_stream =
+    with_connect_deadline(&state, options.timeout, options.cancel_rid, async {
+      let addrs =
+        resolve_host(&addr.hostname, addr.port, &overrides, resolver.as_ref())
+          .await?;
+      Ok(happy_eyeballs::connect(addrs).await?)
+    })
+    .await?;
   let local_addr = tcp_stream.local_addr()?;
   let remote_addr = tcp_stream.peer_addr()?;
 
@@ -219,6 +340,9 @@ where
 pub struct TcpListenerResource {
   pub listener: AsyncRefCell<TcpListener>,
   pub cancel: CancelHandle,
+  /// Kept aside because a pending accept holds the listener's cell.
+  #[cfg(unix)]
+  pub fd: std::os::unix::io::RawFd,
 }
 
 impl Resource for TcpListenerResource {
@@ -231,9 +355,20 @@ impl Resource for TcpListenerResource {
   }
 }
 
-struct UdpSocketResource {
-  socket: AsyncRefCell<UdpSocket>,
-  cancel: CancelHandle,
+impl TcpListenerResource {
+  pub fn new(listener: TcpListener) -> Self {
+    Self {
+      #[cfg(unix)]
+      fd: std::os::unix::io::AsRawFd::as_raw_fd(&listener),
+      listener: AsyncRefCell::new(listener),
+      cancel: Default::default(),
+    }
+  }
+}
+
+pub struct UdpSocketResource {
+  pub socket: AsyncRefCell<UdpSocket>,
+  pub cancel: CancelHandle,
 }
 
 impl Resource for UdpSocketResource {
@@ -246,11 +381,34 @@ impl Resource for UdpSocketResource {
   }
 }
 
+impl UdpSocketResource {
+  /// Runs `f` with the socket, e.g. to change its options. Pending sends and
+  /// receives only hold shared borrows, so this doesn't wait for them.
+  pub fn with_socket<R>(
+    self: &Rc<Self>,
+    f: impl FnOnce(&UdpSocket) -> Result<R, AnyError>,
+  ) -> Result<R, AnyError> {
+    let socket = RcRef::map(self, |r| &r.socket)
+      .try_borrow()
+      .ok_or_else(|| generic_error("Unable to get resources"))?;
+    f(&socket)
+  }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TcpListenOptions {
+  backlog: Option<u32>,
+  reuse_address: Option<bool>,
+  ipv6_only: Option<bool>,
+}
+
 #[op]
 fn op_net_listen_tcp<NP>(
   state: &mut OpState,
   addr: IpAddr,
   reuse_port: bool,
+  options: Option<TcpListenOptions>,
 ) -> Result<(ResourceId, IpAddr), AnyError>
 where
   NP: NetPermissions + 'static,
@@ -258,6 +416,16 @@ where
   if reuse_port {
     super::check_unstable(state, "Deno.listen({ reusePort: true })");
   }
+  let options = options.unwrap_or_default();
+  if options.backlog.is_some()
+    || options.reuse_address.is_some()
+    || options.ipv6_only.is_some()
+  {
+    super::check_unstable(
+      state,
+      "Deno.listen({ backlog, reuseAddress, ipv6Only })",
+    );
+  }
   state
     .borrow_mut::<NP>()
     .check_net(&(&addr.hostname, Some(addr.port)), "Deno.listen()")?;
@@ -270,24 +438,29 @@ where
     Domain::IPV6
   };
   let socket = Socket::new(domain, Type::STREAM, None)?;
-  #[cfg(not(windows))]
-  socket.set_reuse_address(true)?;
+  // On Windows `SO_REUSEADDR` allows binding to a port that is in active use,
+  // so it is only enabled there on request.
+  let reuse_address = options.reuse_address.unwrap_or(!cfg!(windows));
+  if reuse_address {
+    socket.set_reuse_address(true)?;
+  }
   if reuse_port {
     #[cfg(target_os = "linux")]
     socket.set_reuse_port(true)?;
   }
+  if let Some(ipv6_only) = options.ipv6_only {
+    if addr.is_ipv6() {
+      socket.set_only_v6(ipv6_only)?;
+    }
+  }
   let socket_addr = socket2::SockAddr::from(addr);
   socket.bind(&socket_addr)?;
-  socket.listen(128)?;
+  socket.listen(options.backlog.unwrap_or(128).min(i32::MAX as u32) as i32)?;
   socket.set_nonblocking(true)?;
   let std_listener: std::net::TcpListener = socket.into();
   let listener = TcpListener::from_std(std_listener)?;
   let local_addr = listener.local_addr()?;
-  let listener_resource = TcpListenerResource {
-    listener: AsyncRefCell::new(listener),
-    cancel: Default::default(),
-  };
-  let rid = state.resource_table.add(listener_resource);
+  let rid = state.resource_table.add(TcpListenerResource::new(listener));
 
   Ok((rid, IpAddr::from(local_addr)))
 }
@@ -370,6 +543,162 @@ where
   net_listen_udp::<NP>(state, addr, reuse_address)
 }
 
+/// The interface to join a multicast group on: an address for IPv4 groups
+/// and an interface index for IPv6 groups.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum MulticastInterface {
+  Index(u32),
+  Address(String),
+}
+
+enum MulticastMembership {
+  V4(Ipv4Addr, Ipv4Addr),
+  V6(Ipv6Addr, u32),
+}
+
+impl MulticastMembership {
+  fn parse(
+    address: &str,
+    interface: MulticastInterface,
+  ) -> Result<Self, AnyError> {
+    let group = address.parse::<std::net::IpAddr>().map_err(|_| {
+      type_error(format!("Invalid multicast address: {address}"))
+    })?;
+    if !group.is_multicast() {
+      return Err(type_error(format!("{address} is not a multicast address")));
+    }
+    match (group, interface) {
+      (std::net::IpAddr::V4(group), MulticastInterface::Address(interface)) => {
+        let interface = interface.parse::<Ipv4Addr>().map_err(|_| {
+          type_error(format!("Invalid IPv4 interface address: {interface}"))
+        })?;
+        Ok(Self::V4(group, interface))
+      }
+      (std::net::IpAddr::V6(group), MulticastInterface::Index(index)) => {
+        Ok(Self::V6(group, index))
+      }
+      (std::net::IpAddr::V4(_), MulticastInterface::Index(_)) => Err(
+        type_error("IPv4 multicast groups are joined by interface address"),
+      ),
+      (std::net::IpAddr::V6(_), MulticastInterface::Address(_)) => Err(
+        type_error("IPv6 multicast groups are joined by interface index"),
+      ),
+    }
+  }
+}
+
+#[op]
+fn op_net_join_multicast_udp<NP>(
+  state: &mut OpState,
+  rid: ResourceId,
+  address: String,
+  multi_interface: MulticastInterface,
+) -> Result<(), AnyError>
+where
+  NP: NetPermissions + 'static,
+{
+  super::check_unstable(state, "Deno.DatagramConn.joinMulticast");
+  state
+    .borrow_mut::<NP>()
+    .check_net(&(&address, None), "Deno.DatagramConn.joinMulticast()")?;
+  let membership = MulticastMembership::parse(&address, multi_interface)?;
+  let resource = state.resource_table.get::<UdpSocketResource>(rid)?;
+  resource.with_socket(|socket| {
+    match membership {
+      MulticastMembership::V4(group, interface) => {
+        socket.join_multicast_v4(group, interface)?
+      }
+      MulticastMembership::V6(group, index) => {
+        socket.join_multicast_v6(&group, index)?
+      }
+    }
+    Ok(())
+  })
+}
+
+#[op]
+fn op_net_leave_multicast_udp(
+  state: &mut OpState,
+  rid: ResourceId,
+  address: String,
+  multi_interface: MulticastInterface,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.DatagramConn.leaveMulticast");
+  let membership = MulticastMembership::parse(&address, multi_interface)?;
+  let resource = state.resource_table.get::<UdpSocketResource>(rid)?;
+  resource.with_socket(|socket| {
+    match membership {
+      MulticastMembership::V4(group, interface) => {
+        socket.leave_multicast_v4(group, interface)?
+      }
+      MulticastMembership::V6(group, index) => {
+        socket.leave_multicast_v6(&group, index)?
+      }
+    }
+    Ok(())
+  })
+}
+
+#[op]
+fn op_net_set_multicast_ttl_udp(
+  state: &mut OpState,
+  rid: ResourceId,
+  ttl: u32,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.DatagramConn.setMulticastTTL");
+  let resource = state.resource_table.get::<UdpSocketResource>(rid)?;
+  resource.with_socket(|socket| {
+    if socket.local_addr()?.is_ipv4() {
+      socket.set_multicast_ttl_v4(ttl)?;
+    } else {
+      SockRef::from(socket).set_multicast_hops_v6(ttl)?;
+    }
+    Ok(())
+  })
+}
+
+#[op]
+fn op_net_set_multicast_loopback_udp(
+  state: &mut OpState,
+  rid: ResourceId,
+  loopback: bool,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.DatagramConn.setMulticastLoopback");
+  let resource = state.resource_table.get::<UdpSocketResource>(rid)?;
+  resource.with_socket(|socket| {
+    if socket.local_addr()?.is_ipv4() {
+      socket.set_multicast_loop_v4(loopback)?;
+    } else {
+      socket.set_multicast_loop_v6(loopback)?;
+    }
+    Ok(())
+  })
+}
+
+#[op]
+fn op_net_set_broadcast_udp(
+  state: &mut OpState,
+  rid: ResourceId,
+  broadcast: bool,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.DatagramConn.setBroadcast");
+  let resource = state.resource_table.get::<UdpSocketResource>(rid)?;
+  resource.with_socket(|socket| Ok(socket.set_broadcast(broadcast)?))
+}
+
+#[op]
+fn op_net_set_recv_buffer_size_udp(
+  state: &mut OpState,
+  rid: ResourceId,
+  size: usize,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.DatagramConn.setRecvBufferSize");
+  let resource = state.resource_table.get::<UdpSocketResource>(rid)?;
+  resource
+    .with_socket(|socket| Ok(SockRef::from(socket).set_recv_buffer_size(size)?))
+}
+
 #[derive(Serialize, Eq, PartialEq, Debug)]
 #[serde(untagged)]
 pub enum DnsReturnRecord {
@@ -421,31 +750,77 @@ pub struct ResolveAddrArgs {
   query: String,
   record_type: RecordType,
   options: Option<ResolveDnsOption>,
+  #[serde(default)]
+  with_ttl: bool,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ResolveDnsOption {
   name_server: Option<NameServer>,
+  #[serde(default)]
+  name_servers: Vec<NameServer>,
+  #[serde(default)]
+  ca_certs: Vec<String>,
+  timeout: Option<u64>,
+  attempts: Option<usize>,
 }
 
-fn default_port() -> u16 {
-  53
+/// A record together with its type and TTL, as returned by
+/// `Deno.resolveDnsRecords()`.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsRecordWithTtl {
+  record_type: String,
+  ttl: u32,
+  data: DnsReturnRecord,
 }
 
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct NameServer {
-  ip_addr: String,
-  #[serde(default = "default_port")]
-  port: u16,
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum DnsResolveResult {
+  Records(Vec<DnsReturnRecord>),
+  RecordsWithTtl(Vec<DnsRecordWithTtl>),
+}
+
+fn tls_options(
+  state: &OpState,
+) -> (Option<RootCertStore>, Option<Vec<String>>) {
+  let root_cert_store = state
+    .borrow::<crate::DefaultTlsOptions>()
+    .root_cert_store
+    .clone();
+  let unsafely_ignore_certificate_errors = state
+    .try_borrow::<crate::UnsafelyIgnoreCertificateErrors>()
+    .and_then(|it| it.0.clone());
+  (root_cert_store, unsafely_ignore_certificate_errors)
+}
+
+/// Checks permission against the name servers which will be actually queried.
+fn check_name_servers<NP>(
+  state: &Rc<RefCell<OpState>>,
+  config: &ResolverConfig,
+  api_name: &str,
+) -> Result<(), AnyError>
+where
+  NP: NetPermissions + 'static,
+{
+  let mut s = state.borrow_mut();
+  let perm = s.borrow_mut::<NP>();
+  for ns in config.name_servers() {
+    let socker_addr = &ns.socket_addr;
+    let ip = socker_addr.ip().to_string();
+    let port = socker_addr.port();
+    perm.check_net(&(ip, Some(port)), api_name)?;
+  }
+  Ok(())
 }
 
 #[op]
 pub async fn op_dns_resolve<NP>(
   state: Rc<RefCell<OpState>>,
   args: ResolveAddrArgs,
-) -> Result<Vec<DnsReturnRecord>, AnyError>
+) -> Result<DnsResolveResult, AnyError>
 where
   NP: NetPermissions + 'static,
 {
@@ -454,36 +829,46 @@ where
     record_type,
     options,
     cancel_rid,
+    with_ttl,
   } = args;
 
-  let (config, opts) = if let Some(name_server) =
-    options.as_ref().and_then(|o| o.name_server.as_ref())
-  {
-    let group = NameServerConfigGroup::from_ips_clear(
-      &[name_server.ip_addr.parse()?],
-      name_server.port,
-      true,
-    );
-    (
-      ResolverConfig::from_parts(None, vec![], group),
-      ResolverOpts::default(),
-    )
-  } else {
-    system_conf::read_system_conf()?
+  if with_ttl {
+    super::check_unstable2(&state, "Deno.resolveDnsRecords");
+  } else if record_type == RecordType::ANY {
+    // The records of an `ANY` answer can't be told apart without their type.
+    return Err(type_error(
+      "Record type \"ANY\" is only supported by Deno.resolveDnsRecords()",
+    ));
+  }
+  let resolver_options = match options {
+    Some(options) => {
+      let uses_resolver_config = !options.name_servers.is_empty()
+        || !options.ca_certs.is_empty()
+        || options.timeout.is_some()
+        || options.attempts.is_some();
+      if uses_resolver_config {
+        super::check_unstable2(&state, "Deno.resolveDns#resolverConfig");
+      }
+      let mut name_servers = options.name_servers;
+      if let Some(name_server) = options.name_server {
+        name_servers.insert(0, name_server);
+      }
+      ResolverOptions {
+        name_servers,
+        ca_certs: options.ca_certs,
+        timeout: options.timeout,
+        attempts: options.attempts,
+      }
+    }
+    None => ResolverOptions::default(),
   };
 
-  {
-    let mut s = state.borrow_mut();
-    let perm = s.borrow_mut::<NP>();
-
-    // Checks permission against the name servers which will be actually queried.
-    for ns in config.name_servers() {
-      let socker_addr = &ns.socket_addr;
-      let ip = socker_addr.ip().to_string();
-      let port = socker_addr.port();
-      perm.check_net(&(ip, Some(port)), "Deno.resolveDns()")?;
-    }
-  }
+  let (root_cert_store, unsafely_ignore_certificate_errors) =
+    tls_options(&state.borrow());
+  let (config, opts) = resolver_options
+    .into_config(root_cert_store, unsafely_ignore_certificate_errors)?;
+
+  check_name_servers::<NP>(&state, &config, "Deno.resolveDns()")?;
 
   let resolver = AsyncResolver::tokio(config, opts)?;
 
@@ -509,23 +894,55 @@ where
     lookup_fut.await
   };
 
-  lookup
-    .map_err(|e| {
-      let message = format!("{e}");
-      match e.kind() {
-        ResolveErrorKind::NoRecordsFound { .. } => {
-          custom_error("NotFound", message)
-        }
-        ResolveErrorKind::Message("No connections available") => {
-          custom_error("NotConnected", message)
-        }
-        ResolveErrorKind::Timeout => custom_error("TimedOut", message),
-        _ => generic_error(message),
+  let lookup = lookup.map_err(|e| {
+    let message = format!("{e}");
+    match e.kind() {
+      ResolveErrorKind::NoRecordsFound { .. } => {
+        custom_error("NotFound", message)
+      }
+      ResolveErrorKind::Message("No connections available") => {
+        custom_error("NotConnected", message)
+      }
+      ResolveErrorKind::Timeout => custom_error("TimedOut", message),
+      _ => generic_error(message),
+    }
+  })?;
+
+  if !with_ttl {
+    return lookup
+      .iter()
+      .filter_map(|rdata| {
+        rdata_to_return_record(record_type)(rdata).transpose()
+      })
+      .collect::<Result<Vec<DnsReturnRecord>, AnyError>>()
+      .map(DnsResolveResult::Records);
+  }
+
+  let mut records = Vec::new();
+  for record in lookup.record_iter() {
+    let rdata = match record.data() {
+      Some(rdata) => rdata,
+      None => continue,
+    };
+    // `ANY` answers may contain record types we can't represent, skip
+    // those instead of failing the whole lookup.
+    let data = if record_type == RecordType::ANY {
+      match rdata_to_return_record(record.record_type())(rdata) {
+        Ok(data) => data,
+        Err(_) => None,
       }
-    })?
-    .iter()
-    .filter_map(|rdata| rdata_to_return_record(record_type)(rdata).transpose())
-    .collect::<Result<Vec<DnsReturnRecord>, AnyError>>()
+    } else {
+      rdata_to_return_record(record_type)(rdata)?
+    };
+    if let Some(data) = data {
+      records.push(DnsRecordWithTtl {
+        record_type: record.record_type().to_string(),
+        ttl: record.ttl(),
+        data,
+      });
+    }
+  }
+  Ok(DnsResolveResult::RecordsWithTtl(records))
 }
 
 #[op]
@@ -539,6 +956,30 @@ pub fn op_set_nodelay(
   resource.set_nodelay(nodelay)
 }
 
+#[op]
+pub fn op_net_set_option_tcp(
+  state: &mut OpState,
+  rid: ResourceId,
+  option: TcpOption,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.TcpConn.setOption");
+  let resource: Rc<TcpStreamResource> =
+    state.resource_table.get::<TcpStreamResource>(rid)?;
+  resource.set_option(option)
+}
+
+#[op]
+pub fn op_net_get_option_tcp(
+  state: &mut OpState,
+  rid: ResourceId,
+  name: TcpOptionName,
+) -> Result<TcpOption, AnyError> {
+  super::check_unstable(state, "Deno.TcpConn.getOption");
+  let resource: Rc<TcpStreamResource> =
+    state.resource_table.get::<TcpStreamResource>(rid)?;
+  resource.get_option(name)
+}
+
 #[op]
 pub fn op_set_keepalive(
   state: &mut OpState,
@@ -653,9 +1094,6 @@ mod tests {
   use deno_core::Extension;
   use deno_core::JsRuntime;
   use deno_core::RuntimeOptions;
-  use socket2::SockRef;
-  use std::net::Ipv4Addr;
-  use std::net::Ipv6Addr;
   use std::path::Path;
   use trust_dns_proto::rr::rdata::caa::KeyValue;
   use trust_dns_proto::rr::rdata::caa::CAA;
@@ -895,6 +1333,30 @@ mod tests {
     check_sockopt(String::from("127.0.0.1:4246"), set_keepalive, test_fn).await;
   }
 
+  #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+  async fn tcp_set_option() {
+    let set_option = Box::new(|state: &mut OpState, rid| {
+      op_net_set_option_tcp::call(state, rid, TcpOption::Linger(Some(5000)))
+        .unwrap();
+      op_net_set_option_tcp::call(state, rid, TcpOption::NoDelay(true))
+        .unwrap();
+      op_net_set_option_tcp::call(state, rid, TcpOption::KeepAliveTime(30000))
+        .unwrap();
+      assert_eq!(
+        op_net_get_option_tcp::call(state, rid, TcpOptionName::Linger).unwrap(),
+        TcpOption::Linger(Some(5000))
+      );
+    });
+    let test_fn = Box::new(|socket: SockRef| {
+      assert!(socket.nodelay().unwrap());
+      assert_eq!(socket.linger().unwrap(), Some(Duration::from_secs(5)));
+      assert_eq!(socket.keepalive_time().unwrap(), Duration::from_secs(30));
+      // Setting a keepalive timer leaves keepalive itself off.
+      assert!(!socket.keepalive().unwrap());
+    });
+    check_sockopt(String::from("127.0.0.1:4247"), set_option, test_fn).await;
+  }
+
   #[allow(clippy::type_complexity)]
   async fn check_sockopt(
     addr: String,
@@ -928,7 +1390,7 @@ mod tests {
     };
 
     let connect_fut =
-      op_net_connect_tcp::call::<TestPermission>(conn_state, ip_addr);
+      op_net_connect_tcp::call::<TestPermission>(conn_state, ip_addr, None);
     let (rid, _, _) = connect_fut.await.unwrap();
 
     let state = runtime.op_state();
@@ -1,13 +1,17 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use crate::happy_eyeballs;
 use crate::io::TcpStreamResource;
+use crate::ops::with_connect_deadline;
 use crate::ops::IpAddr;
+use crate::ops::TlsHandshakeDetails;
 use crate::ops::TlsHandshakeInfo;
 use crate::resolve_addr::resolve_addr;
 use crate::resolve_addr::resolve_addr_sync;
 use crate::DefaultTlsOptions;
 use crate::NetPermissions;
 use crate::UnsafelyIgnoreCertificateErrors;
+use crate::UnstableChecker;
 use deno_core::error::bad_resource;
 use deno_core::error::custom_error;
 use deno_core::error::generic_error;
@@ -36,17 +40,23 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_tls::create_client_cert_verifier;
 use deno_tls::create_client_config;
 use deno_tls::load_certs;
 use deno_tls::load_private_keys;
+use deno_tls::peer_certificates_info;
 use deno_tls::rustls::Certificate;
 use deno_tls::rustls::ClientConfig;
 use deno_tls::rustls::ClientConnection;
 use deno_tls::rustls::Connection;
 use deno_tls::rustls::PrivateKey;
+use deno_tls::rustls::ProtocolVersion;
 use deno_tls::rustls::ServerConfig;
 use deno_tls::rustls::ServerConnection;
 use deno_tls::rustls::ServerName;
+use deno_tls::SniCertResolver;
+use deno_tls::TlsCertificates;
+use deno_tls::TlsKeyPair;
 use io::Error;
 use io::Read;
 use io::Write;
@@ -56,6 +66,7 @@ use socket2::Socket;
 use socket2::Type;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::convert::From;
 use std::convert::TryFrom;
 use std::fs::File;
@@ -95,12 +106,17 @@ enum State {
 pub struct TlsStream(Option<TlsStreamInner>);
 
 impl TlsStream {
-  fn new(tcp: TcpStream, mut tls: Connection) -> Self {
+  fn new(
+    tcp: TcpStream,
+    mut tls: Connection,
+    server_name: Option<String>,
+  ) -> Self {
     tls.set_buffer_limit(None);
 
     let inner = TlsStreamInner {
       tcp,
       tls,
+      server_name,
       rd_state: State::StreamOpen,
       wr_state: State::StreamOpen,
     };
@@ -112,8 +128,12 @@ impl TlsStream {
     tls_config: Arc<ClientConfig>,
     server_name: ServerName,
   ) -> Self {
+    let name = match &server_name {
+      ServerName::DnsName(name) => Some(name.as_ref().to_string()),
+      _ => None,
+    };
     let tls = ClientConnection::new(tls_config, server_name).unwrap();
-    Self::new(tcp, Connection::Client(tls))
+    Self::new(tcp, Connection::Client(tls), name)
   }
 
   pub fn new_server_side(
@@ -121,7 +141,7 @@ impl TlsStream {
     tls_config: Arc<ServerConfig>,
   ) -> Self {
     let tls = ServerConnection::new(tls_config).unwrap();
-    Self::new(tcp, Connection::Server(tls))
+    Self::new(tcp, Connection::Server(tls), None)
   }
 
   pub fn into_split(self) -> (ReadHalf, WriteHalf) {
@@ -155,6 +175,36 @@ impl TlsStream {
   fn get_alpn_protocol(&mut self) -> Option<ByteString> {
     self.inner_mut().tls.alpn_protocol().map(|s| s.into())
   }
+
+  fn get_handshake_info(&mut self) -> Result<TlsHandshakeInfo, AnyError> {
+    let alpn_protocol = self.get_alpn_protocol();
+    let inner = self.inner_mut();
+    let protocol_version =
+      inner.tls.protocol_version().map(|version| match version {
+        ProtocolVersion::TLSv1_2 => "TLSv1.2".to_string(),
+        ProtocolVersion::TLSv1_3 => "TLSv1.3".to_string(),
+        version => format!("{version:?}"),
+      });
+    let cipher_suite = inner
+      .tls
+      .negotiated_cipher_suite()
+      .map(|suite| format!("{:?}", suite.suite()));
+    let server_name = match &inner.tls {
+      Connection::Client(_) => inner.server_name.clone(),
+      Connection::Server(tls) => tls.sni_hostname().map(|s| s.to_string()),
+    };
+    let peer_certificates =
+      inner.tls.peer_certificates().map(peer_certificates_info);
+    Ok(TlsHandshakeInfo {
+      alpn_protocol,
+      details: Some(TlsHandshakeDetails {
+        protocol_version,
+        cipher_suite,
+        server_name,
+        peer_certificates,
+      }),
+    })
+  }
 }
 
 impl AsyncRead for TlsStream {
@@ -210,6 +260,8 @@ impl Drop for TlsStream {
 pub struct TlsStreamInner {
   tls: Connection,
   tcp: TcpStream,
+  // The name the client side asked the server for, if it is a DNS name.
+  server_name: Option<String>,
   rd_state: State,
   wr_state: State,
 }
@@ -501,8 +553,8 @@ impl WriteHalf {
     .await
   }
 
-  fn get_alpn_protocol(&mut self) -> Option<ByteString> {
-    self.shared.get_alpn_protocol()
+  fn get_handshake_info(&mut self) -> Result<TlsHandshakeInfo, AnyError> {
+    self.shared.get_handshake_info()
   }
 }
 
@@ -623,9 +675,11 @@ impl Shared {
     let _ = unsafe { Weak::from_raw(self_ptr as *const Self) };
   }
 
-  fn get_alpn_protocol(self: &Arc<Self>) -> Option<ByteString> {
+  fn get_handshake_info(
+    self: &Arc<Self>,
+  ) -> Result<TlsHandshakeInfo, AnyError> {
     let mut tls_stream = self.tls_stream.lock();
-    tls_stream.get_alpn_protocol()
+    tls_stream.get_handshake_info()
   }
 }
 
@@ -658,6 +712,7 @@ pub fn init<P: NetPermissions + 'static>() -> Vec<OpDecl> {
     op_tls_start::decl::<P>(),
     op_net_connect_tls::decl::<P>(),
     op_net_listen_tls::decl::<P>(),
+    op_net_update_certificates_tls::decl(),
     op_net_accept_tls::decl(),
     op_tls_handshake::decl(),
   ]
@@ -722,8 +777,7 @@ impl TlsStreamResource {
     let cancel_handle = RcRef::map(self, |r| &r.cancel_handle);
     wr.handshake().try_or_cancel(cancel_handle).await?;
 
-    let alpn_protocol = wr.get_alpn_protocol();
-    let tls_info = TlsHandshakeInfo { alpn_protocol };
+    let tls_info = wr.get_handshake_info()?;
     self.handshake_info.replace(Some(tls_info.clone()));
     Ok(tls_info)
   }
@@ -754,6 +808,8 @@ pub struct ConnectTlsArgs {
   cert_chain: Option<String>,
   private_key: Option<String>,
   alpn_protocols: Option<Vec<String>>,
+  timeout: Option<u64>,
+  cancel_rid: Option<ResourceId>,
 }
 
 #[derive(Deserialize)]
@@ -872,6 +928,9 @@ where
   if args.private_key.is_some() {
     super::check_unstable2(&state, "ConnectTlsOptions.privateKey");
   }
+  if args.timeout.is_some() || args.cancel_rid.is_some() {
+    super::check_unstable2(&state, "Deno.connectTls#timeout");
+  }
 
   {
     let mut s = state.borrow_mut();
@@ -902,11 +961,12 @@ where
     .clone();
   let hostname_dns = ServerName::try_from(&*addr.hostname)
     .map_err(|_| invalid_hostname(&addr.hostname))?;
-  let connect_addr = resolve_addr(&addr.hostname, addr.port)
-    .await?
-    .next()
-    .ok_or_else(|| generic_error("No resolved address found"))?;
-  let tcp_stream = TcpStream::connect(connect_addr).await?;
+  let tcp_stream =
+    with_connect_deadline(&state, args.timeout, args.cancel_rid, async {
+      let addrs = resolve_addr(&addr.hostname, addr.port).await?.collect();
+      Ok(happy_eyeballs::connect(addrs).await?)
+    })
+    .await?;
   let local_addr = tcp_stream.local_addr()?;
   let remote_addr = tcp_stream.peer_addr()?;
 
@@ -967,6 +1027,7 @@ fn load_private_keys_from_file(
 pub struct TlsListenerResource {
   tcp_listener: AsyncRefCell<TcpListener>,
   tls_config: Arc<ServerConfig>,
+  cert_resolver: Arc<SniCertResolver>,
   cancel_handle: CancelHandle,
 }
 
@@ -991,6 +1052,28 @@ pub struct ListenTlsArgs {
   key_file: Option<String>,
   alpn_protocols: Option<Vec<String>>,
   reuse_port: bool,
+  #[serde(default)]
+  ca_certs: Vec<String>,
+  #[serde(default)]
+  request_client_cert: bool,
+  #[serde(default)]
+  require_client_cert: bool,
+  #[serde(default)]
+  sni_certificates: HashMap<String, TlsKeyPair>,
+}
+
+#[op]
+pub fn op_net_update_certificates_tls(
+  state: &mut OpState,
+  rid: ResourceId,
+  certificates: TlsCertificates,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.TlsListener#updateCertificates");
+  let resource = state
+    .resource_table
+    .get::<TlsListenerResource>(rid)
+    .map_err(|_| bad_resource("Listener has been closed"))?;
+  resource.cert_resolver.update_certificates(certificates)
 }
 
 #[op]
@@ -1023,32 +1106,61 @@ where
     }
   }
 
-  let cert_chain = if cert_file.is_some() && cert.is_some() {
-    return Err(generic_error("Both cert and certFile is specified. You can specify either one of them."));
-  } else if let Some(path) = cert_file {
-    load_certs_from_file(path)?
-  } else if let Some(cert) = cert {
-    load_certs(&mut BufReader::new(cert.as_bytes()))?
+  // With SNI certificates the default certificate is optional; clients
+  // asking for an unknown hostname then fail the handshake.
+  let has_default_cert = cert_file.is_some()
+    || cert.is_some()
+    || key_file.is_some()
+    || key.is_some();
+  let default_key_pair = if has_default_cert || args.sni_certificates.is_empty()
+  {
+    let cert_chain = if cert_file.is_some() && cert.is_some() {
+      return Err(generic_error("Both cert and certFile is specified. You can specify either one of them."));
+    } else if let Some(path) = cert_file {
+      load_certs_from_file(path)?
+    } else if let Some(cert) = cert {
+      load_certs(&mut BufReader::new(cert.as_bytes()))?
+    } else {
+      return Err(generic_error("`cert` is not specified."));
+    };
+    let key_der = if key_file.is_some() && key.is_some() {
+      return Err(generic_error(
+        "Both key and keyFile is specified. You can specify either one of them.",
+      ));
+    } else if let Some(path) = key_file {
+      load_private_keys_from_file(path)?.remove(0)
+    } else if let Some(key) = key {
+      load_private_keys(key.as_bytes())?.remove(0)
+    } else {
+      return Err(generic_error("`key` is not specified."));
+    };
+    Some((cert_chain, key_der))
   } else {
-    return Err(generic_error("`cert` is not specified."));
+    None
   };
-  let key_der = if key_file.is_some() && key.is_some() {
-    return Err(generic_error(
-      "Both key and keyFile is specified. You can specify either one of them.",
-    ));
-  } else if let Some(path) = key_file {
-    load_private_keys_from_file(path)?.remove(0)
-  } else if let Some(key) = key {
-    load_private_keys(key.as_bytes())?.remove(0)
+  if !args.sni_certificates.is_empty() {
+    super::check_unstable(state, "Deno.listenTls#sni_certificates");
+  }
+  let cert_resolver =
+    SniCertResolver::new(default_key_pair, args.sni_certificates)?;
+
+  let tls_config = ServerConfig::builder().with_safe_defaults();
+  let tls_config = if args.request_client_cert || args.require_client_cert {
+    super::check_unstable(state, "Deno.listenTls#client_cert");
+    if args.ca_certs.is_empty() {
+      return Err(type_error(
+        "`caCerts` must be specified to verify client certificates.",
+      ));
+    }
+    let ca_certs = args.ca_certs.into_iter().map(|s| s.into_bytes()).collect();
+    tls_config.with_client_cert_verifier(create_client_cert_verifier(
+      ca_certs,
+      args.require_client_cert,
+    )?)
   } else {
-    return Err(generic_error("`key` is not specified."));
+    tls_config.with_no_client_auth()
   };
-
-  let mut tls_config = ServerConfig::builder()
-    .with_safe_defaults()
-    .with_no_client_auth()
-    .with_single_cert(cert_chain, key_der)
-    .expect("invalid key or certificate");
+  let mut tls_config = tls_config.with_cert_resolver(cert_resolver.clone());
   if let Some(alpn_protocols) = args.alpn_protocols {
     super::check_unstable(state, "Deno.listenTls#alpn_protocols");
     tls_config.alpn_protocols =
@@ -1081,6 +1193,7 @@ where
   let tls_listener_resource = TlsListenerResource {
     tcp_listener: AsyncRefCell::new(tcp_listener),
     tls_config: Arc::new(tls_config),
+    cert_resolver,
     cancel_handle: Default::default(),
   };
 
@@ -1139,5 +1252,9 @@ pub async fn op_tls_handshake(
     .borrow()
     .resource_table
     .get::<TlsStreamResource>(rid)?;
-  resource.handshake().await
+  let mut info = resource.handshake().await?;
+  if !state.borrow().borrow::<UnstableChecker>().unstable {
+    info.details = None;
+  }
+  Ok(info)
 }
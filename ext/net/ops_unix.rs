@@ -1,9 +1,15 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use crate::io::TcpStreamResource;
 use crate::io::UnixStreamResource;
+use crate::ops::IpAddr;
+use crate::ops::TcpListenerResource;
+use crate::ops::UdpSocketResource;
 use crate::NetPermissions;
 use deno_core::error::bad_resource;
 use deno_core::error::custom_error;
+use deno_core::error::generic_error;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op;
 use deno_core::AsyncRefCell;
@@ -14,12 +20,28 @@ use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::ZeroCopyBuf;
+use nix::sys::socket::AddressFamily;
+use nix::sys::socket::ControlMessage;
+use nix::sys::socket::ControlMessageOwned;
+use nix::sys::socket::MsgFlags;
+use nix::sys::socket::SockType;
+use nix::sys::socket::SockaddrStorage;
 use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::io::IoSlice;
+use std::io::IoSliceMut;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::io::FromRawFd;
+use std::os::unix::io::OwnedFd;
+use std::os::unix::io::RawFd;
 use std::path::Path;
 use std::rc::Rc;
+use tokio::io::Interest;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::net::UdpSocket;
 use tokio::net::UnixDatagram;
 use tokio::net::UnixListener;
 pub use tokio::net::UnixStream;
@@ -35,6 +57,18 @@ pub fn into_string(s: std::ffi::OsString) -> Result<String, AnyError> {
 struct UnixListenerResource {
   listener: AsyncRefCell<UnixListener>,
   cancel: CancelHandle,
+  /// Kept aside because a pending accept holds the listener's cell.
+  fd: RawFd,
+}
+
+impl UnixListenerResource {
+  fn new(listener: UnixListener) -> Self {
+    Self {
+      fd: listener.as_raw_fd(),
+      listener: AsyncRefCell::new(listener),
+      cancel: Default::default(),
+    }
+  }
 }
 
 impl Resource for UnixListenerResource {
@@ -50,6 +84,18 @@ impl Resource for UnixListenerResource {
 pub struct UnixDatagramResource {
   pub socket: AsyncRefCell<UnixDatagram>,
   pub cancel: CancelHandle,
+  /// Kept aside because a pending receive holds the socket's cell.
+  pub fd: RawFd,
+}
+
+impl UnixDatagramResource {
+  pub fn new(socket: UnixDatagram) -> Self {
+    Self {
+      fd: socket.as_raw_fd(),
+      socket: AsyncRefCell::new(socket),
+      cancel: Default::default(),
+    }
+  }
 }
 
 impl Resource for UnixDatagramResource {
@@ -201,11 +247,9 @@ where
   let listener = UnixListener::bind(address_path)?;
   let local_addr = listener.local_addr()?;
   let pathname = local_addr.as_pathname().map(pathstring).transpose()?;
-  let listener_resource = UnixListenerResource {
-    listener: AsyncRefCell::new(listener),
-    cancel: Default::default(),
-  };
-  let rid = state.resource_table.add(listener_resource);
+  let rid = state
+    .resource_table
+    .add(UnixListenerResource::new(listener));
   Ok((rid, pathname))
 }
 
@@ -223,11 +267,7 @@ where
   let socket = UnixDatagram::bind(address_path)?;
   let local_addr = socket.local_addr()?;
   let pathname = local_addr.as_pathname().map(pathstring).transpose()?;
-  let datagram_resource = UnixDatagramResource {
-    socket: AsyncRefCell::new(socket),
-    cancel: Default::default(),
-  };
-  let rid = state.resource_table.add(datagram_resource);
+  let rid = state.resource_table.add(UnixDatagramResource::new(socket));
   Ok((rid, pathname))
 }
 
@@ -254,6 +294,387 @@ where
   net_listen_unixpacket::<NP>(state, path)
 }
 
+/// The maximum number of file descriptors passed in a single message.
+const MAX_FDS: usize = 64;
+
+/// Received descriptors are marked close-on-exec atomically where supported.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const RECV_FLAGS: MsgFlags = MsgFlags::MSG_CMSG_CLOEXEC;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const RECV_FLAGS: MsgFlags = MsgFlags::empty();
+
+/// Creates resources for received file descriptors that aren't sockets. The
+/// file resource type lives with the embedder, which registers this in the
+/// `OpState`. Without it such descriptors are rejected.
+#[derive(Clone, Copy)]
+pub struct FileResourceFactory(pub fn(std::fs::File) -> Rc<dyn Resource>);
+
+#[derive(Serialize)]
+pub struct PeerCredentials {
+  pid: Option<i32>,
+  uid: u32,
+  gid: u32,
+}
+
+#[op]
+pub fn op_net_unix_peer_credentials(
+  state: &mut OpState,
+  rid: ResourceId,
+) -> Result<PeerCredentials, AnyError> {
+  super::check_unstable(state, "Deno.UnixConn.peerCredentials");
+  let resource = state.resource_table.get::<UnixStreamResource>(rid)?;
+  let wr = resource
+    .wr_try_borrow()
+    .ok_or_else(|| generic_error("Unable to get resources"))?;
+  let cred = wr.as_ref().as_ref().peer_cred()?;
+  Ok(PeerCredentials {
+    pid: cred.pid(),
+    uid: cred.uid(),
+    gid: cred.gid(),
+  })
+}
+
+/// Looks up the descriptor backing a resource that is about to be sent.
+fn resource_fd(state: &OpState, rid: ResourceId) -> Result<RawFd, AnyError> {
+  let table = &state.resource_table;
+  if let Ok(resource) = table.get::<TcpStreamResource>(rid) {
+    let wr = resource
+      .wr_try_borrow()
+      .ok_or_else(|| generic_error("Unable to get resources"))?;
+    return Ok(wr.as_ref().as_ref().as_raw_fd());
+  }
+  if let Ok(resource) = table.get::<UnixStreamResource>(rid) {
+    let wr = resource
+      .wr_try_borrow()
+      .ok_or_else(|| generic_error("Unable to get resources"))?;
+    return Ok(wr.as_ref().as_ref().as_raw_fd());
+  }
+  if let Ok(resource) = table.get::<UdpSocketResource>(rid) {
+    return resource.with_socket(|socket| Ok(socket.as_raw_fd()));
+  }
+  // Listeners and unix datagram sockets are usually busy accepting or
+  // receiving, which holds a mutable borrow of the cell, so their descriptor
+  // is stored along with them.
+  if let Ok(resource) = table.get::<TcpListenerResource>(rid) {
+    return Ok(resource.fd);
+  }
+  if let Ok(resource) = table.get::<UnixListenerResource>(rid) {
+    return Ok(resource.fd);
+  }
+  if let Ok(resource) = table.get::<UnixDatagramResource>(rid) {
+    return Ok(resource.fd);
+  }
+  table
+    .get_any(rid)?
+    .backing_fd()
+    .ok_or_else(|| type_error("Resource cannot be sent as a file descriptor"))
+}
+
+#[op]
+pub async fn op_net_send_fds(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  rids: Vec<ResourceId>,
+  data: ZeroCopyBuf,
+) -> Result<usize, AnyError> {
+  super::check_unstable2(&state, "Deno.UnixConn.sendFds");
+  if rids.len() > MAX_FDS {
+    return Err(type_error(format!(
+      "Cannot send more than {MAX_FDS} resources at once"
+    )));
+  }
+  if data.is_empty() {
+    return Err(type_error("Data to send along resources must not be empty"));
+  }
+  let (resource, fds) = {
+    let state = state.borrow();
+    let resource = state
+      .resource_table
+      .get::<UnixStreamResource>(rid)
+      .map_err(|_| bad_resource("Connection has been closed"))?;
+    let fds = rids
+      .into_iter()
+      .map(|rid| resource_fd(&state, rid))
+      .collect::<Result<Vec<_>, _>>()?;
+    (resource, fds)
+  };
+
+  let wr = resource.wr_borrow_mut().await;
+  let stream: &UnixStream = wr.as_ref().as_ref();
+  let iov = [IoSlice::new(&data)];
+  let cmsgs = [ControlMessage::ScmRights(&fds)];
+  loop {
+    stream.writable().await?;
+    match stream.try_io(Interest::WRITABLE, || {
+      nix::sys::socket::sendmsg::<()>(
+        stream.as_raw_fd(),
+        &iov,
+        &cmsgs,
+        MsgFlags::empty(),
+        None,
+      )
+      .map_err(std::io::Error::from)
+    }) {
+      Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+      result => return Ok(result?),
+    }
+  }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ReceivedResource {
+  #[serde(rename_all = "camelCase")]
+  Tcp {
+    rid: ResourceId,
+    local_addr: IpAddr,
+    remote_addr: IpAddr,
+  },
+  TcpListener {
+    rid: ResourceId,
+    addr: IpAddr,
+  },
+  Udp {
+    rid: ResourceId,
+    addr: IpAddr,
+  },
+  #[serde(rename_all = "camelCase")]
+  Unix {
+    rid: ResourceId,
+    local_path: Option<String>,
+    remote_path: Option<String>,
+  },
+  UnixListener {
+    rid: ResourceId,
+    path: Option<String>,
+  },
+  UnixPacket {
+    rid: ResourceId,
+    path: Option<String>,
+  },
+  File {
+    rid: ResourceId,
+  },
+}
+
+impl ReceivedResource {
+  fn rid(&self) -> ResourceId {
+    match self {
+      Self::Tcp { rid, .. }
+      | Self::TcpListener { rid, .. }
+      | Self::Udp { rid, .. }
+      | Self::Unix { rid, .. }
+      | Self::UnixListener { rid, .. }
+      | Self::UnixPacket { rid, .. }
+      | Self::File { rid } => *rid,
+    }
+  }
+}
+
+/// Wraps a received descriptor into a resource matching what it refers to.
+fn received_resource(
+  state: &mut OpState,
+  fd: OwnedFd,
+) -> Result<ReceivedResource, AnyError> {
+  use nix::sys::socket::getsockname;
+  use nix::sys::socket::getsockopt;
+  use nix::sys::socket::sockopt;
+  use nix::sys::stat::fstat;
+  use nix::sys::stat::SFlag;
+
+  let raw = fd.as_raw_fd();
+  let mode = SFlag::from_bits_truncate(fstat(raw)?.st_mode);
+  if !mode.contains(SFlag::S_IFSOCK) {
+    let factory = state
+      .try_borrow::<FileResourceFactory>()
+      .copied()
+      .ok_or_else(|| type_error("Received an unsupported file descriptor"))?;
+    let resource = factory.0(std::fs::File::from(fd));
+    let rid = state.resource_table.add_rc_dyn(resource);
+    return Ok(ReceivedResource::File { rid });
+  }
+
+  let family = getsockname::<SockaddrStorage>(raw)?.family();
+  let sock_type = getsockopt(raw, sockopt::SockType)?;
+  let listening = getsockopt(raw, sockopt::AcceptConn)?;
+  let table = &mut state.resource_table;
+  let received = match (family, sock_type, listening) {
+    (
+      Some(AddressFamily::Inet | AddressFamily::Inet6),
+      SockType::Stream,
+      true,
+    ) => {
+      let listener = std::net::TcpListener::from(fd);
+      listener.set_nonblocking(true)?;
+      let listener = TcpListener::from_std(listener)?;
+      let addr = listener.local_addr()?;
+      let rid = table.add(TcpListenerResource::new(listener));
+      ReceivedResource::TcpListener {
+        rid,
+        addr: IpAddr::from(addr),
+      }
+    }
+    (Some(AddressFamily::Inet | AddressFamily::Inet6), SockType::Stream, _) => {
+      let stream = std::net::TcpStream::from(fd);
+      stream.set_nonblocking(true)?;
+      let stream = TcpStream::from_std(stream)?;
+      let local_addr = stream.local_addr()?;
+      let remote_addr = stream.peer_addr()?;
+      let rid = table.add(TcpStreamResource::new(stream.into_split()));
+      ReceivedResource::Tcp {
+        rid,
+        local_addr: IpAddr::from(local_addr),
+        remote_addr: IpAddr::from(remote_addr),
+      }
+    }
+    (
+      Some(AddressFamily::Inet | AddressFamily::Inet6),
+      SockType::Datagram,
+      _,
+    ) => {
+      let socket = std::net::UdpSocket::from(fd);
+      socket.set_nonblocking(true)?;
+      let socket = UdpSocket::from_std(socket)?;
+      let addr = socket.local_addr()?;
+      let rid = table.add(UdpSocketResource {
+        socket: AsyncRefCell::new(socket),
+        cancel: Default::default(),
+      });
+      ReceivedResource::Udp {
+        rid,
+        addr: IpAddr::from(addr),
+      }
+    }
+    (Some(AddressFamily::Unix), SockType::Stream, true) => {
+      let listener = std::os::unix::net::UnixListener::from(fd);
+      listener.set_nonblocking(true)?;
+      let listener = UnixListener::from_std(listener)?;
+      let path = listener
+        .local_addr()?
+        .as_pathname()
+        .map(pathstring)
+        .transpose()?;
+      let rid = table.add(UnixListenerResource::new(listener));
+      ReceivedResource::UnixListener { rid, path }
+    }
+    (Some(AddressFamily::Unix), SockType::Stream, _) => {
+      let stream = std::os::unix::net::UnixStream::from(fd);
+      stream.set_nonblocking(true)?;
+      let stream = UnixStream::from_std(stream)?;
+      let local_path = stream
+        .local_addr()?
+        .as_pathname()
+        .map(pathstring)
+        .transpose()?;
+      let remote_path = stream
+        .peer_addr()?
+        .as_pathname()
+        .map(pathstring)
+        .transpose()?;
+      let rid = table.add(UnixStreamResource::new(stream.into_split()));
+      ReceivedResource::Unix {
+        rid,
+        local_path,
+        remote_path,
+      }
+    }
+    (Some(AddressFamily::Unix), SockType::Datagram, _) => {
+      let socket = std::os::unix::net::UnixDatagram::from(fd);
+      socket.set_nonblocking(true)?;
+      let socket = UnixDatagram::from_std(socket)?;
+      let path = socket
+        .local_addr()?
+        .as_pathname()
+        .map(pathstring)
+        .transpose()?;
+      let rid = table.add(UnixDatagramResource::new(socket));
+      ReceivedResource::UnixPacket { rid, path }
+    }
+    _ => return Err(type_error("Received an unsupported socket")),
+  };
+  Ok(received)
+}
+
+#[op]
+pub async fn op_net_recv_fds(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  mut buf: ZeroCopyBuf,
+) -> Result<(usize, Vec<ReceivedResource>), AnyError> {
+  super::check_unstable2(&state, "Deno.UnixConn.recvFds");
+  let resource = state
+    .borrow()
+    .resource_table
+    .get::<UnixStreamResource>(rid)
+    .map_err(|_| bad_resource("Connection has been closed"))?;
+
+  let rd = resource.rd_borrow_mut().await;
+  let stream: &UnixStream = rd.as_ref().as_ref();
+  let recv = async {
+    loop {
+      stream.readable().await?;
+      match stream.try_io(Interest::READABLE, || {
+        let mut iov = [IoSliceMut::new(&mut buf)];
+        let mut cmsg_buffer = nix::cmsg_space!([RawFd; MAX_FDS]);
+        let msg = nix::sys::socket::recvmsg::<()>(
+          stream.as_raw_fd(),
+          &mut iov,
+          Some(&mut cmsg_buffer),
+          RECV_FLAGS,
+        )?;
+        let mut fds = vec![];
+        for cmsg in msg.cmsgs() {
+          if let ControlMessageOwned::ScmRights(received) = cmsg {
+            for fd in received {
+              // SAFETY: the kernel just installed this descriptor for us.
+              fds.push(unsafe { OwnedFd::from_raw_fd(fd) });
+            }
+          }
+        }
+        // Descriptors that didn't fit were closed by the kernel, so the
+        // message can't be used. The received ones are closed on drop.
+        if msg.flags.contains(MsgFlags::MSG_CTRUNC) {
+          return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Received more than {MAX_FDS} file descriptors"),
+          ));
+        }
+        Ok((msg.bytes, fds))
+      }) {
+        Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+        result => return result,
+      }
+    }
+  };
+  let (nread, fds) = recv.try_or_cancel(resource.cancel_handle()).await?;
+
+  let mut state = state.borrow_mut();
+  // Without MSG_CMSG_CLOEXEC there is a window in which a concurrently
+  // spawned child inherits the descriptors.
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  for fd in &fds {
+    nix::fcntl::fcntl(
+      fd.as_raw_fd(),
+      nix::fcntl::FcntlArg::F_SETFD(nix::fcntl::FdFlag::FD_CLOEXEC),
+    )?;
+  }
+  let mut resources = Vec::with_capacity(fds.len());
+  for fd in fds {
+    match received_resource(&mut state, fd) {
+      Ok(resource) => resources.push(resource),
+      Err(err) => {
+        // Don't leak what was already received; the remaining descriptors
+        // are closed as they are dropped.
+        for resource in resources {
+          let _ = state.resource_table.close(resource.rid());
+        }
+        return Err(err);
+      }
+    }
+  }
+  Ok((nread, resources))
+}
+
 pub fn pathstring(pathname: &Path) -> Result<String, AnyError> {
   into_string(pathname.into())
 }
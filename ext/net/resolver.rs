@@ -0,0 +1,237 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use crate::resolve_addr::resolve_addr;
+use deno_core::error::generic_error;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_tls::create_client_config;
+use deno_tls::rustls::RootCertStore;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use trust_dns_resolver::config::NameServerConfig;
+use trust_dns_resolver::config::NameServerConfigGroup;
+use trust_dns_resolver::config::Protocol;
+use trust_dns_resolver::config::ResolverConfig;
+use trust_dns_resolver::config::ResolverOpts;
+use trust_dns_resolver::config::TlsClientConfig;
+use trust_dns_resolver::system_conf;
+pub use trust_dns_resolver::TokioAsyncResolver;
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum NameServerProtocol {
+  #[default]
+  Udp,
+  Tcp,
+  Tls,
+  Https,
+}
+
+impl NameServerProtocol {
+  fn default_port(self) -> u16 {
+    match self {
+      Self::Udp | Self::Tcp => 53,
+      Self::Tls => 853,
+      Self::Https => 443,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NameServer {
+  pub ip_addr: String,
+  pub port: Option<u16>,
+  #[serde(default)]
+  pub protocol: NameServerProtocol,
+  pub tls_dns_name: Option<String>,
+}
+
+/// Name resolution settings shared by `Deno.resolveDns()`, `Deno.connect()`
+/// and `Deno.createHttpClient()`.
+#[derive(Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolverOptions {
+  #[serde(default)]
+  pub name_servers: Vec<NameServer>,
+  #[serde(default)]
+  pub ca_certs: Vec<String>,
+  /// Time to wait for each answer, in milliseconds.
+  pub timeout: Option<u64>,
+  pub attempts: Option<usize>,
+}
+
+impl ResolverOptions {
+  /// Builds the resolver configuration. Falls back to the system
+  /// configuration when no name servers are given.
+  pub fn into_config(
+    self,
+    root_cert_store: Option<RootCertStore>,
+    unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  ) -> Result<(ResolverConfig, ResolverOpts), AnyError> {
+    let (config, mut opts) = if self.name_servers.is_empty() {
+      system_conf::read_system_conf()?
+    } else {
+      let group = name_server_configs(
+        self.name_servers,
+        self.ca_certs,
+        root_cert_store,
+        unsafely_ignore_certificate_errors,
+      )?;
+      (
+        ResolverConfig::from_parts(None, vec![], group),
+        ResolverOpts::default(),
+      )
+    };
+    if let Some(timeout) = self.timeout {
+      opts.timeout = Duration::from_millis(timeout);
+    }
+    if let Some(attempts) = self.attempts {
+      opts.attempts = attempts;
+    }
+    Ok((config, opts))
+  }
+}
+
+fn name_server_configs(
+  name_servers: Vec<NameServer>,
+  ca_certs: Vec<String>,
+  root_cert_store: Option<RootCertStore>,
+  unsafely_ignore_certificate_errors: Option<Vec<String>>,
+) -> Result<NameServerConfigGroup, AnyError> {
+  let mut tls_config = None;
+  let mut group = NameServerConfigGroup::with_capacity(name_servers.len());
+  for name_server in name_servers {
+    let ip_addr: IpAddr = name_server.ip_addr.parse()?;
+    let port = name_server
+      .port
+      .unwrap_or_else(|| name_server.protocol.default_port());
+    let socket_addr = SocketAddr::new(ip_addr, port);
+    let protocols: &[Protocol] = match name_server.protocol {
+      NameServerProtocol::Udp => &[Protocol::Udp, Protocol::Tcp],
+      NameServerProtocol::Tcp => &[Protocol::Tcp],
+      NameServerProtocol::Tls => &[Protocol::Tls],
+      NameServerProtocol::Https => &[Protocol::Https],
+    };
+    let is_encrypted = matches!(
+      name_server.protocol,
+      NameServerProtocol::Tls | NameServerProtocol::Https
+    );
+    if is_encrypted && name_server.tls_dns_name.is_none() {
+      return Err(type_error(
+        "`tlsDnsName` must be specified for TLS and HTTPS name servers.",
+      ));
+    }
+    if is_encrypted && tls_config.is_none() {
+      let config = create_client_config(
+        root_cert_store.clone(),
+        ca_certs.iter().map(|s| s.clone().into_bytes()).collect(),
+        unsafely_ignore_certificate_errors.clone(),
+        None,
+      )?;
+      tls_config = Some(TlsClientConfig(Arc::new(config)));
+    }
+    for protocol in protocols {
+      group.push(NameServerConfig {
+        socket_addr,
+        protocol: *protocol,
+        tls_dns_name: name_server.tls_dns_name.clone(),
+        trust_nx_responses: true,
+        tls_config: if is_encrypted {
+          tls_config.clone()
+        } else {
+          None
+        },
+        bind_addr: None,
+      });
+    }
+  }
+  Ok(group)
+}
+
+/// Static host name to address mappings, the equivalent of curl's
+/// `--resolve`. Host names are matched case-insensitively.
+#[derive(Default, Debug)]
+pub struct HostOverrides(HashMap<String, Vec<IpAddr>>);
+
+impl HostOverrides {
+  pub fn parse(
+    overrides: HashMap<String, Vec<String>>,
+  ) -> Result<Self, AnyError> {
+    let mut map = HashMap::with_capacity(overrides.len());
+    for (host, addrs) in overrides {
+      let addrs = addrs
+        .iter()
+        .map(|addr| {
+          addr
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .map_err(|_| {
+              type_error(format!(
+                "Invalid address \"{addr}\" for host \"{host}\" in `resolve`."
+              ))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+      if addrs.is_empty() {
+        return Err(type_error(format!(
+          "No addresses specified for host \"{host}\" in `resolve`."
+        )));
+      }
+      map.insert(host.to_ascii_lowercase(), addrs);
+    }
+    Ok(Self(map))
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  pub fn get(&self, hostname: &str) -> Option<&[IpAddr]> {
+    self
+      .0
+      .get(&hostname.to_ascii_lowercase())
+      .map(Vec::as_slice)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &[IpAddr])> {
+    self.0.iter().map(|(k, v)| (k.as_str(), v.as_slice()))
+  }
+}
+
+/// Resolves `hostname` for an outgoing connection. IP literals are used as
+/// is, then `overrides` are consulted, then `resolver` if given and finally
+/// the system resolver.
+pub async fn resolve_host(
+  hostname: &str,
+  port: u16,
+  overrides: &HostOverrides,
+  resolver: Option<&TokioAsyncResolver>,
+) -> Result<Vec<SocketAddr>, AnyError> {
+  let host = hostname.trim_start_matches('[').trim_end_matches(']');
+  if let Ok(ip) = host.parse::<IpAddr>() {
+    return Ok(vec![SocketAddr::new(ip, port)]);
+  }
+  if let Some(addrs) = overrides.get(host) {
+    return Ok(addrs.iter().map(|ip| SocketAddr::new(*ip, port)).collect());
+  }
+  let addrs: Vec<SocketAddr> = match resolver {
+    Some(resolver) => resolver
+      .lookup_ip(host)
+      .await
+      .map_err(|e| generic_error(e.to_string()))?
+      .iter()
+      .map(|ip| SocketAddr::new(ip, port))
+      .collect(),
+    None => resolve_addr(hostname, port).await?.collect(),
+  };
+  if addrs.is_empty() {
+    return Err(generic_error("No resolved address found"));
+  }
+  Ok(addrs)
+}
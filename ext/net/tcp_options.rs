@@ -0,0 +1,273 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Socket options of TCP connections, as exposed by
+//! `Deno.TcpConn.setOption()` and `Deno.TcpConn.getOption()`.
+
+use deno_core::error::AnyError;
+use serde::Deserialize;
+use serde::Serialize;
+use socket2::SockRef;
+use std::time::Duration;
+
+/// An option together with its value. Durations are in milliseconds, but
+/// most platforms only honor whole seconds for the keepalive and linger
+/// options.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "name", content = "value", rename_all = "camelCase")]
+pub enum TcpOption {
+  /// `TCP_NODELAY`
+  NoDelay(bool),
+  /// `SO_KEEPALIVE`
+  KeepAlive(bool),
+  /// `TCP_KEEPIDLE` (`TCP_KEEPALIVE` on macOS)
+  KeepAliveTime(u64),
+  /// `TCP_KEEPINTVL`
+  KeepAliveInterval(u64),
+  /// `TCP_KEEPCNT`
+  KeepAliveRetries(u32),
+  /// `SO_LINGER`, `None` if lingering is disabled.
+  Linger(Option<u64>),
+  /// `IP_TOS`, or `IPV6_TCLASS` for IPv6 sockets.
+  Tos(u32),
+  /// `SO_SNDBUF`
+  SendBufferSize(usize),
+  /// `SO_RCVBUF`
+  RecvBufferSize(usize),
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TcpOptionName {
+  NoDelay,
+  KeepAlive,
+  KeepAliveTime,
+  KeepAliveInterval,
+  KeepAliveRetries,
+  Linger,
+  Tos,
+  SendBufferSize,
+  RecvBufferSize,
+}
+
+pub fn set_tcp_option(
+  socket: &SockRef,
+  option: TcpOption,
+) -> Result<(), AnyError> {
+  match option {
+    TcpOption::NoDelay(nodelay) => socket.set_nodelay(nodelay)?,
+    TcpOption::KeepAlive(keepalive) => socket.set_keepalive(keepalive)?,
+    TcpOption::KeepAliveTime(ms) => {
+      set_int_option(socket, sys::IPPROTO_TCP, sys::TCP_KEEPIDLE, secs(ms))?
+    }
+    TcpOption::KeepAliveInterval(ms) => {
+      set_int_option(socket, sys::IPPROTO_TCP, sys::TCP_KEEPINTVL, secs(ms))?
+    }
+    TcpOption::KeepAliveRetries(retries) => set_int_option(
+      socket,
+      sys::IPPROTO_TCP,
+      sys::TCP_KEEPCNT,
+      retries.try_into().unwrap_or(i32::MAX),
+    )?,
+    TcpOption::Linger(ms) => {
+      socket.set_linger(ms.map(Duration::from_millis))?
+    }
+    TcpOption::Tos(tos) if is_ipv6(socket)? => set_tclass(socket, tos)?,
+    TcpOption::Tos(tos) => socket.set_tos(tos)?,
+    TcpOption::SendBufferSize(size) => socket.set_send_buffer_size(size)?,
+    TcpOption::RecvBufferSize(size) => socket.set_recv_buffer_size(size)?,
+  }
+  Ok(())
+}
+
+pub fn get_tcp_option(
+  socket: &SockRef,
+  name: TcpOptionName,
+) -> Result<TcpOption, AnyError> {
+  let option = match name {
+    TcpOptionName::NoDelay => TcpOption::NoDelay(socket.nodelay()?),
+    TcpOptionName::KeepAlive => TcpOption::KeepAlive(socket.keepalive()?),
+    TcpOptionName::KeepAliveTime => {
+      TcpOption::KeepAliveTime(keepalive_time(socket)?)
+    }
+    TcpOptionName::KeepAliveInterval => {
+      TcpOption::KeepAliveInterval(keepalive_interval(socket)?)
+    }
+    TcpOptionName::KeepAliveRetries => {
+      TcpOption::KeepAliveRetries(keepalive_retries(socket)?)
+    }
+    TcpOptionName::Linger => TcpOption::Linger(
+      socket.linger()?.map(|linger| linger.as_millis() as u64),
+    ),
+    TcpOptionName::Tos if is_ipv6(socket)? => TcpOption::Tos(tclass(socket)?),
+    TcpOptionName::Tos => TcpOption::Tos(socket.tos()?),
+    TcpOptionName::SendBufferSize => {
+      TcpOption::SendBufferSize(socket.send_buffer_size()?)
+    }
+    TcpOptionName::RecvBufferSize => {
+      TcpOption::RecvBufferSize(socket.recv_buffer_size()?)
+    }
+  };
+  Ok(option)
+}
+
+fn is_ipv6(socket: &SockRef) -> Result<bool, AnyError> {
+  Ok(socket.local_addr()?.as_socket_ipv6().is_some())
+}
+
+/// Converts milliseconds to the whole seconds the keepalive options take.
+fn secs(ms: u64) -> i32 {
+  (ms / 1000).try_into().unwrap_or(i32::MAX)
+}
+
+// The keepalive timers are set one by one instead of through
+// `SockRef::set_tcp_keepalive()`, which also turns keepalive on and, on
+// Windows, resets the timers that aren't given.
+
+#[cfg(unix)]
+mod sys {
+  pub use nix::libc::IPPROTO_IPV6;
+  pub use nix::libc::IPPROTO_TCP;
+  pub use nix::libc::IPV6_TCLASS;
+  #[cfg(any(target_os = "macos", target_os = "ios"))]
+  pub use nix::libc::TCP_KEEPALIVE as TCP_KEEPIDLE;
+  pub use nix::libc::TCP_KEEPCNT;
+  #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+  pub use nix::libc::TCP_KEEPIDLE;
+  pub use nix::libc::TCP_KEEPINTVL;
+}
+
+// The keepalive options aren't defined by winapi, and need Windows 10 1709.
+#[cfg(windows)]
+mod sys {
+  pub use winapi::shared::ws2def::IPPROTO_TCP;
+  pub const TCP_KEEPIDLE: i32 = 3;
+  pub const TCP_KEEPCNT: i32 = 16;
+  pub const TCP_KEEPINTVL: i32 = 17;
+}
+
+#[cfg(unix)]
+fn set_int_option(
+  socket: &SockRef,
+  level: i32,
+  name: i32,
+  value: i32,
+) -> Result<(), AnyError> {
+  use std::os::unix::io::AsRawFd;
+  // SAFETY: `value` outlives the call, and its size is passed along.
+  let ret = unsafe {
+    nix::libc::setsockopt(
+      socket.as_raw_fd(),
+      level,
+      name,
+      &value as *const i32 as *const nix::libc::c_void,
+      std::mem::size_of::<i32>() as nix::libc::socklen_t,
+    )
+  };
+  if ret == -1 {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(())
+}
+
+#[cfg(unix)]
+fn get_int_option(
+  socket: &SockRef,
+  level: i32,
+  name: i32,
+) -> Result<i32, AnyError> {
+  use std::os::unix::io::AsRawFd;
+  let mut value: i32 = 0;
+  let mut len = std::mem::size_of::<i32>() as nix::libc::socklen_t;
+  // SAFETY: `value` and `len` outlive the call and describe a valid buffer.
+  let ret = unsafe {
+    nix::libc::getsockopt(
+      socket.as_raw_fd(),
+      level,
+      name,
+      &mut value as *mut i32 as *mut nix::libc::c_void,
+      &mut len,
+    )
+  };
+  if ret == -1 {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(value)
+}
+
+#[cfg(windows)]
+fn set_int_option(
+  socket: &SockRef,
+  level: i32,
+  name: i32,
+  value: i32,
+) -> Result<(), AnyError> {
+  use std::os::windows::io::AsRawSocket;
+  use winapi::um::winsock2;
+  // SAFETY: `value` outlives the call, and its size is passed along.
+  let ret = unsafe {
+    winsock2::setsockopt(
+      socket.as_raw_socket() as winsock2::SOCKET,
+      level,
+      name,
+      &value as *const i32 as *const i8,
+      std::mem::size_of::<i32>() as i32,
+    )
+  };
+  if ret == winsock2::SOCKET_ERROR {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(())
+}
+
+#[cfg(unix)]
+fn set_tclass(socket: &SockRef, tclass: u32) -> Result<(), AnyError> {
+  let tclass = tclass.try_into().unwrap_or(i32::MAX);
+  set_int_option(socket, sys::IPPROTO_IPV6, sys::IPV6_TCLASS, tclass)
+}
+
+#[cfg(unix)]
+fn tclass(socket: &SockRef) -> Result<u32, AnyError> {
+  Ok(get_int_option(socket, sys::IPPROTO_IPV6, sys::IPV6_TCLASS)? as u32)
+}
+
+#[cfg(windows)]
+fn set_tclass(_: &SockRef, _: u32) -> Result<(), AnyError> {
+  Err(deno_core::error::not_supported())
+}
+
+#[cfg(windows)]
+fn tclass(_: &SockRef) -> Result<u32, AnyError> {
+  Err(deno_core::error::not_supported())
+}
+
+// Windows can set the keepalive timers but not read them back.
+
+#[cfg(not(windows))]
+fn keepalive_time(socket: &SockRef) -> Result<u64, AnyError> {
+  Ok(socket.keepalive_time()?.as_millis() as u64)
+}
+
+#[cfg(windows)]
+fn keepalive_time(_: &SockRef) -> Result<u64, AnyError> {
+  Err(deno_core::error::not_supported())
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn keepalive_interval(socket: &SockRef) -> Result<u64, AnyError> {
+  Ok(socket.keepalive_interval()?.as_millis() as u64)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn keepalive_interval(_: &SockRef) -> Result<u64, AnyError> {
+  Err(deno_core::error::not_supported())
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn keepalive_retries(socket: &SockRef) -> Result<u32, AnyError> {
+  Ok(socket.keepalive_retries()?)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn keepalive_retries(_: &SockRef) -> Result<u32, AnyError> {
+  Err(deno_core::error::not_supported())
+}
@@ -48,6 +48,12 @@ import { kEmptyObject } from "internal:deno_node/polyfills/internal/util.mjs";
 import { getValidatedPath } from "internal:deno_node/polyfills/internal/fs/utils.mjs";
 import process from "internal:deno_node/polyfills/process.ts";
 
+// Without `--unstable`, `Deno.Command` exits on its unstable options, so the
+// IPC channel and detached children are only used when they are allowed.
+// @ts-ignore Deno[Deno.internal] is used on purpose here
+const unstableCommandOptions = Deno[Deno.internal]?.nodeUnstable
+  ?.unstableCommandOptions === true;
+
 export function mapValues<T, O>(
   record: Readonly<Record<string, T>>,
   transformer: (value: T) => O,
@@ -146,6 +152,12 @@ export class ChildProcess extends EventEmitter {
     null,
   ];
 
+  /**
+   * Whether it is still possible to send and receive messages over the IPC
+   * channel.
+   */
+  connected = false;
+
   #process!: Deno.ChildProcess;
   #spawned = deferred<void>();
 
@@ -163,12 +175,13 @@ export class ChildProcess extends EventEmitter {
       shell = false,
       signal,
       windowsVerbatimArguments = false,
+      detached = false,
     } = options || {};
     const [
       stdin = "pipe",
       stdout = "pipe",
       stderr = "pipe",
-      _channel, // TODO(kt3k): handle this correctly
+      channel,
     ] = normalizeStdioOption(stdio);
     const [cmd, cmdArgs] = buildCommand(
       command,
@@ -179,6 +192,8 @@ export class ChildProcess extends EventEmitter {
     this.spawnargs = [cmd, ...cmdArgs];
 
     const stringEnv = mapValues(env, (value) => value.toString());
+    // Windows has no IPC channel yet.
+    const ipc = channel === "ipc" && unstableCommandOptions && !isWindows;
     try {
       this.#process = new Deno.Command(cmd, {
         args: cmdArgs,
@@ -188,9 +203,18 @@ export class ChildProcess extends EventEmitter {
         stdout: toDenoStdio(stdout as NodeStdio | number),
         stderr: toDenoStdio(stderr as NodeStdio | number),
         windowsRawArguments: windowsVerbatimArguments,
+        ipc,
+        detached: detached && unstableCommandOptions,
       }).spawn();
       this.pid = this.#process.pid;
 
+      if (ipc) {
+        this.connected = true;
+        this.#process.onmessage = (event: MessageEvent) => {
+          this.emit("message", event.data);
+        };
+      }
+
       if (stdin === "pipe") {
         assert(this.#process.stdin);
         this.stdin = Writable.fromWeb(this.#process.stdin);
@@ -239,6 +263,7 @@ export class ChildProcess extends EventEmitter {
       (async () => {
         const status = await this.#process.status;
         this.exitCode = status.code;
+        this.disconnect();
         this.#spawned.then(async () => {
           const exitCode = this.signalCode == null ? this.exitCode : null;
           const signalCode = this.signalCode == null ? null : this.signalCode;
@@ -286,8 +311,44 @@ export class ChildProcess extends EventEmitter {
     this.#process.unref();
   }
 
+  /**
+   * Sends a message to the child process, which receives it as a `message`
+   * event on its `process` object.
+   */
+  // deno-lint-ignore no-explicit-any
+  send(message: any, ...args: any[]): boolean {
+    const callback = typeof args[args.length - 1] === "function"
+      ? args[args.length - 1]
+      : undefined;
+    const onError = (err: Error) => {
+      if (callback) {
+        callback(err);
+      } else {
+        this.emit("error", err);
+      }
+    };
+    if (!this.connected) {
+      nextTick(onError, new Error("Channel closed"));
+      return false;
+    }
+    let written: Promise<void>;
+    try {
+      written = this.#process.postMessage(message);
+    } catch (err) {
+      nextTick(onError, err);
+      return false;
+    }
+    written.then(() => callback?.(null), onError);
+    return true;
+  }
+
   disconnect() {
-    warnNotImplemented("ChildProcess.prototype.disconnect");
+    if (!this.connected) {
+      return;
+    }
+    this.connected = false;
+    this.#process.disconnect();
+    nextTick(() => this.emit("disconnect"));
   }
 
   async #_waitForChildStreamsToClose() {
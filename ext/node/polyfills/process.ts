@@ -69,9 +69,12 @@ import { buildAllowedFlags } from "internal:deno_node/polyfills/internal/process
 const DenoCommand = Deno[Deno.internal]?.nodeUnstable?.Command ||
   Deno.Command;
 
+// @ts-ignore Deno[Deno.internal] is used on purpose here
+const getParentIpcChannel = Deno[Deno.internal]?.nodeUnstable
+  ?.getParentIpcChannel;
+
 const notImplementedEvents = [
   "disconnect",
-  "message",
   "multipleResolves",
   "rejectionHandled",
   "worker",
@@ -410,6 +413,50 @@ class Process extends EventEmitter {
   /** https://nodejs.org/api/process.html#process_process_nexttick_callback_args */
   nextTick = _nextTick;
 
+  #listeningForIpcMessages = false;
+
+  #listenForIpcMessages() {
+    if (this.#listeningForIpcMessages) {
+      return;
+    }
+    const channel = getParentIpcChannel?.();
+    if (!channel) {
+      return;
+    }
+    this.#listeningForIpcMessages = true;
+    channel.onmessage = (event: MessageEvent) => {
+      this.emit("message", event.data);
+    };
+  }
+
+  /** https://nodejs.org/api/process.html#processsendmessage-sendhandle-options-callback */
+  // deno-lint-ignore no-explicit-any
+  send(message: any, ...args: any[]): boolean {
+    const channel = getParentIpcChannel?.();
+    if (!channel) {
+      throw new Error("process.send() requires an IPC channel");
+    }
+    const callback = typeof args[args.length - 1] === "function"
+      ? args[args.length - 1]
+      : undefined;
+    const onError = (err: Error) => {
+      if (callback) {
+        callback(err);
+      } else {
+        this.emit("error", err);
+      }
+    };
+    let written: Promise<void>;
+    try {
+      written = channel.postMessage(message);
+    } catch (err) {
+      _nextTick(onError, err);
+      return false;
+    }
+    written.then(() => callback?.(null), onError);
+    return true;
+  }
+
   /** https://nodejs.org/api/process.html#process_process_events */
   override on(event: "exit", listener: (code: number) => void): this;
   override on(
@@ -422,6 +469,9 @@ class Process extends EventEmitter {
     if (notImplementedEvents.includes(event)) {
       warnNotImplemented(`process.on("${event}")`);
       super.on(event, listener);
+    } else if (event === "message") {
+      super.on(event, listener);
+      this.#listenForIpcMessages();
     } else if (event.startsWith("SIG")) {
       if (event === "SIGBREAK" && Deno.build.os !== "windows") {
         // Ignores SIGBREAK if the platform is not windows.
@@ -495,6 +545,9 @@ class Process extends EventEmitter {
     if (notImplementedEvents.includes(event)) {
       warnNotImplemented(`process.prependListener("${event}")`);
       super.prependListener(event, listener);
+    } else if (event === "message") {
+      super.prependListener(event, listener);
+      this.#listenForIpcMessages();
     } else if (event.startsWith("SIG")) {
       if (event === "SIGBREAK" && Deno.build.os !== "windows") {
         // Ignores SIGBREAK if the platform is not windows.
@@ -8,6 +8,7 @@ pub use webpki_roots;
 
 use deno_core::anyhow::anyhow;
 use deno_core::error::custom_error;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
 use deno_core::Extension;
@@ -18,6 +19,13 @@ use rustls::client::ServerCertVerifier;
 use rustls::client::StoresClientSessions;
 use rustls::client::WebPkiVerifier;
 use rustls::internal::msgs::handshake::DigitallySignedStruct;
+use rustls::server::AllowAnyAnonymousOrAuthenticatedClient;
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::server::ClientCertVerifier;
+use rustls::server::ClientHello;
+use rustls::server::ResolvesServerCert;
+use rustls::sign::any_supported_type;
+use rustls::sign::CertifiedKey;
 use rustls::Certificate;
 use rustls::ClientConfig;
 use rustls::Error;
@@ -28,12 +36,14 @@ use rustls_pemfile::certs;
 use rustls_pemfile::pkcs8_private_keys;
 use rustls_pemfile::rsa_private_keys;
 use serde::Deserialize;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Cursor;
 use std::sync::Arc;
 use std::time::SystemTime;
+use x509_parser::extensions::GeneralName;
 
 /// This extension has no runtime apis, it only exports some shared native functions.
 pub fn init() -> Extension {
@@ -308,3 +318,244 @@ pub fn load_private_keys(bytes: &[u8]) -> Result<Vec<PrivateKey>, AnyError> {
 
   Ok(keys)
 }
+
+/// Builds a verifier for client certificates presented to a TLS server. When
+/// `require` is false, clients that don't present a certificate are still
+/// accepted, but any certificate that is presented must chain to one of the
+/// given CA certificates.
+pub fn create_client_cert_verifier(
+  ca_certs: Vec<Vec<u8>>,
+  require: bool,
+) -> Result<Arc<dyn ClientCertVerifier>, AnyError> {
+  let mut root_cert_store = RootCertStore::empty();
+  for cert in ca_certs {
+    let reader = &mut BufReader::new(Cursor::new(cert));
+    for cert in load_certs(reader)? {
+      root_cert_store.add(&cert).map_err(|e| {
+        custom_error("InvalidData", format!("Invalid CA certificate: {e}"))
+      })?;
+    }
+  }
+
+  Ok(if require {
+    AllowAnyAuthenticatedClient::new(root_cert_store)
+  } else {
+    AllowAnyAnonymousOrAuthenticatedClient::new(root_cert_store)
+  })
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerCertificate {
+  pub subject: String,
+  pub issuer: String,
+  pub subject_alt_names: Vec<String>,
+  pub serial_number: String,
+  pub fingerprint256: String,
+  #[serde(with = "serde_bytes")]
+  pub der: Vec<u8>,
+}
+
+fn hex_upper(bytes: &[u8], separator: &str) -> String {
+  bytes
+    .iter()
+    .map(|b| format!("{b:02X}"))
+    .collect::<Vec<_>>()
+    .join(separator)
+}
+
+fn format_general_name(name: &GeneralName) -> Option<String> {
+  match name {
+    GeneralName::DNSName(name) => Some(format!("DNS:{name}")),
+    GeneralName::RFC822Name(email) => Some(format!("email:{email}")),
+    GeneralName::URI(uri) => Some(format!("URI:{uri}")),
+    GeneralName::IPAddress(bytes) => {
+      let addr = match bytes.len() {
+        4 => <[u8; 4]>::try_from(*bytes).map(std::net::IpAddr::from).ok(),
+        16 => <[u8; 16]>::try_from(*bytes)
+          .map(std::net::IpAddr::from)
+          .ok(),
+        _ => None,
+      }?;
+      Some(format!("IP Address:{addr}"))
+    }
+    _ => None,
+  }
+}
+
+/// Describes a DER encoded certificate presented by the peer of a TLS
+/// connection. The handshake already accepted the certificate, so one that
+/// can't be parsed is still reported, with only its DER and fingerprint.
+pub fn peer_certificate_info(cert: &Certificate) -> PeerCertificate {
+  let fingerprint = ring::digest::digest(&ring::digest::SHA256, &cert.0);
+  let mut info = PeerCertificate {
+    subject: String::new(),
+    issuer: String::new(),
+    subject_alt_names: vec![],
+    serial_number: String::new(),
+    fingerprint256: hex_upper(fingerprint.as_ref(), ":"),
+    der: cert.0.clone(),
+  };
+  if let Ok((_, parsed)) = x509_parser::parse_x509_certificate(&cert.0) {
+    info.subject = parsed.subject().to_string();
+    info.issuer = parsed.issuer().to_string();
+    info.serial_number = hex_upper(parsed.raw_serial(), "");
+    if let Ok(Some(ext)) = parsed.subject_alternative_name() {
+      info.subject_alt_names = ext
+        .value
+        .general_names
+        .iter()
+        .filter_map(format_general_name)
+        .collect();
+    }
+  }
+  info
+}
+
+pub fn peer_certificates_info(certs: &[Certificate]) -> Vec<PeerCertificate> {
+  certs.iter().map(peer_certificate_info).collect()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TlsKeyPair {
+  pub cert: String,
+  pub key: String,
+}
+
+/// The certificates a TLS server presents: an optional default key pair and
+/// key pairs by SNI hostname.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsCertificates {
+  pub cert: Option<String>,
+  pub key: Option<String>,
+  #[serde(default)]
+  pub sni_certificates: HashMap<String, TlsKeyPair>,
+}
+
+impl TlsCertificates {
+  pub fn default_key_pair(
+    &self,
+  ) -> Result<Option<(Vec<Certificate>, PrivateKey)>, AnyError> {
+    match (&self.cert, &self.key) {
+      (Some(cert), Some(key)) => Ok(Some(load_key_pair(&TlsKeyPair {
+        cert: cert.clone(),
+        key: key.clone(),
+      })?)),
+      (None, None) => Ok(None),
+      _ => Err(type_error("Both `cert` and `key` must be specified.")),
+    }
+  }
+}
+
+pub fn load_key_pair(
+  pair: &TlsKeyPair,
+) -> Result<(Vec<Certificate>, PrivateKey), AnyError> {
+  let cert_chain = load_certs(&mut BufReader::new(pair.cert.as_bytes()))?;
+  // The `remove` is safe because load_private_keys checks that there is at least one key.
+  let private_key = load_private_keys(pair.key.as_bytes())?.remove(0);
+  Ok((cert_chain, private_key))
+}
+
+fn certified_key(
+  cert_chain: Vec<Certificate>,
+  private_key: &PrivateKey,
+) -> Result<Arc<CertifiedKey>, AnyError> {
+  let signing_key = any_supported_type(private_key)
+    .map_err(|_| custom_error("InvalidData", "Unsupported private key"))?;
+  Ok(Arc::new(CertifiedKey::new(cert_chain, signing_key)))
+}
+
+#[derive(Default)]
+struct SniCertificates {
+  default: Option<Arc<CertifiedKey>>,
+  by_name: HashMap<String, Arc<CertifiedKey>>,
+}
+
+/// Picks the server certificate by the SNI hostname sent by the client,
+/// falling back to a default certificate. Names may use a single leading
+/// wildcard label, e.g. `*.example.com`.
+///
+/// The certificates can be swapped with `update()` while the server is
+/// running. Connections that already completed their handshake keep the
+/// certificate they negotiated.
+#[derive(Default)]
+pub struct SniCertResolver {
+  certificates: Mutex<SniCertificates>,
+}
+
+impl SniCertResolver {
+  pub fn new(
+    default: Option<(Vec<Certificate>, PrivateKey)>,
+    sni_certificates: HashMap<String, TlsKeyPair>,
+  ) -> Result<Arc<Self>, AnyError> {
+    let resolver = Arc::new(Self::default());
+    resolver.update(default, sni_certificates)?;
+    Ok(resolver)
+  }
+
+  pub fn from_certificates(
+    certificates: TlsCertificates,
+  ) -> Result<Arc<Self>, AnyError> {
+    Self::new(
+      certificates.default_key_pair()?,
+      certificates.sni_certificates,
+    )
+  }
+
+  pub fn update_certificates(
+    &self,
+    certificates: TlsCertificates,
+  ) -> Result<(), AnyError> {
+    self.update(
+      certificates.default_key_pair()?,
+      certificates.sni_certificates,
+    )
+  }
+
+  pub fn update(
+    &self,
+    default: Option<(Vec<Certificate>, PrivateKey)>,
+    sni_certificates: HashMap<String, TlsKeyPair>,
+  ) -> Result<(), AnyError> {
+    if default.is_none() && sni_certificates.is_empty() {
+      return Err(custom_error("InvalidData", "No certificates specified"));
+    }
+
+    let default = match default {
+      Some((cert_chain, private_key)) => {
+        Some(certified_key(cert_chain, &private_key)?)
+      }
+      None => None,
+    };
+    let mut by_name = HashMap::with_capacity(sni_certificates.len());
+    for (name, pair) in sni_certificates {
+      let (cert_chain, private_key) = load_key_pair(&pair)?;
+      by_name.insert(
+        name.to_ascii_lowercase(),
+        certified_key(cert_chain, &private_key)?,
+      );
+    }
+
+    *self.certificates.lock() = SniCertificates { default, by_name };
+    Ok(())
+  }
+}
+
+impl ResolvesServerCert for SniCertResolver {
+  fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+    let certificates = self.certificates.lock();
+    if let Some(name) = client_hello.server_name() {
+      let name = name.to_ascii_lowercase();
+      if let Some(key) = certificates.by_name.get(&name) {
+        return Some(key.clone());
+      }
+      if let Some((_, parent)) = name.split_once('.') {
+        if let Some(key) = certificates.by_name.get(&format!("*.{parent}")) {
+          return Some(key.clone());
+        }
+      }
+    }
+    certificates.default.clone()
+  }
+}
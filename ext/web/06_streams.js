@@ -70,6 +70,7 @@ const {
 } = primordials;
 import { createFilteredInspectProxy } from "internal:deno_console/02_console.js";
 import { assert, AssertionError } from "internal:deno_web/00_infra.js";
+import DOMException from "internal:deno_web/01_dom_exception.js";
 
 /** @template T */
 class Deferred {
@@ -3081,6 +3082,250 @@ function readableByteStreamTee(stream) {
   return [branch1, branch2];
 }
 
+/**
+ * @param {MessagePort} port
+ * @param {"chunk" | "pull" | "close" | "error"} type
+ * @param {any} value
+ */
+function packAndPostMessage(port, type, value) {
+  port.postMessage({ type, value });
+}
+
+/**
+ * @param {MessagePort} port
+ * @param {"chunk" | "error"} type
+ * @param {any} value
+ */
+function packAndPostMessageHandlingError(port, type, value) {
+  try {
+    packAndPostMessage(port, type, value);
+  } catch (error) {
+    packAndPostMessage(port, "error", error);
+    throw error;
+  }
+}
+
+/**
+ * @param {MessagePort} port
+ * @param {(error: DOMException) => void} onError
+ */
+function setUpCrossRealmMessageError(port, onError) {
+  port.addEventListener("messageerror", () => {
+    const error = new DOMException(
+      "Failed to deserialize stream message",
+      "DataCloneError",
+    );
+    packAndPostMessage(port, "error", error);
+    onError(error);
+    port.close();
+  });
+}
+
+/**
+ * @param {ReadableStream} stream
+ * @param {MessagePort} port
+ */
+function setUpCrossRealmTransformReadable(stream, port) {
+  initializeReadableStream(stream);
+  const controller = webidl.createBranded(ReadableStreamDefaultController);
+
+  port.addEventListener("message", (event) => {
+    const { type, value } = event.data;
+    switch (type) {
+      case "chunk":
+        readableStreamDefaultControllerEnqueue(controller, value);
+        break;
+      case "close":
+        readableStreamDefaultControllerClose(controller);
+        port.close();
+        break;
+      case "error":
+        readableStreamDefaultControllerError(controller, value);
+        port.close();
+        break;
+    }
+  });
+  setUpCrossRealmMessageError(
+    port,
+    (error) => readableStreamDefaultControllerError(controller, error),
+  );
+  port.start();
+
+  function startAlgorithm() {
+    return undefined;
+  }
+
+  function pullAlgorithm() {
+    packAndPostMessage(port, "pull", undefined);
+    return resolvePromiseWith(undefined);
+  }
+
+  function cancelAlgorithm(reason) {
+    try {
+      packAndPostMessageHandlingError(port, "error", reason);
+    } catch (error) {
+      return PromiseReject(error);
+    } finally {
+      port.close();
+    }
+    return resolvePromiseWith(undefined);
+  }
+
+  setUpReadableStreamDefaultController(
+    stream,
+    controller,
+    startAlgorithm,
+    pullAlgorithm,
+    cancelAlgorithm,
+    0,
+    () => 1,
+  );
+}
+
+/**
+ * @param {WritableStream} stream
+ * @param {MessagePort} port
+ */
+function setUpCrossRealmTransformWritable(stream, port) {
+  initializeWritableStream(stream);
+  const controller = webidl.createBranded(WritableStreamDefaultController);
+  /** @type {Deferred<void> | undefined} */
+  let backpressurePromise = new Deferred();
+
+  port.addEventListener("message", (event) => {
+    const { type, value } = event.data;
+    switch (type) {
+      case "pull":
+        if (backpressurePromise !== undefined) {
+          backpressurePromise.resolve(undefined);
+          backpressurePromise = undefined;
+        }
+        break;
+      case "error":
+        writableStreamDefaultControllerErrorIfNeeded(controller, value);
+        if (backpressurePromise !== undefined) {
+          backpressurePromise.resolve(undefined);
+          backpressurePromise = undefined;
+        }
+        break;
+    }
+  });
+  setUpCrossRealmMessageError(
+    port,
+    (error) => writableStreamDefaultControllerErrorIfNeeded(controller, error),
+  );
+  port.start();
+
+  function startAlgorithm() {
+    return undefined;
+  }
+
+  function writeAlgorithm(chunk) {
+    if (backpressurePromise === undefined) {
+      backpressurePromise = new Deferred();
+      backpressurePromise.resolve(undefined);
+    }
+    return transformPromiseWith(backpressurePromise.promise, () => {
+      backpressurePromise = new Deferred();
+      try {
+        packAndPostMessageHandlingError(port, "chunk", chunk);
+      } catch (error) {
+        port.close();
+        throw error;
+      }
+    });
+  }
+
+  function closeAlgorithm() {
+    packAndPostMessage(port, "close", undefined);
+    port.close();
+    return resolvePromiseWith(undefined);
+  }
+
+  function abortAlgorithm(reason) {
+    try {
+      packAndPostMessageHandlingError(port, "error", reason);
+    } catch (error) {
+      return PromiseReject(error);
+    } finally {
+      port.close();
+    }
+    return resolvePromiseWith(undefined);
+  }
+
+  setUpWritableStreamDefaultController(
+    stream,
+    controller,
+    startAlgorithm,
+    writeAlgorithm,
+    closeAlgorithm,
+    abortAlgorithm,
+    1,
+    () => 1,
+  );
+}
+
+/**
+ * Transfer steps of a ReadableStream: pipes the stream into `port`, whose
+ * entangled counterpart is sent to the receiving realm.
+ * @param {ReadableStream} stream
+ * @param {MessagePort} port
+ */
+function transferReadableStream(stream, port) {
+  const writable = webidl.createBranded(WritableStream);
+  setUpCrossRealmTransformWritable(writable, port);
+  const promise = readableStreamPipeTo(stream, writable, false, false, false);
+  setPromiseIsHandledToTrue(promise);
+}
+
+/**
+ * Transfer-receiving steps of a ReadableStream.
+ * @param {MessagePort} port
+ * @returns {ReadableStream}
+ */
+function readableStreamForTransferredPort(port) {
+  const stream = webidl.createBranded(ReadableStream);
+  setUpCrossRealmTransformReadable(stream, port);
+  return stream;
+}
+
+/**
+ * Transfer steps of a WritableStream: pipes data arriving on `port` into the
+ * stream.
+ * @param {WritableStream} stream
+ * @param {MessagePort} port
+ */
+function transferWritableStream(stream, port) {
+  const readable = webidl.createBranded(ReadableStream);
+  setUpCrossRealmTransformReadable(readable, port);
+  const promise = readableStreamPipeTo(readable, stream, false, false, false);
+  setPromiseIsHandledToTrue(promise);
+}
+
+/**
+ * Transfer-receiving steps of a WritableStream.
+ * @param {MessagePort} port
+ * @returns {WritableStream}
+ */
+function writableStreamForTransferredPort(port) {
+  const stream = webidl.createBranded(WritableStream);
+  setUpCrossRealmTransformWritable(stream, port);
+  return stream;
+}
+
+/**
+ * Transfer-receiving steps of a TransformStream.
+ * @param {MessagePort} readablePort
+ * @param {MessagePort} writablePort
+ * @returns {TransformStream}
+ */
+function transformStreamForTransferredPorts(readablePort, writablePort) {
+  const stream = webidl.createBranded(TransformStream);
+  stream[_readable] = readableStreamForTransferredPort(readablePort);
+  stream[_writable] = writableStreamForTransferredPort(writablePort);
+  return stream;
+}
+
 /**
  * @param {ReadableStream<ArrayBuffer>} stream
  * @param {ReadableByteStreamController} controller
@@ -6225,6 +6470,8 @@ export {
   getReadableStreamResourceBacking,
   getWritableStreamResourceBacking,
   isReadableStreamDisturbed,
+  isReadableStreamLocked,
+  isWritableStreamLocked,
   ReadableByteStreamController,
   ReadableStream,
   ReadableStreamBYOBReader,
@@ -6235,16 +6482,23 @@ export {
   ReadableStreamDefaultReader,
   readableStreamDisturb,
   readableStreamForRid,
+  readableStreamForTransferredPort,
   readableStreamForRidUnrefable,
   readableStreamForRidUnrefableRef,
   readableStreamForRidUnrefableUnref,
   ReadableStreamPrototype,
   readableStreamThrowIfErrored,
+  transferReadableStream,
+  transferWritableStream,
   TransformStream,
   TransformStreamDefaultController,
+  transformStreamForTransferredPorts,
+  TransformStreamPrototype,
   WritableStream,
   writableStreamClose,
   WritableStreamDefaultController,
   WritableStreamDefaultWriter,
   writableStreamForRid,
+  writableStreamForTransferredPort,
+  WritableStreamPrototype,
 };
@@ -16,6 +16,18 @@ import {
   setEventTargetData,
 } from "internal:deno_web/02_event.js";
 import DOMException from "internal:deno_web/01_dom_exception.js";
+import {
+  isReadableStreamLocked,
+  isWritableStreamLocked,
+  ReadableStreamPrototype,
+  readableStreamForTransferredPort,
+  transferReadableStream,
+  transferWritableStream,
+  transformStreamForTransferredPorts,
+  TransformStreamPrototype,
+  writableStreamForTransferredPort,
+  WritableStreamPrototype,
+} from "internal:deno_web/06_streams.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
   ArrayBufferPrototype,
@@ -24,6 +36,7 @@ const {
   ArrayPrototypePush,
   ObjectPrototypeIsPrototypeOf,
   ObjectSetPrototypeOf,
+  SafeSet,
   Symbol,
   SymbolFor,
   SymbolIterator,
@@ -215,6 +228,31 @@ function deserializeJsMessageData(messageData) {
         ArrayPrototypePush(hostObjects, port);
         break;
       }
+      case "readableStream": {
+        const stream = readableStreamForTransferredPort(
+          createMessagePort(transferable.data),
+        );
+        ArrayPrototypePush(transferables, stream);
+        ArrayPrototypePush(hostObjects, stream);
+        break;
+      }
+      case "writableStream": {
+        const stream = writableStreamForTransferredPort(
+          createMessagePort(transferable.data),
+        );
+        ArrayPrototypePush(transferables, stream);
+        ArrayPrototypePush(hostObjects, stream);
+        break;
+      }
+      case "transformStream": {
+        const stream = transformStreamForTransferredPorts(
+          createMessagePort(transferable.data[0]),
+          createMessagePort(transferable.data[1]),
+        );
+        ArrayPrototypePush(transferables, stream);
+        ArrayPrototypePush(hostObjects, stream);
+        break;
+      }
       case "arrayBuffer": {
         ArrayPrototypePush(transferredArrayBuffers, transferable.data);
         const index = ArrayPrototypePush(transferables, null);
@@ -239,6 +277,85 @@ function deserializeJsMessageData(messageData) {
   return [data, transferables];
 }
 
+/**
+ * @param {unknown} value
+ * @returns {boolean}
+ */
+function isTransferableStream(value) {
+  return ObjectPrototypeIsPrototypeOf(ReadableStreamPrototype, value) ||
+    ObjectPrototypeIsPrototypeOf(WritableStreamPrototype, value) ||
+    ObjectPrototypeIsPrototypeOf(TransformStreamPrototype, value);
+}
+
+/**
+ * @param {ReadableStream | WritableStream | TransformStream} stream
+ */
+function assertStreamTransferable(stream) {
+  if (ObjectPrototypeIsPrototypeOf(ReadableStreamPrototype, stream)) {
+    if (isReadableStreamLocked(stream)) {
+      throw new DOMException(
+        "Can not transfer locked ReadableStream",
+        "DataCloneError",
+      );
+    }
+  } else if (ObjectPrototypeIsPrototypeOf(WritableStreamPrototype, stream)) {
+    if (isWritableStreamLocked(stream)) {
+      throw new DOMException(
+        "Can not transfer locked WritableStream",
+        "DataCloneError",
+      );
+    }
+  } else if (
+    isReadableStreamLocked(stream.readable) ||
+    isWritableStreamLocked(stream.writable)
+  ) {
+    throw new DOMException(
+      "Can not transfer locked TransformStream",
+      "DataCloneError",
+    );
+  }
+}
+
+/**
+ * Runs the transfer steps of `stream`, returning the ids of the message
+ * ports that carry it to the receiving realm.
+ * @param {ReadableStream | WritableStream | TransformStream} stream
+ * @returns {messagePort.Transferable}
+ */
+function transferStream(stream) {
+  if (ObjectPrototypeIsPrototypeOf(ReadableStreamPrototype, stream)) {
+    const channel = new MessageChannel();
+    transferReadableStream(stream, channel.port1);
+    return { kind: "readableStream", data: takePortId(channel.port2) };
+  }
+  if (ObjectPrototypeIsPrototypeOf(WritableStreamPrototype, stream)) {
+    const channel = new MessageChannel();
+    transferWritableStream(stream, channel.port1);
+    return { kind: "writableStream", data: takePortId(channel.port2) };
+  }
+  const readableChannel = new MessageChannel();
+  const writableChannel = new MessageChannel();
+  transferReadableStream(stream.readable, readableChannel.port1);
+  transferWritableStream(stream.writable, writableChannel.port1);
+  return {
+    kind: "transformStream",
+    data: [
+      takePortId(readableChannel.port2),
+      takePortId(writableChannel.port2),
+    ],
+  };
+}
+
+/**
+ * @param {MessagePort} port
+ * @returns {number}
+ */
+function takePortId(port) {
+  const id = port[_id];
+  port[_id] = null;
+  return id;
+}
+
 /**
  * @param {any} data
  * @param {object[]} transferables
@@ -260,11 +377,29 @@ function serializeJsMessageData(data, transferables) {
     }
   }
 
+  // Streams are plain JS objects, they are passed as host objects so the
+  // serializer writes them as references into the transfer list.
+  const hostObjects = [];
+  const streams = new SafeSet();
+  for (let i = 0; i < transferables.length; ++i) {
+    const transferable = transferables[i];
+    if (ObjectPrototypeIsPrototypeOf(MessagePortPrototype, transferable)) {
+      ArrayPrototypePush(hostObjects, transferable);
+    } else if (isTransferableStream(transferable)) {
+      if (streams.has(transferable)) {
+        throw new DOMException(
+          `Stream at index ${i} is transferred more than once`,
+          "DataCloneError",
+        );
+      }
+      streams.add(transferable);
+      assertStreamTransferable(transferable);
+      ArrayPrototypePush(hostObjects, transferable);
+    }
+  }
+
   const serializedData = core.serialize(data, {
-    hostObjects: ArrayPrototypeFilter(
-      transferables,
-      (a) => ObjectPrototypeIsPrototypeOf(MessagePortPrototype, a),
-    ),
+    hostObjects,
     transferredArrayBuffers,
   }, (err) => {
     throw new DOMException(err, "DataCloneError");
@@ -298,6 +433,8 @@ function serializeJsMessageData(data, transferables) {
         data: transferredArrayBuffers[arrayBufferI],
       });
       arrayBufferI++;
+    } else if (isTransferableStream(transferable)) {
+      ArrayPrototypePush(serializedTransferables, transferStream(transferable));
     } else {
       throw new DOMException("Value not transferable", "DataCloneError");
     }
@@ -103,6 +103,15 @@ declare module "internal:deno_web/13_message_port.js" {
   } | {
     kind: "arrayBuffer";
     data: number;
+  } | {
+    kind: "readableStream";
+    data: number;
+  } | {
+    kind: "writableStream";
+    data: number;
+  } | {
+    kind: "transformStream";
+    data: [number, number];
   };
   interface MessageData {
     data: Uint8Array;
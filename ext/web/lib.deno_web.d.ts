@@ -846,7 +846,12 @@ declare class MessageEvent<T = any> extends Event {
 }
 
 /** @category DOM APIs */
-type Transferable = ArrayBuffer | MessagePort;
+type Transferable =
+  | ArrayBuffer
+  | MessagePort
+  | ReadableStream
+  | WritableStream
+  | TransformStream;
 
 /**
  * This type has been renamed to StructuredSerializeOptions. Use that type for
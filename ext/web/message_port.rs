@@ -24,6 +24,9 @@ use tokio::sync::mpsc::UnboundedSender;
 enum Transferable {
   MessagePort(MessagePort),
   ArrayBuffer(u32),
+  ReadableStream(MessagePort),
+  WritableStream(MessagePort),
+  TransformStream(MessagePort, MessagePort),
 }
 
 type MessagePortMessage = (DetachedBuffer, Vec<Transferable>);
@@ -136,6 +139,9 @@ pub enum JsTransferable {
   #[serde(rename_all = "camelCase")]
   MessagePort(ResourceId),
   ArrayBuffer(u32),
+  ReadableStream(ResourceId),
+  WritableStream(ResourceId),
+  TransformStream((ResourceId, ResourceId)),
 }
 
 fn deserialize_js_transferables(
@@ -146,23 +152,52 @@ fn deserialize_js_transferables(
   for js_transferable in js_transferables {
     match js_transferable {
       JsTransferable::MessagePort(id) => {
-        let resource = state
-          .resource_table
-          .take::<MessagePortResource>(id)
-          .map_err(|_| type_error("Invalid message port transfer"))?;
-        resource.cancel.cancel();
-        let resource = Rc::try_unwrap(resource)
-          .map_err(|_| type_error("Message port is not ready for transfer"))?;
-        transferables.push(Transferable::MessagePort(resource.port));
+        let port = take_message_port(state, id)?;
+        transferables.push(Transferable::MessagePort(port));
       }
       JsTransferable::ArrayBuffer(id) => {
         transferables.push(Transferable::ArrayBuffer(id));
       }
+      JsTransferable::ReadableStream(id) => {
+        let port = take_message_port(state, id)?;
+        transferables.push(Transferable::ReadableStream(port));
+      }
+      JsTransferable::WritableStream(id) => {
+        let port = take_message_port(state, id)?;
+        transferables.push(Transferable::WritableStream(port));
+      }
+      JsTransferable::TransformStream((readable_id, writable_id)) => {
+        let readable_port = take_message_port(state, readable_id)?;
+        let writable_port = take_message_port(state, writable_id)?;
+        transferables
+          .push(Transferable::TransformStream(readable_port, writable_port));
+      }
     }
   }
   Ok(transferables)
 }
 
+fn take_message_port(
+  state: &mut OpState,
+  rid: ResourceId,
+) -> Result<MessagePort, AnyError> {
+  let resource = state
+    .resource_table
+    .take::<MessagePortResource>(rid)
+    .map_err(|_| type_error("Invalid message port transfer"))?;
+  resource.cancel.cancel();
+  let resource = Rc::try_unwrap(resource)
+    .map_err(|_| type_error("Message port is not ready for transfer"))?;
+  Ok(resource.port)
+}
+
+fn add_message_port(state: &mut OpState, port: MessagePort) -> ResourceId {
+  state.resource_table.add(MessagePortResource {
+    port,
+    cancel: CancelHandle::new(),
+  })
+}
+
 fn serialize_transferables(
   state: &mut OpState,
   transferables: Vec<Transferable>,
@@ -171,15 +206,28 @@ fn serialize_transferables(
   for transferable in transferables {
     match transferable {
       Transferable::MessagePort(port) => {
-        let rid = state.resource_table.add(MessagePortResource {
-          port,
-          cancel: CancelHandle::new(),
-        });
+        let rid = add_message_port(state, port);
         js_transferables.push(JsTransferable::MessagePort(rid));
       }
       Transferable::ArrayBuffer(id) => {
         js_transferables.push(JsTransferable::ArrayBuffer(id));
       }
+      Transferable::ReadableStream(port) => {
+        let rid = add_message_port(state, port);
+        js_transferables.push(JsTransferable::ReadableStream(rid));
+      }
+      Transferable::WritableStream(port) => {
+        let rid = add_message_port(state, port);
+        js_transferables.push(JsTransferable::WritableStream(rid));
+      }
+      Transferable::TransformStream(readable_port, writable_port) => {
+        let readable_rid = add_message_port(state, readable_port);
+        let writable_rid = add_message_port(state, writable_port);
+        js_transferables.push(JsTransferable::TransformStream((
+          readable_rid,
+          writable_rid,
+        )));
+      }
     }
   }
   js_transferables
@@ -5,7 +5,11 @@
 const core = globalThis.Deno.core;
 const ops = core.ops;
 import * as webidl from "internal:deno_webidl/00_webidl.js";
-import { Deferred, writableStreamClose } from "internal:deno_web/06_streams.js";
+import {
+  CountQueuingStrategy,
+  Deferred,
+  writableStreamClose,
+} from "internal:deno_web/06_streams.js";
 import DOMException from "internal:deno_web/01_dom_exception.js";
 import { add, remove } from "internal:deno_web/03_abort_signal.js";
 import {
@@ -17,6 +21,7 @@ const primordials = globalThis.__bootstrap.primordials;
 const {
   ArrayPrototypeJoin,
   ArrayPrototypeMap,
+  ArrayPrototypePush,
   Error,
   ObjectPrototypeIsPrototypeOf,
   PromisePrototypeCatch,
@@ -48,6 +53,16 @@ webidl.converters.WebSocketStreamOptions = webidl.createDictionaryConverter(
       key: "headers",
       converter: webidl.converters.HeadersInit,
     },
+    {
+      key: "readableHighWaterMark",
+      converter: webidl.converters["unrestricted double"],
+      defaultValue: 1,
+    },
+    {
+      key: "writableHighWaterMark",
+      converter: webidl.converters["unrestricted double"],
+      defaultValue: 1,
+    },
   ],
 );
 webidl.converters.WebSocketCloseInfo = webidl.createDictionaryConverter(
@@ -73,6 +88,11 @@ const _connection = Symbol("[[connection]]");
 const _closed = Symbol("[[closed]]");
 const _earlyClose = Symbol("[[earlyClose]]");
 const _closeSent = Symbol("[[closeSent]]");
+const _pongWaiters = Symbol("[[pongWaiters]]");
+const _readableHighWaterMark = Symbol("[[readableHighWaterMark]]");
+const _writableHighWaterMark = Symbol("[[writableHighWaterMark]]");
+const _idleTimeout = Symbol("[[idleTimeout]]");
+const _idleTimeoutTimeout = Symbol("[[idleTimeoutTimeout]]");
 class WebSocketStream {
   [_rid];
 
@@ -112,6 +132,8 @@ class WebSocketStream {
     }
 
     this[_url] = wsURL.href;
+    this[_readableHighWaterMark] = options.readableHighWaterMark;
+    this[_writableHighWaterMark] = options.writableHighWaterMark;
 
     if (
       options.protocols.length !==
@@ -197,138 +219,12 @@ class WebSocketStream {
               },
             );
           } else {
-            this[_rid] = create.rid;
-
-            const writable = new WritableStream({
-              write: async (chunk) => {
-                if (typeof chunk === "string") {
-                  await core.opAsync("op_ws_send", this[_rid], {
-                    kind: "text",
-                    value: chunk,
-                  });
-                } else if (
-                  ObjectPrototypeIsPrototypeOf(Uint8ArrayPrototype, chunk)
-                ) {
-                  await core.opAsync("op_ws_send", this[_rid], {
-                    kind: "binary",
-                    value: chunk,
-                  }, chunk);
-                } else {
-                  throw new TypeError(
-                    "A chunk may only be either a string or an Uint8Array",
-                  );
-                }
-              },
-              close: async (reason) => {
-                try {
-                  this.close(reason?.code !== undefined ? reason : {});
-                } catch (_) {
-                  this.close();
-                }
-                await this.closed;
-              },
-              abort: async (reason) => {
-                try {
-                  this.close(reason?.code !== undefined ? reason : {});
-                } catch (_) {
-                  this.close();
-                }
-                await this.closed;
-              },
-            });
-            const pull = async (controller) => {
-              const { kind, value } = await core.opAsync(
-                "op_ws_next_event",
-                this[_rid],
-              );
-
-              switch (kind) {
-                case "string": {
-                  controller.enqueue(value);
-                  break;
-                }
-                case "binary": {
-                  controller.enqueue(value);
-                  break;
-                }
-                case "ping": {
-                  await core.opAsync("op_ws_send", this[_rid], {
-                    kind: "pong",
-                  });
-                  await pull(controller);
-                  break;
-                }
-                case "closed":
-                case "close": {
-                  this[_closed].resolve(value);
-                  core.tryClose(this[_rid]);
-                  break;
-                }
-                case "error": {
-                  const err = new Error(value);
-                  this[_closed].reject(err);
-                  controller.error(err);
-                  core.tryClose(this[_rid]);
-                  break;
-                }
-              }
-
-              if (
-                this[_closeSent].state === "fulfilled" &&
-                this[_closed].state === "pending"
-              ) {
-                if (
-                  new Date().getTime() - await this[_closeSent].promise <=
-                    CLOSE_RESPONSE_TIMEOUT
-                ) {
-                  return pull(controller);
-                }
-
-                this[_closed].resolve(value);
-                core.tryClose(this[_rid]);
-              }
-            };
-            const readable = new ReadableStream({
-              start: (controller) => {
-                PromisePrototypeThen(this.closed, () => {
-                  try {
-                    controller.close();
-                  } catch (_) {
-                    // needed to ignore warnings & assertions
-                  }
-                  try {
-                    PromisePrototypeCatch(
-                      writableStreamClose(writable),
-                      () => {},
-                    );
-                  } catch (_) {
-                    // needed to ignore warnings & assertions
-                  }
-                });
-
-                PromisePrototypeThen(this[_closeSent].promise, () => {
-                  if (this[_closed].state === "pending") {
-                    return pull(controller);
-                  }
-                });
-              },
-              pull,
-              cancel: async (reason) => {
-                try {
-                  this.close(reason?.code !== undefined ? reason : {});
-                } catch (_) {
-                  this.close();
-                }
-                await this.closed;
-              },
-            });
-
-            this[_connection].resolve({
-              readable,
-              writable,
-              extensions: create.extensions ?? "",
-              protocol: create.protocol ?? "",
-            });
+            openWebSocketStream(
+              this,
+              create.rid,
+              create.protocol ?? "",
+              create.extensions ?? "",
+            );
           }
         },
         (err) => {
@@ -359,6 +255,30 @@ class WebSocketStream {
     return this[_closed].promise;
   }
 
+  [_pongWaiters] = [];
+  [_readableHighWaterMark] = 1;
+  [_writableHighWaterMark] = 1;
+  [_idleTimeout] = 0;
+  [_idleTimeoutTimeout] = null;
+
+  ping() {
+    webidl.assertBranded(this, WebSocketStreamPrototype);
+    if (
+      this[_connection].state !== "fulfilled" ||
+      this[_closed].state !== "pending"
+    ) {
+      throw new DOMException("The WebSocket is not open.", "InvalidStateError");
+    }
+    const deferred = new Deferred();
+    ArrayPrototypePush(this[_pongWaiters], deferred);
+    PromisePrototypeThen(
+      core.opAsync("op_ws_send", this[_rid], { kind: "ping" }),
+      undefined,
+      (err) => deferred.reject(err),
+    );
+    return deferred.promise;
+  }
+
   close(closeInfo) {
     webidl.assertBranded(this, WebSocketStreamPrototype);
     closeInfo = webidl.converters.WebSocketCloseInfo(closeInfo, {
@@ -421,4 +341,276 @@ class WebSocketStream {
 
 const WebSocketStreamPrototype = WebSocketStream.prototype;
 
-export { WebSocketStream };
+/**
+ * Sets up the streams of a `WebSocketStream` once the connection has been
+ * established and resolves `connection`.
+ * @param {WebSocketStream} ws
+ * @param {number} rid
+ * @param {string} protocol
+ * @param {string} extensions
+ */
+function openWebSocketStream(ws, rid, protocol, extensions) {
+  ws[_rid] = rid;
+
+  const writable = new WritableStream({
+    write: async (chunk) => {
+      if (typeof chunk === "string") {
+        await core.opAsync("op_ws_send", ws[_rid], {
+          kind: "text",
+          value: chunk,
+        });
+      } else if (ObjectPrototypeIsPrototypeOf(Uint8ArrayPrototype, chunk)) {
+        await core.opAsync("op_ws_send", ws[_rid], {
+          kind: "binary",
+          value: chunk,
+        }, chunk);
+      } else {
+        throw new TypeError(
+          "A chunk may only be either a string or an Uint8Array",
+        );
+      }
+    },
+    close: async (reason) => {
+      try {
+        ws.close(reason?.code !== undefined ? reason : {});
+      } catch (_) {
+        ws.close();
+      }
+      await ws.closed;
+    },
+    abort: async (reason) => {
+      try {
+        ws.close(reason?.code !== undefined ? reason : {});
+      } catch (_) {
+        ws.close();
+      }
+      await ws.closed;
+    },
+  }, new CountQueuingStrategy({ highWaterMark: ws[_writableHighWaterMark] }));
+  const pull = async (controller) => {
+    let event;
+    handleIdleTimeout(ws);
+    try {
+      event = await core.opAsync("op_ws_next_event", ws[_rid]);
+    } catch (err) {
+      // The resource was closed while waiting, e.g. after an idle timeout.
+      if (ws[_closed].state !== "pending") {
+        return;
+      }
+      throw err;
+    } finally {
+      clearTimeout(ws[_idleTimeoutTimeout]);
+      ws[_idleTimeoutTimeout] = null;
+    }
+    const { kind, value } = event;
+
+    switch (kind) {
+      case "string": {
+        controller.enqueue(value);
+        break;
+      }
+      case "binary": {
+        controller.enqueue(value);
+        break;
+      }
+      case "ping": {
+        await core.opAsync("op_ws_send", ws[_rid], {
+          kind: "pong",
+        });
+        await pull(controller);
+        break;
+      }
+      case "pong": {
+        const waiters = ws[_pongWaiters];
+        ws[_pongWaiters] = [];
+        for (let i = 0; i < waiters.length; ++i) {
+          waiters[i].resolve();
+        }
+        await pull(controller);
+        break;
+      }
+      case "closed":
+      case "close": {
+        ws[_closed].resolve(value);
+        core.tryClose(ws[_rid]);
+        break;
+      }
+      case "error": {
+        const err = new Error(value);
+        ws[_closed].reject(err);
+        controller.error(err);
+        core.tryClose(ws[_rid]);
+        break;
+      }
+    }
+
+    if (
+      ws[_closeSent].state === "fulfilled" &&
+      ws[_closed].state === "pending"
+    ) {
+      if (
+        new Date().getTime() - await ws[_closeSent].promise <=
+          CLOSE_RESPONSE_TIMEOUT
+      ) {
+        return pull(controller);
+      }
+
+      ws[_closed].resolve(value);
+      core.tryClose(ws[_rid]);
+    }
+  };
+  const readable = new ReadableStream({
+    start: (controller) => {
+      PromisePrototypeThen(ws.closed, () => {
+        try {
+          controller.close();
+        } catch (_) {
+          // needed to ignore warnings & assertions
+        }
+        try {
+          PromisePrototypeCatch(
+            writableStreamClose(writable),
+            () => {},
+          );
+        } catch (_) {
+          // needed to ignore warnings & assertions
+        }
+      });
+
+      PromisePrototypeThen(ws[_closeSent].promise, () => {
+        if (ws[_closed].state === "pending") {
+          return pull(controller);
+        }
+      });
+    },
+    pull,
+    cancel: async (reason) => {
+      try {
+        ws.close(reason?.code !== undefined ? reason : {});
+      } catch (_) {
+        ws.close();
+      }
+      await ws.closed;
+    },
+  }, new CountQueuingStrategy({ highWaterMark: ws[_readableHighWaterMark] }));
+
+  PromisePrototypeThen(ws.closed, rejectPongWaiters, rejectPongWaiters);
+  function rejectPongWaiters() {
+    const waiters = ws[_pongWaiters];
+    ws[_pongWaiters] = [];
+    for (let i = 0; i < waiters.length; ++i) {
+      waiters[i].reject(
+        new DOMException("The WebSocket was closed.", "InvalidStateError"),
+      );
+    }
+  }
+
+  ws[_connection].resolve({
+    readable,
+    writable,
+    extensions,
+    protocol,
+  });
+}
+
+/**
+ * While a read is pending, sends a ping after half of the idle timeout
+ * without incoming frames and closes the connection if nothing arrives
+ * during the other half. Time the consumer takes to read is not counted.
+ * @param {WebSocketStream} ws
+ */
+function handleIdleTimeout(ws) {
+  const idleTimeout = ws[_idleTimeout];
+  if (!idleTimeout) {
+    return;
+  }
+  clearTimeout(ws[_idleTimeoutTimeout]);
+  const timeout = setTimeout(async () => {
+    if (ws[_closed].state !== "pending") {
+      return;
+    }
+    try {
+      await core.opAsync("op_ws_send", ws[_rid], { kind: "ping" });
+    } catch (_) {
+      // The connection is gone, the pending read reports why.
+      return;
+    }
+    // A frame arrived while the ping was sent, and the read that is now
+    // pending, if any, has armed its own timer.
+    if (ws[_idleTimeoutTimeout] !== timeout) {
+      return;
+    }
+    ws[_idleTimeoutTimeout] = setTimeout(async () => {
+      if (ws[_closed].state !== "pending") {
+        return;
+      }
+      const reason = "No response from ping frame.";
+      try {
+        await core.opAsync("op_ws_close", ws[_rid], 1001, reason);
+      } catch (_) {
+        // ignore failures
+      }
+      ws[_closed].resolve({ code: 1001, reason });
+      core.tryClose(ws[_rid]);
+    }, (idleTimeout / 2) * 1000);
+  }, (idleTimeout / 2) * 1000);
+  ws[_idleTimeoutTimeout] = timeout;
+}
+
+/**
+ * Creates the server end of a WebSocket connection for
+ * `Deno.upgradeWebSocketStream()`. `connection` resolves once the upgrade
+ * response has been sent, see `acceptServerWebSocketStream()`.
+ * @param {string} url
+ * @param {object} options
+ * @param {number} [options.readableHighWaterMark]
+ * @param {number} [options.writableHighWaterMark]
+ * @param {number} [options.idleTimeout] in seconds, 0 to disable
+ * @returns {WebSocketStream}
+ */
+function createServerWebSocketStream(url, options) {
+  const ws = webidl.createBranded(WebSocketStream);
+  ws[_url] = url;
+  ws[_connection] = new Deferred();
+  ws[_earlyClose] = false;
+  ws[_closed] = new Deferred();
+  ws[_closeSent] = new Deferred();
+  ws[_pongWaiters] = [];
+  ws[_readableHighWaterMark] = options.readableHighWaterMark ?? 1;
+  ws[_writableHighWaterMark] = options.writableHighWaterMark ?? 1;
+  ws[_idleTimeout] = options.idleTimeout ?? 120;
+  ws[_idleTimeoutTimeout] = null;
+  // Don't report the rejections if the stream isn't used.
+  PromisePrototypeCatch(ws[_connection].promise, () => {});
+  PromisePrototypeCatch(ws[_closed].promise, () => {});
+  return ws;
+}
+
+/**
+ * @param {WebSocketStream} ws
+ * @param {number} rid
+ * @param {string | null} protocol
+ */
+function acceptServerWebSocketStream(ws, rid, protocol) {
+  openWebSocketStream(ws, rid, protocol ?? "", "");
+  if (ws[_earlyClose]) {
+    ws.close();
+  }
+}
+
+/**
+ * @param {WebSocketStream} ws
+ * @param {unknown} err
+ */
+function failServerWebSocketStream(ws, err) {
+  ws[_connection].reject(err);
+  ws[_closed].reject(err);
+}
+
+export {
+  acceptServerWebSocketStream,
+  createServerWebSocketStream,
+  failServerWebSocketStream,
+  WebSocketStream,
+  WebSocketStreamPrototype,
+};
@@ -195,6 +195,10 @@ impl Resource for WsStreamResource {
   fn name(&self) -> Cow<str> {
     "webSocketStream".into()
   }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel()
+  }
 }
 
 pub struct WsCancelResource(Rc<CancelHandle>);
@@ -33,6 +33,7 @@ function createWorker(
   hasSourceCode,
   sourceCode,
   permissions,
+  resourceLimits,
   name,
   workerType,
 ) {
@@ -40,6 +41,7 @@ function createWorker(
     hasSourceCode,
     name,
     permissions: serializePermissions(permissions),
+    resourceLimits,
     sourceCode,
     specifier,
     workerType,
@@ -110,6 +112,7 @@ class Worker extends EventTarget {
       hasSourceCode,
       sourceCode,
       deno?.permissions,
+      deno?.resourceLimits,
       name,
       workerType,
     );
@@ -4,14 +4,18 @@ const core = globalThis.Deno.core;
 const ops = core.ops;
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  ArrayIsArray,
+  ArrayPrototypeMap,
   Date,
   DatePrototype,
   MathTrunc,
   ObjectPrototypeIsPrototypeOf,
+  Symbol,
   SymbolAsyncIterator,
   SymbolIterator,
   Function,
   ObjectEntries,
+  String,
   Uint32Array,
 } = primordials;
 import { pathFromURL } from "internal:runtime/js/06_util.js";
@@ -49,24 +53,69 @@ async function chown(
 function copyFileSync(
   fromPath,
   toPath,
+  { preserve = false } = {},
 ) {
   ops.op_copy_file_sync(
     pathFromURL(fromPath),
     pathFromURL(toPath),
+    preserve,
   );
 }
 
 async function copyFile(
   fromPath,
   toPath,
+  { preserve = false } = {},
 ) {
   await core.opAsync(
     "op_copy_file_async",
     pathFromURL(fromPath),
     pathFromURL(toPath),
+    preserve,
   );
 }
 
+function xattrValue(value) {
+  return typeof value === "string" ? core.encode(value) : value;
+}
+
+function getXattrSync(path, name) {
+  return ops.op_get_xattr_sync(pathFromURL(path), name);
+}
+
+function getXattr(path, name) {
+  return core.opAsync("op_get_xattr_async", pathFromURL(path), name);
+}
+
+function setXattrSync(path, name, value) {
+  ops.op_set_xattr_sync(pathFromURL(path), name, xattrValue(value));
+}
+
+async function setXattr(path, name, value) {
+  await core.opAsync(
+    "op_set_xattr_async",
+    pathFromURL(path),
+    name,
+    xattrValue(value),
+  );
+}
+
+function listXattrSync(path) {
+  return ops.op_list_xattr_sync(pathFromURL(path));
+}
+
+function listXattr(path) {
+  return core.opAsync("op_list_xattr_async", pathFromURL(path));
+}
+
+function removeXattrSync(path, name) {
+  ops.op_remove_xattr_sync(pathFromURL(path), name);
+}
+
+async function removeXattr(path, name) {
+  await core.opAsync("op_remove_xattr_async", pathFromURL(path), name);
+}
+
 function cwd() {
   return ops.op_cwd();
 }
@@ -133,6 +182,127 @@ function readDir(path) {
   };
 }
 
+function walkArgs(root, patterns, {
+  maxDepth = undefined,
+  includeFiles = true,
+  includeDirs = true,
+  includeSymlinks = true,
+  followSymlinks = false,
+  stat = false,
+  exclude = [],
+  gitignore = false,
+} = {}) {
+  return {
+    root: root === undefined ? undefined : pathFromURL(root),
+    patterns,
+    maxDepth,
+    includeFiles,
+    includeDirs,
+    includeSymlinks,
+    followSymlinks,
+    stat,
+    exclude: ArrayPrototypeMap(exclude, String),
+    gitignore,
+  };
+}
+
+async function* walkEntries(args, apiName) {
+  const rid = ops.op_fs_walk_open(args, apiName);
+  try {
+    while (true) {
+      const batch = await core.opAsync("op_fs_walk_next", rid);
+      if (batch === null) {
+        break;
+      }
+      for (let i = 0; i < batch.length; ++i) {
+        const entry = batch[i];
+        if (entry.info === null) {
+          delete entry.info;
+        } else {
+          entry.info = parseFileInfo(entry.info);
+        }
+        yield entry;
+      }
+    }
+  } finally {
+    core.tryClose(rid);
+  }
+}
+
+function walk(root, options) {
+  return walkEntries(walkArgs(root, [], options), "Deno.walk()");
+}
+
+function glob(patterns, options = {}) {
+  if (!ArrayIsArray(patterns)) {
+    patterns = [patterns];
+  }
+  return walkEntries(
+    walkArgs(options.root, ArrayPrototypeMap(patterns, String), options),
+    "Deno.glob()",
+  );
+}
+
+const illegalConstructorKey = Symbol("illegalConstructorKey");
+
+class MemoryMap {
+  #rid;
+  #buffer;
+
+  constructor(key = null, rid, buffer) {
+    if (key !== illegalConstructorKey) {
+      throw new TypeError("Illegal constructor.");
+    }
+    this.#rid = rid;
+    this.#buffer = buffer;
+  }
+
+  get buffer() {
+    return this.#buffer;
+  }
+
+  #assertMapped() {
+    if (this.#rid === null) {
+      throw new TypeError("The mapping has been unmapped");
+    }
+  }
+
+  async flush() {
+    this.#assertMapped();
+    await core.opAsync("op_fs_mmap_flush_async", this.#rid);
+  }
+
+  flushSync() {
+    this.#assertMapped();
+    ops.op_fs_mmap_flush_sync(this.#rid);
+  }
+
+  unmap() {
+    if (this.#rid === null) {
+      return;
+    }
+    ops.op_fs_munmap(this.#rid, this.#buffer);
+    this.#rid = null;
+  }
+}
+
+function mmap(file, {
+  offset = 0,
+  length = undefined,
+  writable = false,
+} = {}) {
+  const args = { offset, length, writable };
+  if (typeof file === "number") {
+    args.rid = file;
+  } else if (typeof file?.rid === "number") {
+    args.rid = file.rid;
+  } else {
+    args.path = pathFromURL(file);
+  }
+  const { 0: rid, 1: buffer } = ops.op_fs_mmap(args);
+  return new MemoryMap(illegalConstructorKey, rid, buffer);
+}
+
 function readLinkSync(path) {
   return ops.op_read_link_sync(pathFromURL(path));
 }
@@ -526,8 +696,13 @@ export {
   funlockSync,
   futime,
   futimeSync,
+  getXattr,
+  getXattrSync,
+  glob,
   link,
   linkSync,
+  listXattr,
+  listXattrSync,
   lstat,
   lstatSync,
   makeTempDir,
@@ -536,6 +711,8 @@ export {
   makeTempFileSync,
   mkdir,
   mkdirSync,
+  mmap,
+  MemoryMap,
   readDir,
   readDirSync,
   readLink,
@@ -544,8 +721,12 @@ export {
   realPathSync,
   remove,
   removeSync,
+  removeXattr,
+  removeXattrSync,
   rename,
   renameSync,
+  setXattr,
+  setXattrSync,
   stat,
   statSync,
   symlink,
@@ -555,4 +736,5 @@ export {
   umask,
   utime,
   utimeSync,
+  walk,
 };
@@ -14,11 +14,13 @@ import {
   readableStreamForRid,
   writableStreamForRid,
 } from "internal:deno_web/06_streams.js";
+import { setFileFromRid } from "internal:deno_net/01_net.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
   ArrayPrototypeFilter,
   Error,
   ObjectValues,
+  TypedArrayPrototypeGetByteLength,
 } = primordials;
 
 function seekSync(
@@ -37,6 +39,62 @@ function seek(
   return core.opAsync("op_seek_async", { rid, offset, whence });
 }
 
+function readAtSync(rid, p, offset) {
+  if (p.length === 0) {
+    return 0;
+  }
+  const nread = ops.op_read_at_sync(rid, offset, p);
+  return nread === 0 ? null : nread;
+}
+
+async function readAt(rid, p, offset) {
+  if (p.length === 0) {
+    return 0;
+  }
+  const nread = await core.opAsync("op_read_at_async", rid, offset, p);
+  return nread === 0 ? null : nread;
+}
+
+function writeAtSync(rid, p, offset) {
+  return ops.op_write_at_sync(rid, offset, p);
+}
+
+function writeAt(rid, p, offset) {
+  return core.opAsync("op_write_at_async", rid, offset, p);
+}
+
+function totalByteLength(buffers) {
+  let length = 0;
+  for (let i = 0; i < buffers.length; ++i) {
+    length += TypedArrayPrototypeGetByteLength(buffers[i]);
+  }
+  return length;
+}
+
+function readvSync(rid, buffers, offset) {
+  if (totalByteLength(buffers) === 0) {
+    return 0;
+  }
+  const nread = ops.op_readv_sync(rid, offset, buffers);
+  return nread === 0 ? null : nread;
+}
+
+async function readv(rid, buffers, offset) {
+  if (totalByteLength(buffers) === 0) {
+    return 0;
+  }
+  const nread = await core.opAsync("op_readv_async", rid, offset, buffers);
+  return nread === 0 ? null : nread;
+}
+
+function writevSync(rid, buffers, offset) {
+  return ops.op_writev_sync(rid, offset, buffers);
+}
+
+function writev(rid, buffers, offset) {
+  return core.opAsync("op_writev_async", rid, offset, buffers);
+}
+
 function openSync(
   path,
   options,
@@ -132,6 +190,38 @@ class FsFile {
     return seekSync(this.rid, offset, whence);
   }
 
+  readAt(p, offset) {
+    return readAt(this.rid, p, offset);
+  }
+
+  readAtSync(p, offset) {
+    return readAtSync(this.rid, p, offset);
+  }
+
+  writeAt(p, offset) {
+    return writeAt(this.rid, p, offset);
+  }
+
+  writeAtSync(p, offset) {
+    return writeAtSync(this.rid, p, offset);
+  }
+
+  readv(buffers, offset) {
+    return readv(this.rid, buffers, offset);
+  }
+
+  readvSync(buffers, offset) {
+    return readvSync(this.rid, buffers, offset);
+  }
+
+  writev(buffers, offset) {
+    return writev(this.rid, buffers, offset);
+  }
+
+  writevSync(buffers, offset) {
+    return writevSync(this.rid, buffers, offset);
+  }
+
   stat() {
     return fstat(this.rid);
   }
@@ -159,6 +249,8 @@ class FsFile {
   }
 }
 
+setFileFromRid((rid) => new FsFile(rid));
+
 class Stdin {
   #readable;
 
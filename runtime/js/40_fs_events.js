@@ -13,8 +13,14 @@ class FsWatcher {
   #rid = 0;
 
   constructor(paths, options) {
-    const { recursive } = options;
-    this.#rid = ops.op_fs_events_open({ recursive, paths });
+    const { recursive = true, debounce, include, exclude } = options;
+    this.#rid = ops.op_fs_events_open({
+      recursive,
+      paths,
+      debounce,
+      include,
+      exclude,
+    });
   }
 
   get rid() {
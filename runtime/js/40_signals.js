@@ -21,8 +21,8 @@ function pollSignal(rid) {
   return promise;
 }
 
-function unbindSignal(rid) {
-  ops.op_signal_unbind(rid);
+function unbindSignal(rid, restoreDefault) {
+  ops.op_signal_unbind(rid, restoreDefault);
 }
 
 // Stores signal listeners and resource data. This has type of
@@ -57,25 +57,26 @@ function addSignalListener(signo, listener) {
   }
 }
 
-function removeSignalListener(signo, listener) {
+function removeSignalListener(signo, listener, options = {}) {
   checkSignalListenerType(listener);
 
   const sigData = getSignalData(signo);
   SetPrototypeDelete(sigData.listeners, listener);
 
   if (sigData.listeners.size === 0 && sigData.rid) {
-    unbindSignal(sigData.rid);
+    unbindSignal(sigData.rid, !!options.restoreDefault);
     sigData.rid = undefined;
   }
 }
 
 async function loop(sigData) {
   while (sigData.rid) {
-    if (await pollSignal(sigData.rid)) {
+    const info = await pollSignal(sigData.rid);
+    if (info === null) {
       return;
     }
     for (const listener of new SafeSetIterator(sigData.listeners)) {
-      listener();
+      listener(info);
     }
   }
 }
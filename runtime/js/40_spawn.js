@@ -1,10 +1,14 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 const core = globalThis.Deno.core;
-const ops = core.ops;
+const { BadResourcePrototype, InterruptedPrototype, ops } = core;
 const primordials = globalThis.__bootstrap.primordials;
 import { pathFromURL } from "internal:runtime/js/06_util.js";
+import { build } from "internal:runtime/js/01_build.js";
+import { errors } from "internal:runtime/js/01_errors.js";
 import { add, remove } from "internal:deno_web/03_abort_signal.js";
+import { MessageEvent } from "internal:deno_web/02_event.js";
+import DOMException from "internal:deno_web/01_dom_exception.js";
 const {
   ArrayPrototypeMap,
   ObjectEntries,
@@ -12,6 +16,7 @@ const {
   String,
   TypeError,
   PromisePrototypeThen,
+  RangeError,
   SafePromiseAll,
   SymbolFor,
   Symbol,
@@ -29,6 +34,177 @@ const illegalConstructorKey = Symbol("illegalConstructorKey");
 
 const promiseIdSymbol = SymbolFor("Deno.core.internalPromiseId");
 
+function ptySize(pty) {
+  if (!pty) {
+    return null;
+  }
+  if (pty === true) {
+    return { rows: 24, columns: 80 };
+  }
+  return { rows: pty.rows, columns: pty.columns };
+}
+
+function assertPtyStdio(options) {
+  if (
+    options?.pty &&
+    (options.stdin !== undefined || options.stdout !== undefined ||
+      options.stderr !== undefined)
+  ) {
+    throw new TypeError(
+      "stdin, stdout and stderr can not be set when spawning with a pty",
+    );
+  }
+}
+
+function resourceLimits(limits) {
+  if (limits == null) {
+    return null;
+  }
+  const { cpu, addressSpace, openFiles } = limits;
+  return { cpu, addressSpace, openFiles };
+}
+
+// Keep in sync with `MAX_MESSAGE_SIZE` in runtime/ops/spawn.rs.
+const MAX_IPC_MESSAGE_SIZE = 64 * 1024 * 1024;
+
+/**
+ * One end of the structured clone message channel between a parent process
+ * and a child spawned with `ipc: true`.
+ */
+class IpcChannel {
+  #rid;
+  #onmessage = null;
+  #onmessageerror = null;
+  #reading = false;
+  #readPromiseId = null;
+  #unrefed = false;
+
+  constructor(rid) {
+    this.#rid = rid;
+  }
+
+  postMessage(message) {
+    if (this.#rid === null) {
+      throw new TypeError("IPC channel is closed");
+    }
+    const data = core.serialize(message, undefined, (err) => {
+      throw new DOMException(err, "DataCloneError");
+    });
+    if (data.byteLength > MAX_IPC_MESSAGE_SIZE) {
+      throw new RangeError(
+        `IPC message of ${data.byteLength} bytes exceeds the 64 MiB limit`,
+      );
+    }
+    // Writes are queued in order. The promise rejects if the message couldn't
+    // be written, e.g. because the other side went away.
+    return core.opAsync("op_spawn_ipc_write", this.#rid, data);
+  }
+
+  get onmessage() {
+    return this.#onmessage;
+  }
+
+  set onmessage(handler) {
+    this.#onmessage = handler;
+    if (handler != null && !this.#reading && this.#rid !== null) {
+      this.#reading = true;
+      this.#readLoop();
+    }
+  }
+
+  get onmessageerror() {
+    return this.#onmessageerror;
+  }
+
+  set onmessageerror(handler) {
+    this.#onmessageerror = handler;
+  }
+
+  /**
+   * Messages that can't be deserialized are reported as `messageerror`
+   * events. A read failure, e.g. a corrupted frame, is reported the same way
+   * and closes the channel.
+   */
+  async #readLoop() {
+    while (this.#rid !== null) {
+      let data;
+      try {
+        const promise = core.opAsync("op_spawn_ipc_read", this.#rid);
+        this.#readPromiseId = promise[promiseIdSymbol];
+        if (this.#unrefed) core.unrefOp(this.#readPromiseId);
+        data = await promise;
+      } catch (error) {
+        if (
+          !ObjectPrototypeIsPrototypeOf(BadResourcePrototype, error) &&
+          !ObjectPrototypeIsPrototypeOf(InterruptedPrototype, error)
+        ) {
+          this.#onmessageerror?.(new MessageEvent("messageerror"));
+        }
+        break;
+      } finally {
+        this.#readPromiseId = null;
+      }
+      if (data === null) {
+        break;
+      }
+      let message;
+      try {
+        message = core.deserialize(data);
+      } catch {
+        this.#onmessageerror?.(new MessageEvent("messageerror"));
+        continue;
+      }
+      this.#onmessage?.(new MessageEvent("message", { data: message }));
+    }
+    this.close();
+  }
+
+  ref() {
+    this.#unrefed = false;
+    if (this.#readPromiseId !== null) core.refOp(this.#readPromiseId);
+  }
+
+  unref() {
+    this.#unrefed = true;
+    if (this.#readPromiseId !== null) core.unrefOp(this.#readPromiseId);
+  }
+
+  close() {
+    if (this.#rid !== null) {
+      core.tryClose(this.#rid);
+      this.#rid = null;
+    }
+  }
+}
+
+/** @type {IpcChannel | null | undefined} */
+let parentIpcChannel;
+
+/**
+ * Returns the channel to the parent process if it was already opened by
+ * `getParentIpcChannel()`, without opening it.
+ */
+function getOpenedParentIpcChannel() {
+  return parentIpcChannel ?? null;
+}
+
+/** Returns the channel to the parent process, if it was set up. */
+function getParentIpcChannel() {
+  if (parentIpcChannel === undefined) {
+    const rid = ops.op_spawn_ipc_parent_open();
+    parentIpcChannel = rid === null ? null : new IpcChannel(rid);
+  }
+  return parentIpcChannel;
+}
+
+function postMessageToParent(message) {
+  const channel = getParentIpcChannel();
+  if (channel === null) {
+    throw new TypeError("Process was not spawned with an IPC channel");
+  }
+  return channel.postMessage(message);
+}
+
 function spawnChildInner(opFn, command, apiName, {
   args = [],
   cwd = undefined,
@@ -41,6 +217,11 @@ function spawnChildInner(opFn, command, apiName, {
   stderr = "piped",
   signal = undefined,
   windowsRawArguments = false,
+  pty = false,
+  ipc = false,
+  detached = false,
+  processGroup = false,
+  limits = undefined,
 } = {}) {
   const child = opFn({
     cmd: pathFromURL(command),
@@ -54,14 +235,21 @@ function spawnChildInner(opFn, command, apiName, {
     stdout,
     stderr,
     windowsRawArguments,
+    pty: ptySize(pty),
+    ipc,
+    detached,
+    processGroup,
+    limits: resourceLimits(limits),
   }, apiName);
   return new ChildProcess(illegalConstructorKey, {
     ...child,
     signal,
+    processGroup: detached || processGroup,
   });
 }
 
 function spawnChild(command, options = {}) {
+  assertPtyStdio(options);
   return spawnChildInner(
     ops.op_spawn_child,
     command,
@@ -116,6 +304,13 @@ class ChildProcess {
     return this.#stderr;
   }
 
+  #ptyRid = null;
+  #pty = false;
+  #processGroup = false;
+
+  /** @type {IpcChannel | null} */
+  #ipc = null;
+
   constructor(key = null, {
     signal,
     rid,
@@ -123,6 +318,9 @@ class ChildProcess {
     stdinRid,
     stdoutRid,
     stderrRid,
+    ptyRid,
+    ipcRid,
+    processGroup,
   } = null) {
     if (key !== illegalConstructorKey) {
       throw new TypeError("Illegal constructor.");
@@ -130,6 +328,7 @@ class ChildProcess {
 
     this.#rid = rid;
     this.#pid = pid;
+    this.#processGroup = processGroup;
 
     if (stdinRid !== null) {
       this.#stdin = writableStreamForRid(stdinRid);
@@ -145,6 +344,15 @@ class ChildProcess {
       this.#stderr = readableStreamForRidUnrefable(stderrRid);
     }
 
+    if (ptyRid !== null) {
+      this.#ptyRid = ptyRid;
+      this.#pty = true;
+    }
+
+    if (ipcRid !== null) {
+      this.#ipc = new IpcChannel(ipcRid);
+    }
+
     const onAbort = () => this.kill("SIGTERM");
     signal?.[add](onAbort);
 
@@ -152,6 +360,10 @@ class ChildProcess {
     this.#waitPromiseId = waitPromise[promiseIdSymbol];
     this.#status = PromisePrototypeThen(waitPromise, (res) => {
       this.#rid = null;
+      if (this.#ptyRid !== null) {
+        core.tryClose(this.#ptyRid);
+        this.#ptyRid = null;
+      }
       signal?.[remove](onAbort);
       return res;
     });
@@ -199,16 +411,81 @@ class ChildProcess {
     };
   }
 
-  kill(signo = "SIGTERM") {
+  kill(signo = "SIGTERM", { group = false } = {}) {
+    // Once the child is reaped its pid, and so its process group id, may be
+    // reused, so not even the group is signalled after that.
     if (this.#rid === null) {
       throw new TypeError("Child process has already terminated.");
     }
-    ops.op_kill(this.#pid, signo, "Deno.Child.kill()");
+    if (group) {
+      if (build.os === "windows") {
+        throw new errors.NotSupported(
+          "Killing a process group is not supported on Windows",
+        );
+      }
+      if (!this.#processGroup && !this.#pty) {
+        throw new TypeError(
+          "Child process does not lead a process group, spawn it with 'processGroup' or 'detached'",
+        );
+      }
+      ops.op_kill(-this.#pid, signo, "Deno.Child.kill()");
+      return;
+    }
+    // A child attached to a pty leads its own process group, which the
+    // signal is forwarded to like a terminal would.
+    const pid = this.#pty ? -this.#pid : this.#pid;
+    ops.op_kill(pid, signo, "Deno.Child.kill()");
+  }
+
+  postMessage(message) {
+    if (this.#ipc === null) {
+      throw new TypeError("Child process was not spawned with ipc");
+    }
+    return this.#ipc.postMessage(message);
+  }
+
+  get onmessage() {
+    return this.#ipc?.onmessage ?? null;
+  }
+
+  set onmessage(handler) {
+    if (this.#ipc === null) {
+      throw new TypeError("Child process was not spawned with ipc");
+    }
+    this.#ipc.onmessage = handler;
+  }
+
+  get onmessageerror() {
+    return this.#ipc?.onmessageerror ?? null;
+  }
+
+  set onmessageerror(handler) {
+    if (this.#ipc === null) {
+      throw new TypeError("Child process was not spawned with ipc");
+    }
+    this.#ipc.onmessageerror = handler;
+  }
+
+  /** Closes the IPC channel, letting the child exit once it is done. */
+  disconnect() {
+    this.#ipc?.close();
+  }
+
+  resizePty({ rows, columns }) {
+    if (this.#ptyRid === null) {
+      throw new TypeError(
+        this.#pty
+          ? "Child process has already terminated."
+          : "Child process was not spawned with a pty",
+      );
+    }
+    ops.op_spawn_pty_resize(this.#ptyRid, { rows, columns });
   }
 
   ref() {
     this.#unrefed = false;
     core.refOp(this.#waitPromiseId);
+    this.#ipc?.ref();
     if (this.#stdout) readableStreamForRidUnrefableRef(this.#stdout);
     if (this.#stderr) readableStreamForRidUnrefableRef(this.#stderr);
   }
@@ -216,6 +493,7 @@ class ChildProcess {
   unref() {
     this.#unrefed = true;
     core.unrefOp(this.#waitPromiseId);
+    this.#ipc?.unref();
     if (this.#stdout) readableStreamForRidUnrefableUnref(this.#stdout);
     if (this.#stderr) readableStreamForRidUnrefableUnref(this.#stderr);
   }
@@ -227,6 +505,7 @@ function spawn(command, options) {
       "Piped stdin is not supported for this function, use 'Deno.Command().spawn()' instead",
     );
   }
+  assertPtyStdio(options);
   return spawnChildInner(
     ops.op_spawn_child,
     command,
@@ -247,6 +526,11 @@ function spawnSync(command, {
   stdout = "piped",
   stderr = "piped",
   windowsRawArguments = false,
+  pty = false,
+  ipc = false,
+  detached = false,
+  processGroup = false,
+  limits = undefined,
 } = {}) {
   if (stdin === "piped") {
     throw new TypeError(
@@ -265,6 +549,11 @@ function spawnSync(command, {
     stdout,
     stderr,
     windowsRawArguments,
+    pty: ptySize(pty),
+    ipc,
+    detached,
+    processGroup,
+    limits: resourceLimits(limits),
   });
   return {
     success: result.status.success,
@@ -323,4 +612,10 @@ class Command {
   }
 }
 
-export { ChildProcess, Command };
+export {
+  ChildProcess,
+  Command,
+  getOpenedParentIpcChannel,
+  getParentIpcChannel,
+  postMessageToParent,
+};
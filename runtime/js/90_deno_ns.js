@@ -174,8 +174,23 @@ const denoNsUnstable = {
   flockSync: fs.flockSync,
   funlock: fs.funlock,
   funlockSync: fs.funlockSync,
+  walk: fs.walk,
+  glob: fs.glob,
+  mmap: fs.mmap,
+  MemoryMap: fs.MemoryMap,
+  getXattr: fs.getXattr,
+  getXattrSync: fs.getXattrSync,
+  setXattr: fs.setXattr,
+  setXattrSync: fs.setXattrSync,
+  listXattr: fs.listXattr,
+  listXattrSync: fs.listXattrSync,
+  removeXattr: fs.removeXattr,
+  removeXattrSync: fs.removeXattrSync,
   upgradeHttp: http.upgradeHttp,
   upgradeHttpRaw: flash.upgradeHttpRaw,
+  upgradeWebSocketStream: http.upgradeWebSocketStream,
+  peerCertificates: flash.peerCertificates,
+  resolveDnsRecords: net.resolveDnsRecords,
 };
 
 export { denoNs, denoNsUnstable };
@@ -33,6 +33,7 @@ import * as formData from "internal:deno_fetch/21_formdata.js";
 import * as request from "internal:deno_fetch/23_request.js";
 import * as response from "internal:deno_fetch/23_response.js";
 import * as fetch from "internal:deno_fetch/26_fetch.js";
+import * as eventSource from "internal:deno_fetch/27_eventsource.js";
 import * as messagePort from "internal:deno_web/13_message_port.js";
 import * as webidl from "internal:deno_webidl/00_webidl.js";
 import DOMException from "internal:deno_web/01_dom_exception.js";
@@ -142,6 +143,7 @@ const windowOrWorkerGlobalScope = {
 const unstableWindowOrWorkerGlobalScope = {
   BroadcastChannel: util.nonEnumerable(broadcastChannel.BroadcastChannel),
   WebSocketStream: util.nonEnumerable(webSocketStream.WebSocketStream),
+  EventSource: util.nonEnumerable(eventSource.EventSource),
 
   GPU: util.nonEnumerable(webgpu.GPU),
   GPUAdapter: util.nonEnumerable(webgpu.GPUAdapter),
@@ -57,6 +57,7 @@ import * as performance from "internal:deno_web/15_performance.js";
 import * as url from "internal:deno_url/00_url.js";
 import * as fetch from "internal:deno_fetch/26_fetch.js";
 import * as messagePort from "internal:deno_web/13_message_port.js";
+import * as spawn from "internal:runtime/js/40_spawn.js";
 import { denoNs, denoNsUnstable } from "internal:runtime/js/90_deno_ns.js";
 import { errors } from "internal:runtime/js/01_errors.js";
 import * as webidl from "internal:deno_webidl/00_webidl.js";
@@ -231,6 +232,27 @@ function formatException(error) {
   }
 }
 
+/**
+ * @param {"onmessage" | "onmessageerror"} name
+ * @returns {PropertyDescriptor}
+ */
+function parentIpcHandler(name) {
+  return {
+    get() {
+      return spawn.getOpenedParentIpcChannel()?.[name] ?? null;
+    },
+    set(handler) {
+      const channel = spawn.getParentIpcChannel();
+      if (channel === null) {
+        throw new TypeError("Process was not spawned with an IPC channel");
+      }
+      channel[name] = handler;
+    },
+    enumerable: false,
+    configurable: true,
+  };
+}
+
 function runtimeStart(runtimeOptions, source) {
   core.setMacrotaskCallback(timers.handleTimerMacrotask);
   core.setMacrotaskCallback(promiseRejectMacrotaskCallback);
@@ -471,6 +493,9 @@ function bootstrapMainRuntime(runtimeOptions) {
         ops.op_node_unstable_net_listen_udp,
         ops.op_node_unstable_net_listen_unixpacket,
       ),
+      getParentIpcChannel: spawn.getParentIpcChannel,
+      // Whether unstable `Deno.Command` options, like `ipc`, can be used.
+      unstableCommandOptions: !!runtimeOptions.unstableFlag,
     },
   });
 
@@ -508,6 +533,14 @@ function bootstrapMainRuntime(runtimeOptions) {
         ops.op_net_listen_unixpacket,
       ),
     });
+    // The IPC channel to the parent process only belongs to the main worker.
+    // It is opened when a handler is first set, so inspecting `Deno` doesn't
+    // take over the channel.
+    ObjectDefineProperties(finalDenoNs, {
+      postMessage: util.writable(spawn.postMessageToParent),
+      onmessage: parentIpcHandler("onmessage"),
+      onmessageerror: parentIpcHandler("onmessageerror"),
+    });
   }
 
   // Setup `Deno` global - we're actually overriding already existing global
@@ -600,6 +633,7 @@ function bootstrapWorkerRuntime(
         ops.op_node_unstable_net_listen_udp,
         ops.op_node_unstable_net_listen_unixpacket,
       ),
+      unstableCommandOptions: !!runtimeOptions.unstableFlag,
     },
   });
 
@@ -5,13 +5,20 @@ use super::utils::into_string;
 use crate::fs_util::canonicalize_path;
 use crate::permissions::PermissionsContainer;
 use deno_core::error::custom_error;
+use deno_core::error::generic_error;
+use deno_core::error::range_error;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op;
+use deno_core::serde_v8;
+use deno_core::v8;
+use deno_core::AsyncRefCell;
 use deno_core::CancelFuture;
 use deno_core::CancelHandle;
 use deno_core::Extension;
 use deno_core::OpState;
+use deno_core::RcRef;
+use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::ZeroCopyBuf;
 use deno_crypto::rand::thread_rng;
@@ -33,11 +40,10 @@ use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
-#[cfg(not(unix))]
-use deno_core::error::generic_error;
 #[cfg(not(unix))]
 use deno_core::error::not_supported;
 
@@ -50,6 +56,14 @@ pub fn init() -> Extension {
       op_write_file_async::decl(),
       op_seek_sync::decl(),
       op_seek_async::decl(),
+      op_read_at_sync::decl(),
+      op_read_at_async::decl(),
+      op_write_at_sync::decl(),
+      op_write_at_async::decl(),
+      op_readv_sync::decl(),
+      op_readv_async::decl(),
+      op_writev_sync::decl(),
+      op_writev_async::decl(),
       op_fdatasync_sync::decl(),
       op_fdatasync_async::decl(),
       op_fsync_sync::decl(),
@@ -72,12 +86,22 @@ pub fn init() -> Extension {
       op_remove_async::decl(),
       op_copy_file_sync::decl(),
       op_copy_file_async::decl(),
+      op_get_xattr_sync::decl(),
+      op_get_xattr_async::decl(),
+      op_set_xattr_sync::decl(),
+      op_set_xattr_async::decl(),
+      op_list_xattr_sync::decl(),
+      op_list_xattr_async::decl(),
+      op_remove_xattr_sync::decl(),
+      op_remove_xattr_async::decl(),
       op_stat_sync::decl(),
       op_stat_async::decl(),
       op_realpath_sync::decl(),
       op_realpath_async::decl(),
       op_read_dir_sync::decl(),
       op_read_dir_async::decl(),
+      op_fs_walk_open::decl(),
+      op_fs_walk_next::decl(),
       op_rename_sync::decl(),
       op_rename_async::decl(),
       op_link_sync::decl(),
@@ -103,6 +127,10 @@ pub fn init() -> Extension {
       op_readfile_text_sync::decl(),
       op_readfile_async::decl(),
       op_readfile_text_async::decl(),
+      op_fs_mmap::decl(),
+      op_fs_mmap_flush_sync::decl(),
+      op_fs_mmap_flush_async::decl(),
+      op_fs_munmap::decl(),
     ])
     .build()
 }
@@ -374,6 +402,270 @@ async fn op_seek_async(
   .await
 }
 
+#[cfg(unix)]
+fn read_at(
+  file: &std::fs::File,
+  buf: &mut [u8],
+  offset: u64,
+) -> io::Result<usize> {
+  use std::os::unix::fs::FileExt;
+  file.read_at(buf, offset)
+}
+
+#[cfg(unix)]
+fn write_at(
+  file: &std::fs::File,
+  buf: &[u8],
+  offset: u64,
+) -> io::Result<usize> {
+  use std::os::unix::fs::FileExt;
+  file.write_at(buf, offset)
+}
+
+// Unlike pread/pwrite, these also move the cursor of the file, so it is put
+// back afterwards. Handles duplicated for the async ops share the cursor, so
+// a concurrent read or write that depends on it may still observe the move.
+#[cfg(windows)]
+fn read_at(
+  file: &std::fs::File,
+  buf: &mut [u8],
+  offset: u64,
+) -> io::Result<usize> {
+  use std::os::windows::fs::FileExt;
+  let mut file = file;
+  let position = file.stream_position()?;
+  let result = file.seek_read(buf, offset);
+  file.seek(SeekFrom::Start(position))?;
+  result
+}
+
+#[cfg(windows)]
+fn write_at(
+  file: &std::fs::File,
+  buf: &[u8],
+  offset: u64,
+) -> io::Result<usize> {
+  use std::os::windows::fs::FileExt;
+  let mut file = file;
+  let position = file.stream_position()?;
+  let result = file.seek_write(buf, offset);
+  file.seek(SeekFrom::Start(position))?;
+  result
+}
+
+/// Fills `bufs` in order from `offset` with a single `preadv`.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn read_vectored_at(
+  file: &std::fs::File,
+  bufs: &mut [ZeroCopyBuf],
+  offset: u64,
+) -> io::Result<usize> {
+  use nix::sys::uio::preadv;
+  use std::io::IoSliceMut;
+  use std::os::unix::io::AsRawFd;
+  let mut iovs: Vec<_> = bufs
+    .iter_mut()
+    .map(|buf| IoSliceMut::new(&mut buf[..]))
+    .collect();
+  let offset = offset
+    .try_into()
+    .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+  Ok(preadv(file.as_raw_fd(), &mut iovs, offset)?)
+}
+
+/// Writes all of `bufs` back to back from `offset`, with a single `pwritev`
+/// unless it comes up short.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn write_vectored_at(
+  file: &std::fs::File,
+  bufs: &[ZeroCopyBuf],
+  offset: u64,
+) -> io::Result<usize> {
+  use nix::sys::uio::pwritev;
+  use std::io::IoSlice;
+  use std::os::unix::io::AsRawFd;
+  let iovs: Vec<_> = bufs.iter().map(|buf| IoSlice::new(buf)).collect();
+  let file_offset = offset
+    .try_into()
+    .map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+  let nwritten = pwritev(file.as_raw_fd(), &iovs, file_offset)?;
+  write_remaining_at(file, bufs, offset, nwritten)
+}
+
+// macOS only has preadv/pwritev since 11.0, so the buffers are read and
+// written one at a time there.
+#[cfg(any(windows, target_os = "macos"))]
+fn read_vectored_at(
+  file: &std::fs::File,
+  bufs: &mut [ZeroCopyBuf],
+  offset: u64,
+) -> io::Result<usize> {
+  let mut total = 0;
+  for buf in bufs {
+    let nread = read_at(file, buf, offset + total as u64)?;
+    total += nread;
+    if nread < buf.len() {
+      break;
+    }
+  }
+  Ok(total)
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+fn write_vectored_at(
+  file: &std::fs::File,
+  bufs: &[ZeroCopyBuf],
+  offset: u64,
+) -> io::Result<usize> {
+  write_remaining_at(file, bufs, offset, 0)
+}
+
+/// Writes whatever of `bufs` is left after the first `skip` bytes, which
+/// were already written at `offset`.
+fn write_remaining_at(
+  file: &std::fs::File,
+  bufs: &[ZeroCopyBuf],
+  offset: u64,
+  skip: usize,
+) -> io::Result<usize> {
+  let mut total = 0;
+  for buf in bufs {
+    let mut buf: &[u8] = buf;
+    if total + buf.len() <= skip {
+      total += buf.len();
+      continue;
+    }
+    if total < skip {
+      buf = &buf[skip - total..];
+      total = skip;
+    }
+    while !buf.is_empty() {
+      let nwritten = write_at(file, buf, offset + total as u64)?;
+      if nwritten == 0 {
+        return Err(io::ErrorKind::WriteZero.into());
+      }
+      total += nwritten;
+      buf = &buf[nwritten..];
+    }
+  }
+  Ok(total)
+}
+
+#[op]
+fn op_read_at_sync(
+  state: &mut OpState,
+  rid: ResourceId,
+  offset: u64,
+  buf: &mut [u8],
+) -> Result<usize, AnyError> {
+  super::check_unstable(state, "Deno.FsFile.readAtSync");
+  StdFileResource::with_file(state, rid, |std_file| {
+    Ok(read_at(std_file, buf, offset)?)
+  })
+}
+
+// Positional IO does not depend on the cursor, so the async ops work on a
+// duplicated handle and run concurrently, instead of queueing behind other
+// operations on the file.
+#[op]
+async fn op_read_at_async(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  offset: u64,
+  mut buf: ZeroCopyBuf,
+) -> Result<usize, AnyError> {
+  super::check_unstable2(&state, "Deno.FsFile.readAt");
+  let std_file = StdFileResource::clone_file(&mut state.borrow_mut(), rid)?;
+  tokio::task::spawn_blocking(move || Ok(read_at(&std_file, &mut buf, offset)?))
+    .await
+    .unwrap()
+}
+
+#[op]
+fn op_write_at_sync(
+  state: &mut OpState,
+  rid: ResourceId,
+  offset: u64,
+  buf: &mut [u8],
+) -> Result<usize, AnyError> {
+  super::check_unstable(state, "Deno.FsFile.writeAtSync");
+  StdFileResource::with_file(state, rid, |std_file| {
+    Ok(write_at(std_file, buf, offset)?)
+  })
+}
+
+#[op]
+async fn op_write_at_async(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  offset: u64,
+  buf: ZeroCopyBuf,
+) -> Result<usize, AnyError> {
+  super::check_unstable2(&state, "Deno.FsFile.writeAt");
+  let std_file = StdFileResource::clone_file(&mut state.borrow_mut(), rid)?;
+  tokio::task::spawn_blocking(move || Ok(write_at(&std_file, &buf, offset)?))
+    .await
+    .unwrap()
+}
+
+#[op]
+fn op_readv_sync(
+  state: &mut OpState,
+  rid: ResourceId,
+  offset: u64,
+  mut bufs: Vec<ZeroCopyBuf>,
+) -> Result<usize, AnyError> {
+  super::check_unstable(state, "Deno.FsFile.readvSync");
+  StdFileResource::with_file(state, rid, |std_file| {
+    Ok(read_vectored_at(std_file, &mut bufs, offset)?)
+  })
+}
+
+#[op]
+async fn op_readv_async(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  offset: u64,
+  mut bufs: Vec<ZeroCopyBuf>,
+) -> Result<usize, AnyError> {
+  super::check_unstable2(&state, "Deno.FsFile.readv");
+  let std_file = StdFileResource::clone_file(&mut state.borrow_mut(), rid)?;
+  tokio::task::spawn_blocking(move || {
+    Ok(read_vectored_at(&std_file, &mut bufs, offset)?)
+  })
+  .await
+  .unwrap()
+}
+
+#[op]
+fn op_writev_sync(
+  state: &mut OpState,
+  rid: ResourceId,
+  offset: u64,
+  bufs: Vec<ZeroCopyBuf>,
+) -> Result<usize, AnyError> {
+  super::check_unstable(state, "Deno.FsFile.writevSync");
+  StdFileResource::with_file(state, rid, |std_file| {
+    Ok(write_vectored_at(std_file, &bufs, offset)?)
+  })
+}
+
+#[op]
+async fn op_writev_async(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  offset: u64,
+  bufs: Vec<ZeroCopyBuf>,
+) -> Result<usize, AnyError> {
+  super::check_unstable2(&state, "Deno.FsFile.writev");
+  let std_file = StdFileResource::clone_file(&mut state.borrow_mut(), rid)?;
+  tokio::task::spawn_blocking(move || {
+    Ok(write_vectored_at(&std_file, &bufs, offset)?)
+  })
+  .await
+  .unwrap()
+}
+
 #[op]
 fn op_fdatasync_sync(
   state: &mut OpState,
@@ -849,7 +1141,11 @@ fn op_copy_file_sync(
   state: &mut OpState,
   from: String,
   to: String,
+  preserve: bool,
 ) -> Result<(), AnyError> {
+  if preserve {
+    super::check_unstable(state, "Deno.copyFileSync#preserve");
+  }
   let from_path = PathBuf::from(&from);
   let to_path = PathBuf::from(&to);
 
@@ -857,6 +1153,39 @@ fn op_copy_file_sync(
   permissions.check_read(&from_path, "Deno.copyFileSync()")?;
   permissions.check_write(&to_path, "Deno.copyFileSync()")?;
 
+  copy_file(&from_path, &to_path, preserve)
+}
+
+#[op]
+async fn op_copy_file_async(
+  state: Rc<RefCell<OpState>>,
+  from: String,
+  to: String,
+  preserve: bool,
+) -> Result<(), AnyError> {
+  if preserve {
+    super::check_unstable2(&state, "Deno.copyFile#preserve");
+  }
+  let from = PathBuf::from(&from);
+  let to = PathBuf::from(&to);
+
+  {
+    let mut state = state.borrow_mut();
+    let permissions = state.borrow_mut::<PermissionsContainer>();
+    permissions.check_read(&from, "Deno.copyFile()")?;
+    permissions.check_write(&to, "Deno.copyFile()")?;
+  }
+
+  tokio::task::spawn_blocking(move || copy_file(&from, &to, preserve))
+    .await
+    .unwrap()
+}
+
+fn copy_file(
+  from_path: &Path,
+  to_path: &Path,
+  preserve: bool,
+) -> Result<(), AnyError> {
   // On *nix, Rust reports non-existent `from` as ErrorKind::InvalidInput
   // See https://github.com/rust-lang/rust/issues/54800
   // Once the issue is resolved, we should remove this workaround.
@@ -878,120 +1207,332 @@ fn op_copy_file_sync(
     )
   };
 
-  #[cfg(target_os = "macos")]
-  {
-    use libc::clonefile;
-    use libc::stat;
-    use libc::unlink;
-    use std::ffi::CString;
-    use std::io::Read;
-    use std::os::unix::fs::OpenOptionsExt;
-    use std::os::unix::fs::PermissionsExt;
+  copy_file_contents(from_path, to_path).map_err(err_mapper)?;
+  if preserve {
+    preserve_file_metadata(from_path, to_path).map_err(err_mapper)?;
+  }
+  Ok(())
+}
 
-    let from = CString::new(from).unwrap();
-    let to = CString::new(to).unwrap();
-
-    // SAFETY: `from` and `to` are valid C strings.
-    // std::fs::copy does open() + fcopyfile() on macOS. We try to use
-    // clonefile() instead, which is more efficient.
-    unsafe {
-      let mut st = std::mem::zeroed();
-      let ret = stat(from.as_ptr(), &mut st);
-      if ret != 0 {
-        return Err(err_mapper(Error::last_os_error()).into());
-      }
+#[cfg(target_os = "macos")]
+fn copy_file_contents(from_path: &Path, to_path: &Path) -> io::Result<()> {
+  use libc::clonefile;
+  use libc::stat;
+  use libc::unlink;
+  use std::ffi::CString;
+  use std::io::Read;
+  use std::os::unix::ffi::OsStrExt;
+  use std::os::unix::fs::OpenOptionsExt;
+  use std::os::unix::fs::PermissionsExt;
+
+  let from = CString::new(from_path.as_os_str().as_bytes())?;
+  let to = CString::new(to_path.as_os_str().as_bytes())?;
+
+  // SAFETY: `from` and `to` are valid C strings.
+  // std::fs::copy does open() + fcopyfile() on macOS. We try to use
+  // clonefile() instead, which is more efficient.
+  unsafe {
+    let mut st = std::mem::zeroed();
+    let ret = stat(from.as_ptr(), &mut st);
+    if ret != 0 {
+      return Err(Error::last_os_error());
+    }
 
-      if st.st_size > 128 * 1024 {
-        // Try unlink. If it fails, we are going to try clonefile() anyway.
-        let _ = unlink(to.as_ptr());
-        // Matches rust stdlib behavior for io::copy.
-        // https://github.com/rust-lang/rust/blob/3fdd578d72a24d4efc2fe2ad18eec3b6ba72271e/library/std/src/sys/unix/fs.rs#L1613-L1616
-        if clonefile(from.as_ptr(), to.as_ptr(), 0) == 0 {
-          return Ok(());
-        }
-      } else {
-        // Do a regular copy. fcopyfile() is an overkill for < 128KB
-        // files.
-        let mut buf = [0u8; 128 * 1024];
-        let mut from_file =
-          std::fs::File::open(&from_path).map_err(err_mapper)?;
-        let perm = from_file.metadata().map_err(err_mapper)?.permissions();
-
-        let mut to_file = std::fs::OpenOptions::new()
-          // create the file with the correct mode right away
-          .mode(perm.mode())
-          .write(true)
-          .create(true)
-          .truncate(true)
-          .open(&to_path)
-          .map_err(err_mapper)?;
-        let writer_metadata = to_file.metadata()?;
-        if writer_metadata.is_file() {
-          // Set the correct file permissions, in case the file already existed.
-          // Don't set the permissions on already existing non-files like
-          // pipes/FIFOs or device nodes.
-          to_file.set_permissions(perm)?;
-        }
-        loop {
-          let nread = from_file.read(&mut buf).map_err(err_mapper)?;
-          if nread == 0 {
-            break;
-          }
-          to_file.write_all(&buf[..nread]).map_err(err_mapper)?;
-        }
+    if st.st_size > 128 * 1024 {
+      // Try unlink. If it fails, we are going to try clonefile() anyway.
+      let _ = unlink(to.as_ptr());
+      // Matches rust stdlib behavior for io::copy.
+      // https://github.com/rust-lang/rust/blob/3fdd578d72a24d4efc2fe2ad18eec3b6ba72271e/library/std/src/sys/unix/fs.rs#L1613-L1616
+      if clonefile(from.as_ptr(), to.as_ptr(), 0) == 0 {
         return Ok(());
       }
+    } else {
+      // Do a regular copy. fcopyfile() is an overkill for < 128KB
+      // files.
+      let mut buf = [0u8; 128 * 1024];
+      let mut from_file = std::fs::File::open(from_path)?;
+      let perm = from_file.metadata()?.permissions();
+
+      let mut to_file = std::fs::OpenOptions::new()
+        // create the file with the correct mode right away
+        .mode(perm.mode())
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(to_path)?;
+      let writer_metadata = to_file.metadata()?;
+      if writer_metadata.is_file() {
+        // Set the correct file permissions, in case the file already existed.
+        // Don't set the permissions on already existing non-files like
+        // pipes/FIFOs or device nodes.
+        to_file.set_permissions(perm)?;
+      }
+      loop {
+        let nread = from_file.read(&mut buf)?;
+        if nread == 0 {
+          break;
+        }
+        to_file.write_all(&buf[..nread])?;
+      }
+      return Ok(());
     }
+  }
+
+  // clonefile() failed, fall back to std::fs::copy().
+  std::fs::copy(from_path, to_path)?;
+  Ok(())
+}
 
-    // clonefile() failed, fall back to std::fs::copy().
+/// Clones the extents of `from_path` with `FICLONE` on file systems that
+/// support it, like btrfs and XFS. Otherwise falls back to `std::fs::copy()`,
+/// which uses `copy_file_range()`.
+#[cfg(target_os = "linux")]
+fn copy_file_contents(from_path: &Path, to_path: &Path) -> io::Result<()> {
+  use std::os::unix::fs::OpenOptionsExt;
+  use std::os::unix::fs::PermissionsExt;
+  use std::os::unix::io::AsRawFd;
+
+  let from_file = std::fs::File::open(from_path)?;
+  let perm = from_file.metadata()?.permissions();
+  // Cloning into existing non-files like pipes/FIFOs or device nodes fails
+  // anyway, leave those to std::fs::copy().
+  if std::fs::metadata(to_path).map_or(true, |metadata| metadata.is_file()) {
+    let to_file = std::fs::OpenOptions::new()
+      .mode(perm.mode())
+      .write(true)
+      .create(true)
+      .truncate(true)
+      .open(to_path)?;
+    let (from_fd, to_fd) = (from_file.as_raw_fd(), to_file.as_raw_fd());
+    // SAFETY: Both descriptors stay open for the duration of the call.
+    let ret = unsafe { libc::ioctl(to_fd, libc::FICLONE as _, from_fd) };
+    if ret == 0 {
+      // Set the correct file permissions, in case the file already existed.
+      to_file.set_permissions(perm)?;
+      return Ok(());
+    }
   }
 
+  std::fs::copy(from_path, to_path)?;
+  Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn copy_file_contents(from_path: &Path, to_path: &Path) -> io::Result<()> {
   // returns size of from as u64 (we ignore)
-  std::fs::copy(&from_path, &to_path).map_err(err_mapper)?;
+  std::fs::copy(from_path, to_path)?;
+  Ok(())
+}
+
+/// Copies the mode, access and modification times, and on Unix the extended
+/// attributes of `from_path` onto `to_path`.
+fn preserve_file_metadata(from_path: &Path, to_path: &Path) -> io::Result<()> {
+  let metadata = std::fs::metadata(from_path)?;
+  std::fs::set_permissions(to_path, metadata.permissions())?;
+  let atime = filetime::FileTime::from_last_access_time(&metadata);
+  let mtime = filetime::FileTime::from_last_modification_time(&metadata);
+  filetime::set_file_times(to_path, atime, mtime)?;
+
+  #[cfg(unix)]
+  for name in xattr::list(from_path)? {
+    if let Some(value) = xattr::get(from_path, &name)? {
+      match xattr::set(to_path, &name, &value) {
+        // The destination's file system does not support the attribute, or
+        // it is in a namespace like `security.*` that needs privileges.
+        Err(err)
+          if matches!(
+            err.raw_os_error(),
+            Some(libc::ENOTSUP | libc::EPERM | libc::EACCES)
+          ) => {}
+        result => result?,
+      }
+    }
+  }
+
   Ok(())
 }
 
+#[cfg(unix)]
+fn get_xattr(path: &Path, name: &str) -> Result<Option<Vec<u8>>, AnyError> {
+  xattr::get(path, name).map_err(|err| {
+    default_err_mapper(err, format!("getxattr '{}'", path.display())).into()
+  })
+}
+
+#[cfg(unix)]
+fn set_xattr(path: &Path, name: &str, value: &[u8]) -> Result<(), AnyError> {
+  xattr::set(path, name, value).map_err(|err| {
+    default_err_mapper(err, format!("setxattr '{}'", path.display())).into()
+  })
+}
+
+#[cfg(unix)]
+fn list_xattr(path: &Path) -> Result<Vec<String>, AnyError> {
+  let names = xattr::list(path).map_err(|err| {
+    default_err_mapper(err, format!("listxattr '{}'", path.display()))
+  })?;
+  // Not all names can be encoded as UTF-8. Skip those for now.
+  Ok(names.filter_map(|name| into_string(name).ok()).collect())
+}
+
+#[cfg(unix)]
+fn remove_xattr(path: &Path, name: &str) -> Result<(), AnyError> {
+  xattr::remove(path, name).map_err(|err| {
+    default_err_mapper(err, format!("removexattr '{}'", path.display())).into()
+  })
+}
+
+#[cfg(not(unix))]
+fn get_xattr(_path: &Path, _name: &str) -> Result<Option<Vec<u8>>, AnyError> {
+  Err(not_supported())
+}
+
+#[cfg(not(unix))]
+fn set_xattr(_path: &Path, _name: &str, _value: &[u8]) -> Result<(), AnyError> {
+  Err(not_supported())
+}
+
+#[cfg(not(unix))]
+fn list_xattr(_path: &Path) -> Result<Vec<String>, AnyError> {
+  Err(not_supported())
+}
+
+#[cfg(not(unix))]
+fn remove_xattr(_path: &Path, _name: &str) -> Result<(), AnyError> {
+  Err(not_supported())
+}
+
 #[op]
-async fn op_copy_file_async(
+fn op_get_xattr_sync(
+  state: &mut OpState,
+  path: String,
+  name: String,
+) -> Result<Option<ZeroCopyBuf>, AnyError> {
+  super::check_unstable(state, "Deno.getXattrSync");
+  let path = PathBuf::from(path);
+  state
+    .borrow_mut::<PermissionsContainer>()
+    .check_read(&path, "Deno.getXattrSync()")?;
+  Ok(get_xattr(&path, &name)?.map(ZeroCopyBuf::from))
+}
+
+#[op]
+async fn op_get_xattr_async(
   state: Rc<RefCell<OpState>>,
-  from: String,
-  to: String,
+  path: String,
+  name: String,
+) -> Result<Option<ZeroCopyBuf>, AnyError> {
+  super::check_unstable2(&state, "Deno.getXattr");
+  let path = PathBuf::from(path);
+  {
+    let mut state = state.borrow_mut();
+    state
+      .borrow_mut::<PermissionsContainer>()
+      .check_read(&path, "Deno.getXattr()")?;
+  }
+  let value = tokio::task::spawn_blocking(move || get_xattr(&path, &name))
+    .await
+    .unwrap()?;
+  Ok(value.map(ZeroCopyBuf::from))
+}
+
+#[op]
+fn op_set_xattr_sync(
+  state: &mut OpState,
+  path: String,
+  name: String,
+  value: ZeroCopyBuf,
 ) -> Result<(), AnyError> {
-  let from = PathBuf::from(&from);
-  let to = PathBuf::from(&to);
+  super::check_unstable(state, "Deno.setXattrSync");
+  let path = PathBuf::from(path);
+  state
+    .borrow_mut::<PermissionsContainer>()
+    .check_write(&path, "Deno.setXattrSync()")?;
+  set_xattr(&path, &name, &value)
+}
 
+#[op]
+async fn op_set_xattr_async(
+  state: Rc<RefCell<OpState>>,
+  path: String,
+  name: String,
+  value: ZeroCopyBuf,
+) -> Result<(), AnyError> {
+  super::check_unstable2(&state, "Deno.setXattr");
+  let path = PathBuf::from(path);
   {
     let mut state = state.borrow_mut();
-    let permissions = state.borrow_mut::<PermissionsContainer>();
-    permissions.check_read(&from, "Deno.copyFile()")?;
-    permissions.check_write(&to, "Deno.copyFile()")?;
+    state
+      .borrow_mut::<PermissionsContainer>()
+      .check_write(&path, "Deno.setXattr()")?;
   }
+  tokio::task::spawn_blocking(move || set_xattr(&path, &name, &value))
+    .await
+    .unwrap()
+}
 
-  tokio::task::spawn_blocking(move || {
-    // On *nix, Rust reports non-existent `from` as ErrorKind::InvalidInput
-    // See https://github.com/rust-lang/rust/issues/54800
-    // Once the issue is resolved, we should remove this workaround.
-    if cfg!(unix) && !from.is_file() {
-      return Err(custom_error(
-        "NotFound",
-        format!(
-          "File not found, copy '{}' -> '{}'",
-          from.display(),
-          to.display()
-        ),
-      ));
-    }
-    // returns size of from as u64 (we ignore)
-    std::fs::copy(&from, &to).map_err(|err| {
-      default_err_mapper(
-        err,
-        format!("copy '{}' -> '{}'", from.display(), to.display()),
-      )
-    })?;
-    Ok(())
-  })
-  .await
-  .unwrap()
+#[op]
+fn op_list_xattr_sync(
+  state: &mut OpState,
+  path: String,
+) -> Result<Vec<String>, AnyError> {
+  super::check_unstable(state, "Deno.listXattrSync");
+  let path = PathBuf::from(path);
+  state
+    .borrow_mut::<PermissionsContainer>()
+    .check_read(&path, "Deno.listXattrSync()")?;
+  list_xattr(&path)
+}
+
+#[op]
+async fn op_list_xattr_async(
+  state: Rc<RefCell<OpState>>,
+  path: String,
+) -> Result<Vec<String>, AnyError> {
+  super::check_unstable2(&state, "Deno.listXattr");
+  let path = PathBuf::from(path);
+  {
+    let mut state = state.borrow_mut();
+    state
+      .borrow_mut::<PermissionsContainer>()
+      .check_read(&path, "Deno.listXattr()")?;
+  }
+  tokio::task::spawn_blocking(move || list_xattr(&path))
+    .await
+    .unwrap()
+}
+
+#[op]
+fn op_remove_xattr_sync(
+  state: &mut OpState,
+  path: String,
+  name: String,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.removeXattrSync");
+  let path = PathBuf::from(path);
+  state
+    .borrow_mut::<PermissionsContainer>()
+    .check_write(&path, "Deno.removeXattrSync()")?;
+  remove_xattr(&path, &name)
+}
+
+#[op]
+async fn op_remove_xattr_async(
+  state: Rc<RefCell<OpState>>,
+  path: String,
+  name: String,
+) -> Result<(), AnyError> {
+  super::check_unstable2(&state, "Deno.removeXattr");
+  let path = PathBuf::from(path);
+  {
+    let mut state = state.borrow_mut();
+    state
+      .borrow_mut::<PermissionsContainer>()
+      .check_write(&path, "Deno.removeXattr()")?;
+  }
+  tokio::task::spawn_blocking(move || remove_xattr(&path, &name))
+    .await
+    .unwrap()
 }
 
 fn to_msec(maybe_time: Result<SystemTime, io::Error>) -> (u64, bool) {
@@ -1322,6 +1863,329 @@ async fn op_read_dir_async(
   .unwrap()
 }
 
+/// Number of entries sent to JS per `op_fs_walk_next` call.
+const WALK_BATCH_SIZE: usize = 256;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkArgs {
+  /// Directory to walk, or that relative glob patterns are resolved against.
+  /// Defaults to the current working directory.
+  root: Option<String>,
+  /// Glob patterns entries have to match. Empty for `Deno.walk()`.
+  patterns: Vec<String>,
+  max_depth: Option<usize>,
+  include_files: bool,
+  include_dirs: bool,
+  include_symlinks: bool,
+  follow_symlinks: bool,
+  stat: bool,
+  exclude: Vec<String>,
+  gitignore: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkEntry {
+  path: String,
+  name: String,
+  is_file: bool,
+  is_directory: bool,
+  is_symlink: bool,
+  depth: usize,
+  info: Option<FsStat>,
+}
+
+type WalkBatch = Result<Vec<WalkEntry>, AnyError>;
+
+struct WalkResource {
+  receiver: AsyncRefCell<tokio::sync::mpsc::Receiver<WalkBatch>>,
+  cancel: CancelHandle,
+}
+
+impl Resource for WalkResource {
+  fn name(&self) -> Cow<str> {
+    "fsWalk".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel();
+  }
+}
+
+/// A directory the walker starts from, along with how deep it has to go.
+struct WalkRoot {
+  dir: PathBuf,
+  max_depth: Option<usize>,
+}
+
+fn is_glob_component(component: &str) -> bool {
+  component.contains(['*', '?', '[', '{'])
+}
+
+/// Splits every pattern into the literal directory it starts with and the
+/// rest, so only those directories have to be walked.
+fn glob_roots(
+  base: &Path,
+  patterns: &[String],
+) -> Result<(Vec<WalkRoot>, globset::GlobSet), AnyError> {
+  let mut roots = Vec::with_capacity(patterns.len());
+  let mut builder = globset::GlobSetBuilder::new();
+  for pattern in patterns {
+    let path = base.join(pattern);
+    let glob = globset::GlobBuilder::new(&path.to_string_lossy())
+      .literal_separator(true)
+      .build()
+      .map_err(|err| {
+        type_error(format!("Invalid glob \"{pattern}\": {err}"))
+      })?;
+    builder.add(glob);
+
+    let mut dir = PathBuf::new();
+    let mut rest = Vec::new();
+    for component in path.components() {
+      let name = component.as_os_str().to_string_lossy();
+      if rest.is_empty() && !is_glob_component(&name) {
+        dir.push(component);
+      } else {
+        rest.push(name);
+      }
+    }
+    // A pattern without any wildcards names a single entry, which is found
+    // by listing its parent.
+    if rest.is_empty() {
+      if let Some(parent) = dir.parent() {
+        dir = parent.to_path_buf();
+      }
+      rest.push(Cow::Borrowed(""));
+    }
+    let max_depth = if rest.iter().any(|component| component == "**") {
+      None
+    } else {
+      Some(rest.len())
+    };
+    roots.push(WalkRoot { dir, max_depth });
+  }
+  let glob_set = builder
+    .build()
+    .map_err(|err| type_error(format!("Invalid glob: {err}")))?;
+  Ok((roots, glob_set))
+}
+
+fn walk_error(err: ignore::Error) -> AnyError {
+  if err.io_error().is_some() {
+    err.into_io_error().unwrap().into()
+  } else {
+    generic_error(err.to_string())
+  }
+}
+
+/// Walks `roots` on a blocking thread and sends the entries in batches,
+/// until the receiving resource is closed.
+fn walk_blocking(
+  roots: Vec<WalkRoot>,
+  glob_set: Option<globset::GlobSet>,
+  args: WalkArgs,
+  mut permissions: PermissionsContainer,
+  api_name: String,
+  sender: tokio::sync::mpsc::Sender<WalkBatch>,
+) {
+  let mut seen = std::collections::HashSet::new();
+  let mut batch = Vec::with_capacity(WALK_BATCH_SIZE);
+  for root in roots {
+    // Nothing can match below a directory that doesn't exist.
+    if glob_set.is_some() && !root.dir.is_dir() {
+      continue;
+    }
+    let mut excludes = ignore::gitignore::GitignoreBuilder::new(&root.dir);
+    for pattern in &args.exclude {
+      if let Err(err) = excludes.add_line(None, pattern) {
+        let _ = sender.blocking_send(Err(type_error(format!(
+          "Invalid exclude pattern \"{pattern}\": {err}"
+        ))));
+        return;
+      }
+    }
+    let excludes = match excludes.build() {
+      Ok(excludes) => excludes,
+      Err(err) => {
+        let _ = sender.blocking_send(Err(type_error(err.to_string())));
+        return;
+      }
+    };
+
+    let max_depth = match (root.max_depth, args.max_depth) {
+      (Some(a), Some(b)) => Some(a.min(b)),
+      (a, b) => a.or(b),
+    };
+    let walker = ignore::WalkBuilder::new(&root.dir)
+      .standard_filters(false)
+      .git_ignore(args.gitignore)
+      .git_exclude(args.gitignore)
+      // Ignore files above the root are outside of what `check_read` allowed.
+      .parents(false)
+      .require_git(false)
+      .follow_links(args.follow_symlinks)
+      .max_depth(max_depth)
+      .filter_entry(move |entry| {
+        let is_dir = entry.file_type().map_or(false, |ft| ft.is_dir());
+        !excludes.matched(entry.path(), is_dir).is_ignore()
+      })
+      .build();
+
+    for entry in walker {
+      let entry = match entry {
+        Ok(entry) => entry,
+        Err(err) => {
+          let _ = sender.blocking_send(Err(walk_error(err)));
+          return;
+        }
+      };
+      // Only the roots were checked up front, so a followed symlink must not
+      // lead the walk out of the readable paths.
+      if args.follow_symlinks && entry.depth() > 0 && entry.path_is_symlink() {
+        let checked = std::fs::canonicalize(entry.path())
+          .map_err(AnyError::from)
+          .and_then(|target| permissions.check_read(&target, &api_name));
+        if let Err(err) = checked {
+          let _ = sender.blocking_send(Err(err));
+          return;
+        }
+      }
+      if let Some(glob_set) = &glob_set {
+        if entry.depth() == 0 || !glob_set.is_match(entry.path()) {
+          continue;
+        }
+        if !seen.insert(entry.path().to_path_buf()) {
+          continue;
+        }
+      }
+
+      let file_type = entry.file_type();
+      let is_symlink = entry.path_is_symlink();
+      let is_directory = file_type.map_or(false, |ft| ft.is_dir());
+      let is_file = file_type.map_or(false, |ft| ft.is_file());
+      let included = if is_symlink && !args.follow_symlinks {
+        args.include_symlinks
+      } else if is_directory {
+        args.include_dirs
+      } else {
+        args.include_files
+      };
+      if !included {
+        continue;
+      }
+
+      let info = if args.stat {
+        let metadata = if args.follow_symlinks {
+          std::fs::metadata(entry.path())
+        } else {
+          std::fs::symlink_metadata(entry.path())
+        };
+        match metadata {
+          Ok(metadata) => Some(get_stat(metadata)),
+          Err(err) => {
+            let desc = format!("stat '{}'", entry.path().display());
+            let err = default_err_mapper(err, desc);
+            let _ = sender.blocking_send(Err(err.into()));
+            return;
+          }
+        }
+      } else {
+        None
+      };
+
+      let depth = entry.depth();
+      let name = entry.file_name().to_os_string();
+      // Not all filenames can be encoded as UTF-8. Skip those for now.
+      let path = into_string(entry.into_path().into_os_string());
+      let (path, name) = match (path, into_string(name)) {
+        (Ok(path), Ok(name)) => (path, name),
+        _ => continue,
+      };
+      batch.push(WalkEntry {
+        path,
+        name,
+        is_file,
+        is_directory,
+        is_symlink,
+        depth,
+        info,
+      });
+      if batch.len() == WALK_BATCH_SIZE {
+        let full =
+          std::mem::replace(&mut batch, Vec::with_capacity(WALK_BATCH_SIZE));
+        if sender.blocking_send(Ok(full)).is_err() {
+          return;
+        }
+      }
+    }
+  }
+  if !batch.is_empty() {
+    let _ = sender.blocking_send(Ok(batch));
+  }
+}
+
+#[op]
+fn op_fs_walk_open(
+  state: &mut OpState,
+  args: WalkArgs,
+  api_name: String,
+) -> Result<ResourceId, AnyError> {
+  super::check_unstable(state, &api_name);
+
+  let base = match &args.root {
+    Some(root) => PathBuf::from(root),
+    None => {
+      let cwd = current_dir()?;
+      state
+        .borrow_mut::<PermissionsContainer>()
+        .check_read_blind(&cwd, "CWD", &api_name)?;
+      cwd
+    }
+  };
+  let (roots, glob_set) = if args.patterns.is_empty() {
+    let root = WalkRoot {
+      dir: base,
+      max_depth: None,
+    };
+    (vec![root], None)
+  } else {
+    let (roots, glob_set) = glob_roots(&base, &args.patterns)?;
+    (roots, Some(glob_set))
+  };
+  let permissions = state.borrow_mut::<PermissionsContainer>();
+  for root in &roots {
+    permissions.check_read(&root.dir, &api_name)?;
+  }
+  let permissions = permissions.clone();
+
+  let (sender, receiver) = tokio::sync::mpsc::channel(4);
+  tokio::task::spawn_blocking(move || {
+    debug!("op_fs_walk_open {} roots", roots.len());
+    walk_blocking(roots, glob_set, args, permissions, api_name, sender)
+  });
+  let resource = WalkResource {
+    receiver: AsyncRefCell::new(receiver),
+    cancel: Default::default(),
+  };
+  Ok(state.resource_table.add(resource))
+}
+
+#[op]
+async fn op_fs_walk_next(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<Option<Vec<WalkEntry>>, AnyError> {
+  let resource = state.borrow().resource_table.get::<WalkResource>(rid)?;
+  let mut receiver = RcRef::map(&resource, |r| &r.receiver).borrow_mut().await;
+  let cancel = RcRef::map(&resource, |r| &r.cancel);
+  match receiver.recv().or_cancel(cancel).await? {
+    Some(batch) => batch.map(Some),
+    None => Ok(None),
+  }
+}
+
 #[op]
 fn op_rename_sync(
   state: &mut OpState,
@@ -2074,6 +2938,199 @@ async fn op_readfile_text_async(
   read_future.await?
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MmapArgs {
+  rid: Option<ResourceId>,
+  path: Option<String>,
+  offset: u64,
+  length: Option<usize>,
+  writable: bool,
+}
+
+/// A memory mapping of a file, shared between the `ArrayBuffer` exposing it
+/// and the resource used to flush it. The file is unmapped once the
+/// `ArrayBuffer` is detached or garbage collected.
+struct Mapping {
+  /// `None` for an empty mapping, which the OS doesn't support.
+  mmap: Option<memmap2::MmapMut>,
+  writable: bool,
+}
+
+struct MmapResource(std::sync::Weak<Mapping>);
+
+impl Resource for MmapResource {
+  fn name(&self) -> Cow<str> {
+    "mmap".into()
+  }
+}
+
+impl MmapResource {
+  fn flush(&self) -> Result<(), AnyError> {
+    let mapping = self
+      .0
+      .upgrade()
+      .ok_or_else(|| type_error("The mapping has been unmapped"))?;
+    // Writes to a copy-on-write mapping never reach the file.
+    match &mapping.mmap {
+      Some(mmap) if mapping.writable => mmap.flush()?,
+      _ => {}
+    }
+    Ok(())
+  }
+}
+
+unsafe extern "C" fn mapping_deleter(
+  _data: *mut std::ffi::c_void,
+  _byte_length: usize,
+  deleter_data: *mut std::ffi::c_void,
+) {
+  // `deleter_data` was created by `Arc::into_raw` in `op_fs_mmap` and V8
+  // calls the deleter exactly once.
+  drop(Arc::from_raw(deleter_data as *const Mapping));
+}
+
+/// Maps a file into memory, returning the rid used to flush the mapping and
+/// an `ArrayBuffer` backed by it.
+#[op(v8)]
+fn op_fs_mmap<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  state: &mut OpState,
+  args: MmapArgs,
+) -> Result<serde_v8::Value<'a>, AnyError> {
+  super::check_unstable(state, "Deno.mmap");
+
+  let file = match (args.rid, args.path) {
+    (Some(rid), _) => StdFileResource::clone_file(state, rid)?,
+    (None, Some(path)) => {
+      let path = PathBuf::from(path);
+      let permissions = state.borrow_mut::<PermissionsContainer>();
+      permissions.check_read(&path, "Deno.mmap()")?;
+      if args.writable {
+        permissions.check_write(&path, "Deno.mmap()")?;
+      }
+      std::fs::OpenOptions::new()
+        .read(true)
+        .write(args.writable)
+        .open(&path)
+        .map_err(|err| {
+          default_err_mapper(err, format!("mmap '{}'", path.display()))
+        })?
+    }
+    (None, None) => return Err(type_error("Either rid or path is required")),
+  };
+
+  // Touching pages past the end of the file raises SIGBUS, so the mapping has
+  // to stay within it.
+  let file_len = file.metadata()?.len();
+  let available = file_len.checked_sub(args.offset).ok_or_else(|| {
+    range_error(format!(
+      "Offset {} is past the end of the file ({file_len} bytes)",
+      args.offset
+    ))
+  })?;
+  let length = match args.length {
+    Some(length) if length as u64 > available => {
+      return Err(range_error(format!(
+        "Cannot map {length} bytes at offset {}, the file is {file_len} bytes",
+        args.offset
+      )));
+    }
+    Some(length) => length,
+    None => usize::try_from(available)
+      .map_err(|_| range_error("The file is too large to be mapped"))?,
+  };
+
+  let mmap = if length == 0 {
+    None
+  } else {
+    let mut options = memmap2::MmapOptions::new();
+    options.offset(args.offset).len(length);
+    // SAFETY: The mapping is only exposed through an `ArrayBuffer`, which V8
+    // treats as shared mutable memory anyway. A read only mapping is made
+    // copy on write, so writing to the buffer cannot fault. If the file is
+    // truncated by another process while it is mapped, touching the pages
+    // past its new end still raises SIGBUS, which can't be prevented.
+    Some(unsafe {
+      if args.writable {
+        options.map_mut(&file)?
+      } else {
+        options.map_copy(&file)?
+      }
+    })
+  };
+  let mapping = Arc::new(Mapping {
+    mmap,
+    writable: args.writable,
+  });
+  let rid = state
+    .resource_table
+    .add(MmapResource(Arc::downgrade(&mapping)));
+
+  let (ptr, len) = match &mapping.mmap {
+    Some(mmap) => (mmap.as_ptr(), mmap.len()),
+    None => (std::ptr::NonNull::<u8>::dangling().as_ptr() as *const u8, 0),
+  };
+  let ptr = ptr as *mut std::ffi::c_void;
+  // SAFETY: `ptr` and `len` describe the mapping, which stays alive until V8
+  // calls `mapping_deleter` with the leaked `Arc`.
+  let backing_store = unsafe {
+    v8::ArrayBuffer::new_backing_store_from_ptr(
+      ptr,
+      len,
+      mapping_deleter,
+      Arc::into_raw(mapping) as *mut std::ffi::c_void,
+    )
+  }
+  .make_shared();
+  let buffer = v8::ArrayBuffer::with_backing_store(scope, &backing_store);
+  let rid = v8::Integer::new_from_unsigned(scope, rid);
+  let result =
+    v8::Array::new_with_elements(scope, &[rid.into(), buffer.into()]);
+  Ok(serde_v8::Value {
+    v8_value: result.into(),
+  })
+}
+
+#[op]
+fn op_fs_mmap_flush_sync(
+  state: &mut OpState,
+  rid: ResourceId,
+) -> Result<(), AnyError> {
+  state.resource_table.get::<MmapResource>(rid)?.flush()
+}
+
+#[op]
+async fn op_fs_mmap_flush_async(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<(), AnyError> {
+  let resource = state.borrow().resource_table.get::<MmapResource>(rid)?;
+  let mapping = MmapResource(resource.0.clone());
+  tokio::task::spawn_blocking(move || mapping.flush())
+    .await
+    .unwrap()
+}
+
+/// Detaches the `ArrayBuffer` backed by the mapping, which unmaps the file as
+/// soon as V8 releases it.
+#[op(v8)]
+fn op_fs_munmap<'a>(
+  _scope: &mut v8::HandleScope<'a>,
+  state: &mut OpState,
+  rid: ResourceId,
+  buffer: serde_v8::Value<'a>,
+) -> Result<(), AnyError> {
+  let buffer = v8::Local::<v8::ArrayBuffer>::try_from(buffer.v8_value)?;
+  // The buffer may have been transferred, in which case the mapping lives on
+  // in the receiving realm.
+  if !buffer.was_detached() {
+    buffer.detach(None);
+  }
+  state.resource_table.close(rid)?;
+  Ok(())
+}
+
 // Like String::from_utf8_lossy but operates on owned values
 fn string_from_utf8_lossy(buf: Vec<u8>) -> String {
   match String::from_utf8_lossy(&buf) {
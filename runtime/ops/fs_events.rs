@@ -1,6 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use crate::permissions::PermissionsContainer;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
 use deno_core::AsyncRefCell;
@@ -14,7 +15,12 @@ use deno_core::ResourceId;
 use deno_core::op;
 
 use deno_core::Extension;
+use globset::GlobBuilder;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
 use notify::event::Event as NotifyEvent;
+use notify::event::ModifyKind;
+use notify::event::RenameMode;
 use notify::Error as NotifyError;
 use notify::EventKind;
 use notify::RecommendedWatcher;
@@ -24,10 +30,18 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::convert::From;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
 
 pub fn init() -> Extension {
   Extension::builder("deno_fs_events")
@@ -38,7 +52,15 @@ pub fn init() -> Extension {
 struct FsEventsResource {
   #[allow(unused)]
   watcher: RecommendedWatcher,
-  receiver: AsyncRefCell<mpsc::Receiver<Result<FsEvent, AnyError>>>,
+  receiver: AsyncRefCell<mpsc::Receiver<Result<NotifyEvent, AnyError>>>,
+  /// Set when events were dropped because the receiver fell behind.
+  overflowed: Arc<AtomicBool>,
+  debounce: Option<Duration>,
+  /// Debounced events that have not been polled yet.
+  pending: RefCell<VecDeque<FsEvent>>,
+  /// An error received in the middle of a debounce window, reported once the
+  /// events collected before it have been polled.
+  pending_error: RefCell<Option<AnyError>>,
   cancel: CancelHandle,
 }
 
@@ -60,13 +82,25 @@ impl Resource for FsEventsResource {
 ///
 /// Feel free to expand this struct as long as you can add tests to demonstrate
 /// the complexity.
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, PartialEq)]
 struct FsEvent {
   kind: &'static str,
   paths: Vec<PathBuf>,
   flag: Option<&'static str>,
 }
 
+impl FsEvent {
+  /// Tells the caller that events were lost, and that it needs to rescan the
+  /// watched paths to learn their current state.
+  fn rescan() -> Self {
+    FsEvent {
+      kind: "other",
+      paths: vec![],
+      flag: Some("rescan"),
+    }
+  }
+}
+
 impl From<NotifyEvent> for FsEvent {
   fn from(e: NotifyEvent) -> Self {
     let kind = match e.kind {
@@ -88,10 +122,121 @@ impl From<NotifyEvent> for FsEvent {
   }
 }
 
+fn is_paired_rename(event: &NotifyEvent) -> bool {
+  matches!(
+    event.kind,
+    EventKind::Modify(ModifyKind::Name(RenameMode::Both))
+  )
+}
+
+/// Coalesces the events received within one debounce window. Duplicates are
+/// reported once, paired renames are reported as "rename" events whose paths
+/// hold the old and the new path, and the separate "from" and "to" halves of
+/// a rename are dropped when the paired event is part of the same window.
+fn debounce_events(events: Vec<NotifyEvent>) -> Vec<FsEvent> {
+  let paired_renames = events
+    .iter()
+    .filter(|e| is_paired_rename(e))
+    .filter_map(|e| e.tracker())
+    .collect::<HashSet<_>>();
+  let mut debounced: Vec<FsEvent> = Vec::with_capacity(events.len());
+  for event in events {
+    let is_paired_half = matches!(
+      event.kind,
+      EventKind::Modify(ModifyKind::Name(RenameMode::From | RenameMode::To))
+    ) && event
+      .tracker()
+      .map_or(false, |tracker| paired_renames.contains(&tracker));
+    if is_paired_half {
+      continue;
+    }
+    let is_rename = is_paired_rename(&event);
+    let mut event = FsEvent::from(event);
+    if is_rename {
+      event.kind = "rename";
+    }
+    if !debounced.contains(&event) {
+      debounced.push(event);
+    }
+  }
+  debounced
+}
+
+/// Decides which paths are reported, based on the include and exclude globs
+/// passed to `Deno.watchFs()`.
+struct PathFilter {
+  include: Option<GlobSet>,
+  exclude: Option<GlobSet>,
+}
+
+impl PathFilter {
+  fn new(include: &[String], exclude: &[String]) -> Result<Self, AnyError> {
+    Ok(Self {
+      include: build_glob_set(include)?,
+      exclude: build_glob_set(exclude)?,
+    })
+  }
+
+  fn is_match(&self, path: &Path) -> bool {
+    self.include.as_ref().map_or(true, |set| set.is_match(path))
+      && !self
+        .exclude
+        .as_ref()
+        .map_or(false, |set| set.is_match(path))
+  }
+
+  /// Events without paths, like rescan notices, are always reported.
+  fn matches_event(&self, event: &NotifyEvent) -> bool {
+    event.paths.is_empty() || event.paths.iter().any(|p| self.is_match(p))
+  }
+}
+
+/// Event paths are absolute, so relative patterns are resolved against the
+/// current directory. A pattern without a path separator matches file names
+/// in any directory.
+fn build_glob_set(patterns: &[String]) -> Result<Option<GlobSet>, AnyError> {
+  if patterns.is_empty() {
+    return Ok(None);
+  }
+  let cwd = std::env::current_dir()?;
+  let mut builder = GlobSetBuilder::new();
+  for pattern in patterns {
+    let has_separator =
+      pattern.contains('/') || (cfg!(windows) && pattern.contains('\\'));
+    let resolved = if !has_separator {
+      format!("**/{pattern}")
+    } else if Path::new(pattern).is_absolute() {
+      pattern.clone()
+    } else {
+      cwd.join(pattern).to_string_lossy().into_owned()
+    };
+    // globset matches paths with `/` separators on every platform, and
+    // doesn't treat `\` as a separator in patterns.
+    let resolved = if cfg!(windows) {
+      resolved.replace('\\', "/")
+    } else {
+      resolved
+    };
+    let glob = GlobBuilder::new(&resolved)
+      .literal_separator(true)
+      .build()
+      .map_err(|err| {
+        type_error(format!("Invalid glob \"{pattern}\": {err}"))
+      })?;
+    builder.add(glob);
+  }
+  Ok(Some(builder.build()?))
+}
+
 #[derive(Deserialize)]
 pub struct OpenArgs {
   recursive: bool,
   paths: Vec<String>,
+  debounce: Option<u64>,
+  #[serde(default)]
+  include: Vec<String>,
+  #[serde(default)]
+  exclude: Vec<String>,
 }
 
 #[op]
@@ -99,15 +244,36 @@ fn op_fs_events_open(
   state: &mut OpState,
   args: OpenArgs,
 ) -> Result<ResourceId, AnyError> {
-  let (sender, receiver) = mpsc::channel::<Result<FsEvent, AnyError>>(16);
+  if args.debounce.is_some() {
+    super::check_unstable(state, "Deno.watchFs.debounce");
+  }
+  if !args.include.is_empty() {
+    super::check_unstable(state, "Deno.watchFs.include");
+  }
+  if !args.exclude.is_empty() {
+    super::check_unstable(state, "Deno.watchFs.exclude");
+  }
+  let filter = PathFilter::new(&args.include, &args.exclude)?;
+  let overflowed = Arc::new(AtomicBool::new(false));
+  let (sender, receiver) = mpsc::channel::<Result<NotifyEvent, AnyError>>(16);
   let sender = Mutex::new(sender);
   let mut watcher: RecommendedWatcher = Watcher::new(
-    move |res: Result<NotifyEvent, NotifyError>| {
-      let res2 = res.map(FsEvent::from).map_err(AnyError::from);
-      let sender = sender.lock();
-      // Ignore result, if send failed it means that watcher was already closed,
-      // but not all messages have been flushed.
-      let _ = sender.try_send(res2);
+    {
+      let overflowed = overflowed.clone();
+      move |res: Result<NotifyEvent, NotifyError>| {
+        if matches!(&res, Ok(event) if !filter.matches_event(event)) {
+          return;
+        }
+        let sender = sender.lock();
+        match sender.try_send(res.map_err(AnyError::from)) {
+          Err(TrySendError::Full(_)) => {
+            overflowed.store(true, Ordering::Relaxed);
+          }
+          // Ignore a closed channel, it means that watcher was already
+          // closed, but not all messages have been flushed.
+          Err(TrySendError::Closed(_)) | Ok(()) => {}
+        }
+      }
     },
     Default::default(),
   )?;
@@ -126,6 +292,13 @@ fn op_fs_events_open(
   let resource = FsEventsResource {
     watcher,
     receiver: AsyncRefCell::new(receiver),
+    overflowed,
+    debounce: args
+      .debounce
+      .filter(|ms| *ms > 0)
+      .map(Duration::from_millis),
+    pending: Default::default(),
+    pending_error: Default::default(),
     cancel: Default::default(),
   };
   let rid = state.resource_table.add(resource);
@@ -138,12 +311,47 @@ async fn op_fs_events_poll(
   rid: ResourceId,
 ) -> Result<Option<FsEvent>, AnyError> {
   let resource = state.borrow().resource_table.get::<FsEventsResource>(rid)?;
+  if resource.overflowed.swap(false, Ordering::Relaxed) {
+    return Ok(Some(FsEvent::rescan()));
+  }
+  if let Some(event) = resource.pending.borrow_mut().pop_front() {
+    return Ok(Some(event));
+  }
+  if let Some(err) = resource.pending_error.borrow_mut().take() {
+    return Err(err);
+  }
   let mut receiver = RcRef::map(&resource, |r| &r.receiver).borrow_mut().await;
-  let cancel = RcRef::map(resource, |r| &r.cancel);
-  let maybe_result = receiver.recv().or_cancel(cancel).await?;
-  match maybe_result {
-    Some(Ok(value)) => Ok(Some(value)),
-    Some(Err(err)) => Err(err),
-    None => Ok(None),
+  let cancel = RcRef::map(&resource, |r| &r.cancel);
+  let maybe_result = receiver.recv().or_cancel(cancel.clone()).await?;
+  let first = match maybe_result {
+    Some(Ok(value)) => value,
+    Some(Err(err)) => return Err(err),
+    None => return Ok(None),
+  };
+  let debounce = match resource.debounce {
+    Some(debounce) => debounce,
+    None => return Ok(Some(first.into())),
+  };
+
+  let deadline = tokio::time::Instant::now() + debounce;
+  let mut events = vec![first];
+  loop {
+    let next = tokio::time::timeout_at(deadline, receiver.recv())
+      .or_cancel(cancel.clone())
+      .await?;
+    match next {
+      Ok(Some(Ok(event))) => events.push(event),
+      // Report the events collected so far before the error.
+      Ok(Some(Err(err))) => {
+        *resource.pending_error.borrow_mut() = Some(err);
+        break;
+      }
+      // The window has elapsed or the watcher was closed.
+      Ok(None) | Err(_) => break,
+    }
   }
+  let mut events = debounce_events(events).into_iter();
+  let first = events.next();
+  resource.pending.borrow_mut().extend(events);
+  Ok(first)
 }
@@ -31,8 +31,20 @@ use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
 use tokio::process;
 
+#[cfg(unix)]
+use deno_core::futures::ready;
 #[cfg(unix)]
 use std::os::unix::io::FromRawFd;
+#[cfg(unix)]
+use std::pin::Pin;
+#[cfg(unix)]
+use std::task::Context;
+#[cfg(unix)]
+use std::task::Poll;
+#[cfg(unix)]
+use tokio::io::unix::AsyncFd;
+#[cfg(unix)]
+use tokio::io::ReadBuf;
 
 #[cfg(windows)]
 use std::os::windows::io::FromRawHandle;
@@ -79,6 +91,16 @@ pub static STDERR_HANDLE: Lazy<StdFile> = Lazy::new(|| {
 pub fn init() -> Extension {
   Extension::builder("deno_io")
     .ops(vec![op_read_sync::decl(), op_write_sync::decl()])
+    .state(|state| {
+      // Files received over unix sockets become regular file resources.
+      #[cfg(unix)]
+      state.put(deno_net::ops_unix::FileResourceFactory(|file| {
+        Rc::new(StdFileResource::fs_file(file))
+      }));
+      #[cfg(not(unix))]
+      let _ = state;
+      Ok(())
+    })
     .build()
 }
 
@@ -305,6 +327,153 @@ impl Resource for ChildStderrResource {
   }
 }
 
+/// The master side of a pty. It is switched to non-blocking mode and polled
+/// through the reactor, so a pending read doesn't occupy a blocking-pool
+/// thread and can be cancelled. Clones share the same descriptor.
+#[cfg(unix)]
+#[derive(Clone)]
+pub struct PtyMaster(Arc<AsyncFd<StdFile>>);
+
+#[cfg(unix)]
+impl PtyMaster {
+  pub fn new(file: StdFile) -> Result<Self, AnyError> {
+    use nix::fcntl::fcntl;
+    use nix::fcntl::FcntlArg;
+    use nix::fcntl::OFlag;
+    use std::os::unix::io::AsRawFd;
+
+    let fd = file.as_raw_fd();
+    let flags = OFlag::from_bits_truncate(fcntl(fd, FcntlArg::F_GETFL)?);
+    fcntl(fd, FcntlArg::F_SETFL(flags | OFlag::O_NONBLOCK))?;
+    Ok(Self(Arc::new(AsyncFd::new(file)?)))
+  }
+}
+
+#[cfg(unix)]
+impl AsyncRead for PtyMaster {
+  fn poll_read(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<std::io::Result<()>> {
+    loop {
+      let mut guard = ready!(self.0.poll_read_ready(cx))?;
+      let unfilled = buf.initialize_unfilled();
+      match guard.try_io(|inner| inner.get_ref().read(unfilled)) {
+        Ok(Ok(nread)) => {
+          buf.advance(nread);
+          return Poll::Ready(Ok(()));
+        }
+        Ok(Err(err)) => return Poll::Ready(Err(err)),
+        Err(_would_block) => continue,
+      }
+    }
+  }
+}
+
+#[cfg(unix)]
+impl AsyncWrite for PtyMaster {
+  fn poll_write(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+  ) -> Poll<std::io::Result<usize>> {
+    loop {
+      let mut guard = ready!(self.0.poll_write_ready(cx))?;
+      match guard.try_io(|inner| inner.get_ref().write(buf)) {
+        Ok(result) => return Poll::Ready(result),
+        Err(_would_block) => continue,
+      }
+    }
+  }
+
+  fn poll_flush(
+    self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+  ) -> Poll<std::io::Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn poll_shutdown(
+    self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+  ) -> Poll<std::io::Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+}
+
+#[cfg(unix)]
+pub type ChildPtyInputResource = WriteOnlyResource<PtyMaster>;
+
+#[cfg(unix)]
+impl Resource for ChildPtyInputResource {
+  fn name(&self) -> Cow<str> {
+    "childPtyInput".into()
+  }
+
+  deno_core::impl_writable!();
+
+  fn shutdown(self: Rc<Self>) -> AsyncResult<()> {
+    Box::pin(self.shutdown())
+  }
+}
+
+#[cfg(unix)]
+pub type ChildPtyOutputResource = ReadOnlyResource<PtyMaster>;
+
+#[cfg(unix)]
+impl ChildPtyOutputResource {
+  async fn read_pty(
+    self: Rc<Self>,
+    data: &mut [u8],
+  ) -> Result<usize, AnyError> {
+    match self.read(data).await {
+      // Reading from the master side of a pty fails with EIO once every
+      // process holding the slave side has exited.
+      Err(err) if is_eio(&err) => Ok(0),
+      result => result,
+    }
+  }
+}
+
+#[cfg(unix)]
+fn is_eio(err: &AnyError) -> bool {
+  err
+    .downcast_ref::<std::io::Error>()
+    .and_then(|err| err.raw_os_error())
+    == Some(libc::EIO)
+}
+
+#[cfg(unix)]
+impl Resource for ChildPtyOutputResource {
+  fn name(&self) -> Cow<str> {
+    "childPtyOutput".into()
+  }
+
+  fn read(self: Rc<Self>, limit: usize) -> AsyncResult<BufView> {
+    Box::pin(async move {
+      let mut vec = vec![0; limit];
+      let nread = self.read_pty(&mut vec).await?;
+      vec.truncate(nread);
+      Ok(BufView::from(vec))
+    })
+  }
+
+  fn read_byob(
+    self: Rc<Self>,
+    mut buf: BufMutView,
+  ) -> AsyncResult<(usize, BufMutView)> {
+    Box::pin(async move {
+      let nread = self.read_pty(buf.as_mut()).await?;
+      Ok((nread, buf))
+    })
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel_read_ops();
+  }
+}
+
 #[derive(Clone, Copy)]
 enum StdFileResourceKind {
   File,
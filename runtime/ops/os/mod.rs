@@ -14,6 +14,7 @@ use deno_core::OpState;
 use deno_node::NODE_ENV_VAR_ALLOWLIST;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::env;
 
 mod sys_info;
@@ -106,8 +107,14 @@ fn op_set_env(
 
 #[op]
 fn op_env(state: &mut OpState) -> Result<HashMap<String, String>, AnyError> {
-  state.borrow_mut::<PermissionsContainer>().check_env_all()?;
-  Ok(env::vars().collect())
+  let mut vars = env::vars().collect::<HashMap<_, _>>();
+  let permitted = state
+    .borrow_mut::<PermissionsContainer>()
+    .check_env_partial(vars.keys().cloned().collect())?
+    .into_iter()
+    .collect::<HashSet<_>>();
+  vars.retain(|key, _| permitted.contains(key));
+  Ok(vars)
 }
 
 #[op]
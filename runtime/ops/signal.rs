@@ -10,17 +10,24 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use serde::Serialize;
 
 use std::borrow::Cow;
+#[cfg(unix)]
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 #[cfg(unix)]
-use tokio::signal::unix::signal;
+use signal_hook_registry::SigId;
+#[cfg(unix)]
+use std::sync::atomic::AtomicBool;
 #[cfg(unix)]
-use tokio::signal::unix::Signal;
+use std::sync::atomic::AtomicUsize;
 #[cfg(unix)]
-use tokio::signal::unix::SignalKind;
+use std::sync::atomic::Ordering;
+#[cfg(unix)]
+use tokio::net::UnixDatagram;
 #[cfg(windows)]
 use tokio::signal::windows::ctrl_break;
 #[cfg(windows)]
@@ -40,11 +47,25 @@ pub fn init() -> Extension {
     .build()
 }
 
+/// Details about a single signal delivery, as reported to JS listeners.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalInfo {
+  signal: &'static str,
+  signo: i32,
+  pid: Option<u32>,
+  uid: Option<u32>,
+  code: Option<i32>,
+}
+
 #[cfg(unix)]
-/// The resource for signal stream.
-/// The second element is the waker of polling future.
+/// The resource for signal stream. Deliveries are written by the signal
+/// handler into one end of a datagram socket pair and read from the other.
 struct SignalStreamResource {
-  signal: AsyncRefCell<Signal>,
+  signal: AsyncRefCell<UnixDatagram>,
+  signo: libc::c_int,
+  id: SigId,
+  released: Cell<bool>,
   cancel: CancelHandle,
 }
 
@@ -55,10 +76,168 @@ impl Resource for SignalStreamResource {
   }
 
   fn close(self: Rc<Self>) {
+    self.release();
     self.cancel.cancel();
   }
 }
 
+#[cfg(unix)]
+impl Drop for SignalStreamResource {
+  fn drop(&mut self) {
+    self.release();
+  }
+}
+
+#[cfg(unix)]
+impl SignalStreamResource {
+  /// Size of a single record: signo, pid, uid and code as native i32s.
+  const RECORD_LEN: usize = 16;
+
+  fn new(signo: libc::c_int) -> Result<Self, AnyError> {
+    let (reader, writer) = std::os::unix::net::UnixDatagram::pair()?;
+    reader.set_nonblocking(true)?;
+    writer.set_nonblocking(true)?;
+
+    // SAFETY: the action only builds a record on the stack and calls
+    // `send`, which is async-signal-safe. The writer socket is owned by the
+    // closure, which the registry drops only after the action was
+    // unregistered and no handler is running it any longer.
+    let id = unsafe {
+      signal_hook_registry::register_sigaction(signo, move |info| {
+        let (pid, uid) = (info.si_pid(), info.si_uid());
+        let mut record = [0u8; Self::RECORD_LEN];
+        record[0..4].copy_from_slice(&info.si_signo.to_ne_bytes());
+        record[4..8].copy_from_slice(&(pid as i32).to_ne_bytes());
+        record[8..12].copy_from_slice(&(uid as i32).to_ne_bytes());
+        record[12..16].copy_from_slice(&info.si_code.to_ne_bytes());
+        // A full socket means the listener is behind; like the kernel, we
+        // coalesce pending deliveries of the same signal.
+        let _ = writer.send(&record);
+      })?
+    };
+    dispositions::bind(signo);
+
+    Ok(Self {
+      signal: AsyncRefCell::new(UnixDatagram::from_std(reader)?),
+      signo,
+      id,
+      released: Cell::new(false),
+      cancel: Default::default(),
+    })
+  }
+
+  /// Stops forwarding deliveries. This must happen as soon as the resource
+  /// is closed rather than once a pending poll lets go of it, so that a
+  /// restored default disposition applies immediately.
+  fn release(&self) {
+    if self.released.replace(true) {
+      return;
+    }
+    signal_hook_registry::unregister(self.id);
+    if let Some(active) = dispositions::active(self.signo) {
+      active.fetch_sub(1, Ordering::SeqCst);
+    }
+  }
+}
+
+#[cfg(unix)]
+async fn recv_signal_info(
+  socket: &UnixDatagram,
+) -> Result<SignalInfo, AnyError> {
+  let mut record = [0u8; SignalStreamResource::RECORD_LEN];
+  let n = socket.recv(&mut record).await?;
+  debug_assert_eq!(n, record.len());
+  let field =
+    |i: usize| i32::from_ne_bytes(record[i * 4..i * 4 + 4].try_into().unwrap());
+  let signo = field(0);
+  // The kernel leaves the sender fields zeroed for signals that don't
+  // originate from a process, e.g. SIGWINCH raised by the terminal.
+  let pid = match field(1) {
+    0 => None,
+    pid => Some(pid as u32),
+  };
+  Ok(SignalInfo {
+    signal: signal_int_to_str(signo)?,
+    signo,
+    pid,
+    uid: pid.map(|_| field(2) as u32),
+    code: Some(field(3)),
+  })
+}
+
+/// Bookkeeping that lets a process fall back to the default disposition of a
+/// signal once nobody listens to it anymore.
+///
+/// The signal handler installed by `signal_hook_registry` stays in place for
+/// the lifetime of the process, so without this a signal such as SIGTERM is
+/// silently swallowed after its last listener was removed. Instead a guard
+/// action is registered alongside the listeners which, when asked to, carries
+/// out the default action itself.
+///
+/// Only listeners bound through `op_signal_bind` are counted. Actions the
+/// runtime registers on its own, such as tokio's SIGCHLD handler that reaps
+/// spawned children, keep running, which is harmless since the default
+/// action of the signals they use is to ignore them.
+#[cfg(unix)]
+mod dispositions {
+  use super::*;
+  use std::collections::HashSet;
+  use std::sync::Mutex;
+
+  const MAX_SIGNAL: usize = 128;
+
+  #[allow(clippy::declare_interior_mutable_const)]
+  const ZERO: AtomicUsize = AtomicUsize::new(0);
+  #[allow(clippy::declare_interior_mutable_const)]
+  const FALSE: AtomicBool = AtomicBool::new(false);
+
+  static ACTIVE: [AtomicUsize; MAX_SIGNAL] = [ZERO; MAX_SIGNAL];
+  static RESTORE: [AtomicBool; MAX_SIGNAL] = [FALSE; MAX_SIGNAL];
+  static GUARDED: Mutex<Option<HashSet<libc::c_int>>> = Mutex::new(None);
+
+  pub fn active(signo: libc::c_int) -> Option<&'static AtomicUsize> {
+    ACTIVE.get(usize::try_from(signo).ok()?)
+  }
+
+  fn restore(signo: libc::c_int) -> Option<&'static AtomicBool> {
+    RESTORE.get(usize::try_from(signo).ok()?)
+  }
+
+  pub fn bind(signo: libc::c_int) {
+    if let Some(active) = active(signo) {
+      active.fetch_add(1, Ordering::SeqCst);
+    }
+    if let Some(restore) = restore(signo) {
+      restore.store(false, Ordering::SeqCst);
+    }
+  }
+
+  /// Request that the default action be taken for `signo` while no
+  /// listeners are bound to it.
+  pub fn restore_default(signo: libc::c_int) -> Result<(), AnyError> {
+    let flag = match restore(signo) {
+      Some(flag) => flag,
+      None => return Ok(()),
+    };
+    let mut guarded = GUARDED.lock().unwrap();
+    let guarded = guarded.get_or_insert_with(HashSet::new);
+    if guarded.insert(signo) {
+      // SAFETY: the guard only reads atomics and calls
+      // `emulate_default_handler`, which is async-signal-safe.
+      unsafe {
+        signal_hook_registry::register(signo, move || {
+          let idle = ACTIVE[signo as usize].load(Ordering::SeqCst) == 0;
+          if idle && RESTORE[signo as usize].load(Ordering::SeqCst) {
+            let _ = signal_hook::low_level::emulate_default_handler(signo);
+          }
+        })?;
+      }
+    }
+    flag.store(true, Ordering::SeqCst);
+    Ok(())
+  }
+}
+
 // TODO: CtrlClose could be mapped to SIGHUP but that needs a
 // tokio::windows::signal::CtrlClose type, or something from a different crate
 #[cfg(windows)]
@@ -471,10 +650,7 @@ fn op_signal_bind(
       "Binding to signal '{sig}' is not allowed",
     )));
   }
-  let resource = SignalStreamResource {
-    signal: AsyncRefCell::new(signal(SignalKind::from_raw(signo))?),
-    cancel: Default::default(),
-  };
+  let resource = SignalStreamResource::new(signo)?;
   let rid = state.resource_table.add(resource);
   Ok(rid)
 }
@@ -504,11 +680,35 @@ fn op_signal_bind(
   Ok(rid)
 }
 
+/// Resolves with the next delivery, or `None` once the stream is closed.
+#[cfg(unix)]
 #[op]
 async fn op_signal_poll(
   state: Rc<RefCell<OpState>>,
   rid: ResourceId,
-) -> Result<bool, AnyError> {
+) -> Result<Option<SignalInfo>, AnyError> {
+  let resource = state
+    .borrow_mut()
+    .resource_table
+    .get::<SignalStreamResource>(rid)?;
+
+  let cancel = RcRef::map(&resource, |r| &r.cancel);
+  let signal = RcRef::map(&resource, |r| &r.signal).borrow_mut().await;
+
+  match recv_signal_info(&signal).or_cancel(cancel).await {
+    Ok(info) => Ok(Some(info?)),
+    Err(_) => Ok(None),
+  }
+}
+
+/// Resolves with the next delivery, or `None` once the stream is closed.
+/// Console control events carry no sender details on Windows.
+#[cfg(windows)]
+#[op]
+async fn op_signal_poll(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<Option<SignalInfo>, AnyError> {
   let resource = state
     .borrow_mut()
     .resource_table
@@ -517,9 +717,19 @@ async fn op_signal_poll(
   let cancel = RcRef::map(&resource, |r| &r.cancel);
   let mut signal = RcRef::map(&resource, |r| &r.signal).borrow_mut().await;
 
+  let signo = match &*signal {
+    WindowsSignal::Sigint(_) => 2,
+    WindowsSignal::Sigbreak(_) => 21,
+  };
   match signal.recv().or_cancel(cancel).await {
-    Ok(result) => Ok(result.is_none()),
-    Err(_) => Ok(true),
+    Ok(Some(())) => Ok(Some(SignalInfo {
+      signal: signal_int_to_str(signo)?,
+      signo,
+      pid: None,
+      uid: None,
+      code: None,
+    })),
+    Ok(None) | Err(_) => Ok(None),
   }
 }
 
@@ -527,7 +737,16 @@ async fn op_signal_poll(
 pub fn op_signal_unbind(
   state: &mut OpState,
   rid: ResourceId,
+  restore_default: bool,
 ) -> Result<(), AnyError> {
+  if restore_default {
+    super::check_unstable(state, "Deno.removeSignalListener#restoreDefault");
+  }
+  #[cfg(unix)]
+  if restore_default {
+    let resource = state.resource_table.get::<SignalStreamResource>(rid)?;
+    dispositions::restore_default(resource.signo)?;
+  }
   state.resource_table.close(rid)?;
   Ok(())
 }
@@ -6,6 +6,7 @@ use super::io::ChildStdoutResource;
 use super::process::Stdio;
 use super::process::StdioOrRid;
 use crate::permissions::PermissionsContainer;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op;
 use deno_core::Extension;
@@ -33,6 +34,10 @@ pub fn init() -> Extension {
       op_spawn_child::decl(),
       op_spawn_wait::decl(),
       op_spawn_sync::decl(),
+      op_spawn_pty_resize::decl(),
+      op_spawn_ipc_parent_open::decl(),
+      op_spawn_ipc_read::decl(),
+      op_spawn_ipc_write::decl(),
     ])
     .build()
 }
@@ -59,11 +64,38 @@ pub struct SpawnArgs {
   uid: Option<u32>,
   #[cfg(windows)]
   windows_raw_arguments: bool,
+  pty: Option<PtySize>,
+  #[serde(default)]
+  ipc: bool,
+  #[serde(default)]
+  detached: bool,
+  #[serde(default)]
+  process_group: bool,
+  limits: Option<ResourceLimits>,
 
   #[serde(flatten)]
   stdio: ChildStdio,
 }
 
+/// Resource limits applied to the child before it executes the command.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLimits {
+  /// CPU time, in seconds.
+  cpu: Option<u64>,
+  /// Size of the virtual address space, in bytes.
+  address_space: Option<u64>,
+  /// Number of open file descriptors.
+  open_files: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtySize {
+  rows: u16,
+  columns: u16,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ChildStdio {
@@ -128,6 +160,15 @@ fn create_command(
   args: SpawnArgs,
   api_name: &str,
 ) -> Result<std::process::Command, AnyError> {
+  if args.detached {
+    super::check_unstable(state, "Deno.Command.detached");
+  }
+  if args.process_group {
+    super::check_unstable(state, "Deno.Command.processGroup");
+  }
+  if args.limits.is_some() {
+    super::check_unstable(state, "Deno.Command.limits");
+  }
   state
     .borrow_mut::<PermissionsContainer>()
     .check_run(&args.cmd, api_name)?;
@@ -154,6 +195,9 @@ fn create_command(
     command.env_clear();
   }
   command.envs(args.env);
+  // Only a child spawned with `ipc: true` gets a channel, the one this
+  // process may have been spawned with is not passed on.
+  ipc::clear_env(&mut command);
 
   #[cfg(unix)]
   if let Some(gid) = args.gid {
@@ -173,6 +217,26 @@ fn create_command(
     });
   }
 
+  #[cfg(unix)]
+  if args.detached {
+    process_group::detach(&mut command);
+  } else if args.process_group {
+    process_group::isolate(&mut command);
+  }
+  #[cfg(windows)]
+  {
+    let mut creation_flags = 0;
+    if args.detached {
+      process_group::detach(&mut creation_flags);
+    } else if args.process_group {
+      process_group::isolate(&mut creation_flags);
+    }
+    process_group::apply_creation_flags(&mut command, creation_flags);
+  }
+  if let Some(limits) = args.limits {
+    process_group::set_limits(&mut command, limits)?;
+  }
+
   command.stdin(args.stdio.stdin.as_stdio());
   command.stdout(match args.stdio.stdout {
     Stdio::Inherit => StdioOrRid::Rid(1).as_stdio(state)?,
@@ -194,21 +258,48 @@ struct Child {
   stdin_rid: Option<ResourceId>,
   stdout_rid: Option<ResourceId>,
   stderr_rid: Option<ResourceId>,
+  pty_rid: Option<ResourceId>,
+  ipc_rid: Option<ResourceId>,
 }
 
 fn spawn_child(
   state: &mut OpState,
   command: std::process::Command,
+  pty_master: Option<std::fs::File>,
+  ipc_stream: Option<ipc::IpcStream>,
+  detached: bool,
 ) -> Result<Child, AnyError> {
   let mut command = tokio::process::Command::from(command);
-  // TODO(@crowlkats): allow detaching processes.
-  //  currently deno will orphan a process when exiting with an error or Deno.exit()
-  // We want to kill child when it's closed
-  command.kill_on_drop(true);
+  // TODO(@crowlkats): currently deno will orphan a process when exiting with
+  //  an error or Deno.exit()
+  // We want to kill child when it's closed, unless it was detached.
+  command.kill_on_drop(!detached);
 
   let mut child = command.spawn()?;
+  // Drop the parent's copies of the pty slave, so reading from the master
+  // reports EOF once the child and its descendants have exited.
+  drop(command);
   let pid = child.id().expect("Process ID should be set.");
 
+  let ipc_rid = match ipc_stream {
+    Some(stream) => Some(ipc::add_resource(state, stream)?),
+    None => None,
+  };
+
+  if let Some(master) = pty_master {
+    let (stdin_rid, stdout_rid, pty_rid) = pty::add_resources(state, master)?;
+    let child_rid = state.resource_table.add(ChildResource(child));
+    return Ok(Child {
+      rid: child_rid,
+      pid,
+      stdin_rid: Some(stdin_rid),
+      stdout_rid: Some(stdout_rid),
+      stderr_rid: None,
+      pty_rid: Some(pty_rid),
+      ipc_rid,
+    });
+  }
+
   let stdin_rid = child
     .stdin
     .take()
@@ -232,17 +323,42 @@ fn spawn_child(
     stdin_rid,
     stdout_rid,
     stderr_rid,
+    pty_rid: None,
+    ipc_rid,
   })
 }
 
 #[op]
 fn op_spawn_child(
   state: &mut OpState,
-  args: SpawnArgs,
+  mut args: SpawnArgs,
   api_name: String,
 ) -> Result<Child, AnyError> {
-  let command = create_command(state, args, &api_name)?;
-  spawn_child(state, command)
+  let pty_size = args.pty.take();
+  if pty_size.is_some() {
+    super::check_unstable(state, "Deno.Command.pty");
+    // The pty's session already puts the child in a process group of its own.
+    args.process_group = false;
+  }
+  let use_ipc = args.ipc;
+  if use_ipc {
+    super::check_unstable(state, "Deno.Command.ipc");
+  }
+  let detached = args.detached;
+  let mut command = create_command(state, args, &api_name)?;
+  let pty_master = match pty_size {
+    Some(size) => Some(pty::attach(&mut command, &size)?),
+    None => None,
+  };
+  // The child's end of the channel has to stay open until the child is
+  // spawned, and is closed in the parent afterwards.
+  let (ipc_stream, _ipc_child_stream) = if use_ipc {
+    let (parent, child) = ipc::attach(&mut command)?;
+    (Some(parent), Some(child))
+  } else {
+    (None, None)
+  };
+  spawn_child(state, command, pty_master, ipc_stream, detached)
 }
 
 #[op]
@@ -268,6 +384,16 @@ fn op_spawn_sync(
   state: &mut OpState,
   args: SpawnArgs,
 ) -> Result<SpawnOutput, AnyError> {
+  if args.pty.is_some() {
+    return Err(type_error(
+      "pty is not supported by Deno.Command().outputSync()",
+    ));
+  }
+  if args.ipc {
+    return Err(type_error(
+      "ipc is not supported by Deno.Command().outputSync()",
+    ));
+  }
   let stdout = matches!(args.stdio.stdout, Stdio::Piped);
   let stderr = matches!(args.stdio.stderr, Stdio::Piped);
   let output =
@@ -287,3 +413,485 @@ fn op_spawn_sync(
     },
   })
 }
+
+struct ChildPtyResource(std::fs::File);
+
+impl Resource for ChildPtyResource {
+  fn name(&self) -> Cow<str> {
+    "childPty".into()
+  }
+}
+
+#[op]
+fn op_spawn_pty_resize(
+  state: &mut OpState,
+  rid: ResourceId,
+  size: PtySize,
+) -> Result<(), AnyError> {
+  let resource = state.resource_table.get::<ChildPtyResource>(rid)?;
+  pty::resize(&resource.0, &size)
+}
+
+/// Opens the IPC channel to the parent process, if this process was spawned
+/// with `ipc: true`.
+#[op]
+fn op_spawn_ipc_parent_open(
+  state: &mut OpState,
+) -> Result<Option<ResourceId>, AnyError> {
+  ipc::open_parent(state)
+}
+
+#[op]
+async fn op_spawn_ipc_read(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<Option<ZeroCopyBuf>, AnyError> {
+  ipc::read(state, rid).await
+}
+
+#[op]
+async fn op_spawn_ipc_write(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  data: ZeroCopyBuf,
+) -> Result<(), AnyError> {
+  ipc::write(state, rid, data).await
+}
+
+#[cfg(unix)]
+mod pty {
+  use super::ChildPtyResource;
+  use super::PtySize;
+  use crate::ops::io::ChildPtyInputResource;
+  use crate::ops::io::ChildPtyOutputResource;
+  use crate::ops::io::PtyMaster;
+  use deno_core::error::AnyError;
+  use deno_core::OpState;
+  use deno_core::ResourceId;
+  use nix::fcntl::fcntl;
+  use nix::fcntl::FcntlArg;
+  use nix::fcntl::FdFlag;
+  use nix::pty::openpty;
+  use nix::pty::Winsize;
+  use std::fs::File;
+  use std::os::unix::io::AsRawFd;
+  use std::os::unix::io::FromRawFd;
+  use std::os::unix::process::CommandExt;
+
+  fn winsize(size: &PtySize) -> Winsize {
+    Winsize {
+      ws_row: size.rows,
+      ws_col: size.columns,
+      ws_xpixel: 0,
+      ws_ypixel: 0,
+    }
+  }
+
+  /// Opens a new pseudo-terminal and makes its slave side the stdio and the
+  /// controlling terminal of `command`. Returns the master side.
+  pub fn attach(
+    command: &mut std::process::Command,
+    size: &PtySize,
+  ) -> Result<File, AnyError> {
+    let pty = openpty(&winsize(size), None)?;
+    fcntl(pty.master, FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC))?;
+    fcntl(pty.slave, FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC))?;
+    // SAFETY: `openpty` returned two freshly opened descriptors that nothing
+    // else owns.
+    let (master, slave) =
+      unsafe { (File::from_raw_fd(pty.master), File::from_raw_fd(pty.slave)) };
+
+    command.stdin(slave.try_clone()?);
+    command.stdout(slave.try_clone()?);
+    command.stderr(slave);
+    // SAFETY: `setsid` and `ioctl` are async-signal-safe.
+    unsafe {
+      command.pre_exec(|| {
+        // Start a new session, so the pty can become the controlling
+        // terminal and signals like SIGINT reach the child's process group.
+        // A detached child already leads a session of its own.
+        if libc::getsid(0) != libc::getpid() && libc::setsid() == -1 {
+          return Err(std::io::Error::last_os_error());
+        }
+        if libc::ioctl(0, libc::TIOCSCTTY as _, 0) == -1 {
+          return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+      });
+    }
+
+    Ok(master)
+  }
+
+  /// Adds the resources for writing to the child's stdin, reading its
+  /// output and resizing the pty, returning their ids in that order.
+  pub fn add_resources(
+    state: &mut OpState,
+    master: File,
+  ) -> Result<(ResourceId, ResourceId, ResourceId), AnyError> {
+    let stream = PtyMaster::new(master.try_clone()?)?;
+    let stdin_rid = state
+      .resource_table
+      .add(ChildPtyInputResource::from(stream.clone()));
+    let stdout_rid = state
+      .resource_table
+      .add(ChildPtyOutputResource::from(stream));
+    let pty_rid = state.resource_table.add(ChildPtyResource(master));
+    Ok((stdin_rid, stdout_rid, pty_rid))
+  }
+
+  pub fn resize(master: &File, size: &PtySize) -> Result<(), AnyError> {
+    let winsize = winsize(size);
+    // SAFETY: `master` is an open pty master and `winsize` outlives the call.
+    let ret = unsafe {
+      libc::ioctl(master.as_raw_fd(), libc::TIOCSWINSZ as _, &winsize)
+    };
+    if ret == -1 {
+      return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+  }
+}
+
+#[cfg(unix)]
+mod process_group {
+  use super::ResourceLimits;
+  use deno_core::error::AnyError;
+  use std::os::unix::process::CommandExt;
+
+  /// Runs the child in a new session, which also makes it the leader of a
+  /// new process group.
+  pub fn detach(command: &mut std::process::Command) {
+    // SAFETY: `setsid` is async-signal-safe.
+    unsafe {
+      command.pre_exec(|| {
+        if libc::setsid() == -1 {
+          return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+      });
+    }
+  }
+
+  /// Makes the child the leader of a new process group, so it and its
+  /// descendants can be signalled together.
+  pub fn isolate(command: &mut std::process::Command) {
+    command.process_group(0);
+  }
+
+  pub fn set_limits(
+    command: &mut std::process::Command,
+    limits: ResourceLimits,
+  ) -> Result<(), AnyError> {
+    let limits = [
+      (libc::RLIMIT_CPU, limits.cpu),
+      (libc::RLIMIT_AS, limits.address_space),
+      (libc::RLIMIT_NOFILE, limits.open_files),
+    ];
+    // SAFETY: `setrlimit` is async-signal-safe and `limits` is moved into the
+    // closure, so nothing is allocated after forking.
+    unsafe {
+      command.pre_exec(move || {
+        for (resource, value) in limits {
+          if let Some(value) = value {
+            let limit = libc::rlimit {
+              rlim_cur: value as libc::rlim_t,
+              rlim_max: value as libc::rlim_t,
+            };
+            if libc::setrlimit(resource, &limit) == -1 {
+              return Err(std::io::Error::last_os_error());
+            }
+          }
+        }
+        Ok(())
+      });
+    }
+    Ok(())
+  }
+}
+
+#[cfg(windows)]
+mod process_group {
+  use super::ResourceLimits;
+  use deno_core::error::type_error;
+  use deno_core::error::AnyError;
+  use std::os::windows::process::CommandExt;
+  use winapi::um::winbase::CREATE_NEW_PROCESS_GROUP;
+  use winapi::um::winbase::DETACHED_PROCESS;
+
+  /// `Command::creation_flags()` replaces the flags set before, so they are
+  /// collected in `flags` and applied once with `apply_creation_flags()`.
+  pub fn detach(flags: &mut u32) {
+    *flags |= DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP;
+  }
+
+  pub fn isolate(flags: &mut u32) {
+    *flags |= CREATE_NEW_PROCESS_GROUP;
+  }
+
+  pub fn apply_creation_flags(command: &mut std::process::Command, flags: u32) {
+    if flags != 0 {
+      command.creation_flags(flags);
+    }
+  }
+
+  pub fn set_limits(
+    _command: &mut std::process::Command,
+    _limits: ResourceLimits,
+  ) -> Result<(), AnyError> {
+    Err(type_error("limits are not supported on this platform"))
+  }
+}
+
+#[cfg(not(unix))]
+mod pty {
+  use super::PtySize;
+  use deno_core::error::type_error;
+  use deno_core::error::AnyError;
+  use deno_core::OpState;
+  use deno_core::ResourceId;
+  use std::fs::File;
+
+  pub fn attach(
+    _command: &mut std::process::Command,
+    _size: &PtySize,
+  ) -> Result<File, AnyError> {
+    Err(type_error("pty is not supported on this platform"))
+  }
+
+  pub fn add_resources(
+    _state: &mut OpState,
+    _master: File,
+  ) -> Result<(ResourceId, ResourceId, ResourceId), AnyError> {
+    Err(type_error("pty is not supported on this platform"))
+  }
+
+  pub fn resize(_master: &File, _size: &PtySize) -> Result<(), AnyError> {
+    Err(type_error("pty is not supported on this platform"))
+  }
+}
+
+/// Messages are exchanged over a unix socket pair, framed as a little-endian
+/// `u32` length followed by the serialized message.
+#[cfg(unix)]
+mod ipc {
+  use deno_core::error::type_error;
+  use deno_core::error::AnyError;
+  use deno_core::AsyncRefCell;
+  use deno_core::CancelFuture;
+  use deno_core::CancelHandle;
+  use deno_core::OpState;
+  use deno_core::RcRef;
+  use deno_core::Resource;
+  use deno_core::ResourceId;
+  use deno_core::ZeroCopyBuf;
+  use std::borrow::Cow;
+  use std::cell::RefCell;
+  use std::io::ErrorKind;
+  use std::os::unix::io::AsRawFd;
+  use std::os::unix::io::FromRawFd;
+  use std::os::unix::io::RawFd;
+  use std::os::unix::process::CommandExt;
+  use std::rc::Rc;
+  use std::sync::atomic::AtomicBool;
+  use std::sync::atomic::Ordering;
+  use tokio::io::AsyncReadExt;
+  use tokio::io::AsyncWriteExt;
+  use tokio::net::unix::OwnedReadHalf;
+  use tokio::net::unix::OwnedWriteHalf;
+
+  /// Tells the child which of its file descriptors is the IPC channel.
+  const IPC_FD_ENV: &str = "DENO_IPC_FD";
+
+  /// Frames with a larger length prefix are rejected instead of allocated.
+  const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+  /// Set once the channel to the parent was opened, as its descriptor can
+  /// only be owned by one resource.
+  static PARENT_OPENED: AtomicBool = AtomicBool::new(false);
+
+  pub type IpcStream = std::os::unix::net::UnixStream;
+
+  struct IpcPipeResource {
+    read_half: AsyncRefCell<OwnedReadHalf>,
+    write_half: AsyncRefCell<OwnedWriteHalf>,
+    cancel: CancelHandle,
+  }
+
+  impl Resource for IpcPipeResource {
+    fn name(&self) -> Cow<str> {
+      "ipcPipe".into()
+    }
+
+    fn close(self: Rc<Self>) {
+      self.cancel.cancel();
+    }
+  }
+
+  pub fn clear_env(command: &mut std::process::Command) {
+    command.env_remove(IPC_FD_ENV);
+  }
+
+  /// Creates the channel and makes the child's end inheritable by `command`.
+  /// Returns the parent's and the child's end.
+  pub fn attach(
+    command: &mut std::process::Command,
+  ) -> Result<(IpcStream, IpcStream), AnyError> {
+    let (parent, child) = IpcStream::pair()?;
+    let fd = child.as_raw_fd();
+    command.env(IPC_FD_ENV, fd.to_string());
+    // SAFETY: `fcntl` is async-signal-safe.
+    unsafe {
+      command.pre_exec(move || {
+        if libc::fcntl(fd, libc::F_SETFD, 0) == -1 {
+          return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+      });
+    }
+    Ok((parent, child))
+  }
+
+  pub fn add_resource(
+    state: &mut OpState,
+    stream: IpcStream,
+  ) -> Result<ResourceId, AnyError> {
+    stream.set_nonblocking(true)?;
+    let (read_half, write_half) =
+      tokio::net::UnixStream::from_std(stream)?.into_split();
+    Ok(state.resource_table.add(IpcPipeResource {
+      read_half: AsyncRefCell::new(read_half),
+      write_half: AsyncRefCell::new(write_half),
+      cancel: Default::default(),
+    }))
+  }
+
+  pub fn open_parent(
+    state: &mut OpState,
+  ) -> Result<Option<ResourceId>, AnyError> {
+    // The variable is left in the environment, as changing it is unsound
+    // once other threads are running; `clear_env` keeps it from children.
+    let fd = match std::env::var(IPC_FD_ENV) {
+      Ok(fd) => fd,
+      Err(_) => return Ok(None),
+    };
+    let fd: RawFd = fd
+      .parse()
+      .map_err(|_| type_error(format!("Invalid {IPC_FD_ENV}: {fd}")))?;
+    let stat = nix::sys::stat::fstat(fd)?;
+    if stat.st_mode & libc::S_IFMT != libc::S_IFSOCK {
+      return Err(type_error(format!("{IPC_FD_ENV} is not a socket")));
+    }
+    if PARENT_OPENED.swap(true, Ordering::SeqCst) {
+      return Err(type_error("IPC channel to the parent is already open"));
+    }
+    // Don't leak the channel to processes spawned by this one.
+    // SAFETY: `fd` is an open descriptor, checked by `fstat` above.
+    if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+      return Err(std::io::Error::last_os_error().into());
+    }
+    // SAFETY: the descriptor was inherited from the parent for this channel
+    // only, and it is taken at most once.
+    let stream = unsafe { IpcStream::from_raw_fd(fd) };
+    add_resource(state, stream).map(Some)
+  }
+
+  pub async fn read(
+    state: Rc<RefCell<OpState>>,
+    rid: ResourceId,
+  ) -> Result<Option<ZeroCopyBuf>, AnyError> {
+    let resource = state.borrow().resource_table.get::<IpcPipeResource>(rid)?;
+    let mut reader = RcRef::map(&resource, |r| &r.read_half).borrow_mut().await;
+    let cancel = RcRef::map(&resource, |r| &r.cancel);
+    let mut len = [0; 4];
+    match reader
+      .read_exact(&mut len)
+      .or_cancel(cancel.clone())
+      .await?
+    {
+      Ok(_) => {}
+      // The other side closed the channel.
+      Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+      Err(err) => return Err(err.into()),
+    }
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_MESSAGE_SIZE {
+      return Err(
+        std::io::Error::new(
+          ErrorKind::InvalidData,
+          format!("IPC message of {len} bytes exceeds the 64 MiB limit"),
+        )
+        .into(),
+      );
+    }
+    let mut data = vec![0; len];
+    reader.read_exact(&mut data).or_cancel(cancel).await??;
+    Ok(Some(data.into()))
+  }
+
+  pub async fn write(
+    state: Rc<RefCell<OpState>>,
+    rid: ResourceId,
+    data: ZeroCopyBuf,
+  ) -> Result<(), AnyError> {
+    let resource = state.borrow().resource_table.get::<IpcPipeResource>(rid)?;
+    let mut writer =
+      RcRef::map(&resource, |r| &r.write_half).borrow_mut().await;
+    if data.len() > MAX_MESSAGE_SIZE {
+      return Err(type_error("Message is too large"));
+    }
+    let len = data.len() as u32;
+    writer.write_all(&len.to_le_bytes()).await?;
+    writer.write_all(&data).await?;
+    Ok(())
+  }
+}
+
+#[cfg(not(unix))]
+mod ipc {
+  use deno_core::error::type_error;
+  use deno_core::error::AnyError;
+  use deno_core::OpState;
+  use deno_core::ResourceId;
+  use deno_core::ZeroCopyBuf;
+  use std::cell::RefCell;
+  use std::rc::Rc;
+
+  pub enum IpcStream {}
+
+  pub fn clear_env(_command: &mut std::process::Command) {}
+
+  pub fn attach(
+    _command: &mut std::process::Command,
+  ) -> Result<(IpcStream, IpcStream), AnyError> {
+    Err(type_error("ipc is not supported on this platform"))
+  }
+
+  pub fn add_resource(
+    _state: &mut OpState,
+    stream: IpcStream,
+  ) -> Result<ResourceId, AnyError> {
+    match stream {}
+  }
+
+  pub fn open_parent(
+    _state: &mut OpState,
+  ) -> Result<Option<ResourceId>, AnyError> {
+    Ok(None)
+  }
+
+  pub async fn read(
+    _state: Rc<RefCell<OpState>>,
+    _rid: ResourceId,
+  ) -> Result<Option<ZeroCopyBuf>, AnyError> {
+    Err(type_error("ipc is not supported on this platform"))
+  }
+
+  pub async fn write(
+    _state: Rc<RefCell<OpState>>,
+    _rid: ResourceId,
+    _data: ZeroCopyBuf,
+  ) -> Result<(), AnyError> {
+    Err(type_error("ipc is not supported on this platform"))
+  }
+}
@@ -4,6 +4,7 @@ use crate::ops::TestingFeaturesEnabled;
 use crate::permissions::create_child_permissions;
 use crate::permissions::ChildPermissionsArg;
 use crate::permissions::PermissionsContainer;
+use crate::resource_limits::ResourceLimits;
 use crate::web_worker::run_web_worker;
 use crate::web_worker::SendableWebWorkerHandle;
 use crate::web_worker::WebWorker;
@@ -35,6 +36,7 @@ pub struct CreateWebWorkerArgs {
   pub permissions: PermissionsContainer,
   pub main_module: ModuleSpecifier,
   pub worker_type: WebWorkerType,
+  pub resource_limits: ResourceLimits,
 }
 
 pub type CreateWebWorkerCb = dyn Fn(CreateWebWorkerArgs) -> (WebWorker, SendableWebWorkerHandle)
@@ -130,6 +132,7 @@ pub struct CreateWorkerArgs {
   has_source_code: bool,
   name: Option<String>,
   permissions: Option<ChildPermissionsArg>,
+  resource_limits: Option<ResourceLimits>,
   source_code: String,
   specifier: String,
   worker_type: WebWorkerType,
@@ -163,6 +166,11 @@ fn op_create_worker(
   if args.permissions.is_some() {
     super::check_unstable(state, "Worker.deno.permissions");
   }
+  let resource_limits = args.resource_limits.unwrap_or_default();
+  if !resource_limits.is_empty() {
+    super::check_unstable(state, "Worker.deno.resourceLimits");
+    resource_limits.validate()?;
+  }
   let parent_permissions = state.borrow_mut::<PermissionsContainer>();
   let worker_permissions = if let Some(child_permissions_arg) = args.permissions
   {
@@ -210,6 +218,7 @@ fn op_create_worker(
         permissions: worker_permissions,
         main_module: module_specifier.clone(),
         worker_type,
+        resource_limits,
       });
 
     // Send thread safe handle from newly created worker to host thread
@@ -271,6 +271,24 @@ impl EnvDescriptor {
   }
 }
 
+impl EnvDescriptor {
+  /// Returns the prefix if this descriptor is a trailing wildcard pattern
+  /// such as `AWS_*`.
+  fn prefix(&self) -> Option<&str> {
+    self.0.as_ref().strip_suffix('*')
+  }
+
+  /// Whether access granted for this descriptor implies access to `other`.
+  /// An exact name only covers itself, while a pattern covers every name
+  /// and pattern that starts with its prefix.
+  fn covers(&self, other: &EnvDescriptor) -> bool {
+    match self.prefix() {
+      Some(prefix) => other.0.as_ref().starts_with(prefix),
+      None => self == other,
+    }
+  }
+}
+
 impl AsRef<str> for EnvDescriptor {
   fn as_ref(&self) -> &str {
     self.0.as_ref()
@@ -835,18 +853,21 @@ impl Default for UnaryPermission<NetDescriptor> {
 
 impl UnaryPermission<EnvDescriptor> {
   pub fn query(&self, env: Option<&str>) -> PermissionState {
-    let env = env.map(EnvVarName::new);
+    let env = env.map(EnvDescriptor::new);
     if self.global_state == PermissionState::Denied
       && match env.as_ref() {
         None => true,
-        Some(env) => self.denied_list.contains(&EnvDescriptor::new(env)),
+        Some(env) => self
+          .denied_list
+          .iter()
+          .any(|env_| env.covers(env_) || env_.covers(env)),
       }
     {
       PermissionState::Denied
     } else if self.global_state == PermissionState::Granted
       || match env.as_ref() {
         None => false,
-        Some(env) => self.granted_list.contains(&EnvDescriptor::new(env)),
+        Some(env) => self.granted_list.iter().any(|env_| env_.covers(env)),
       }
     {
       PermissionState::Granted
@@ -904,7 +925,8 @@ impl UnaryPermission<EnvDescriptor> {
 
   pub fn revoke(&mut self, env: Option<&str>) -> PermissionState {
     if let Some(env) = env {
-      self.granted_list.remove(&EnvDescriptor::new(env));
+      let env = EnvDescriptor::new(env);
+      self.granted_list.retain(|env_| !env_.covers(&env));
     } else {
       self.granted_list.clear();
     }
@@ -946,6 +968,28 @@ impl UnaryPermission<EnvDescriptor> {
     }
     result
   }
+
+  /// Filters `names` down to the variables that can be accessed without
+  /// prompting. If access was granted to individual variables or patterns
+  /// only, the permitted subset is returned; otherwise this falls back to
+  /// `check_all()` and returns every name on success.
+  pub fn check_partial(
+    &mut self,
+    names: Vec<String>,
+  ) -> Result<Vec<String>, AnyError> {
+    if self.global_state != PermissionState::Granted
+      && !self.granted_list.is_empty()
+    {
+      return Ok(
+        names
+          .into_iter()
+          .filter(|name| self.query(Some(name)) == PermissionState::Granted)
+          .collect(),
+      );
+    }
+    self.check_all()?;
+    Ok(names)
+  }
 }
 
 impl Default for UnaryPermission<EnvDescriptor> {
@@ -1487,6 +1531,10 @@ impl Permissions {
             .map(|x| {
               if x.is_empty() {
                 Err(AnyError::msg("Empty path is not allowed"))
+              } else if x.strip_suffix('*').unwrap_or(x).contains('*') {
+                Err(AnyError::msg(format!(
+                  "Wildcard is only allowed at the end of \"{x}\""
+                )))
               } else {
                 Ok(EnvDescriptor::new(x))
               }
@@ -1712,6 +1760,14 @@ impl PermissionsContainer {
   pub fn check_env_all(&mut self) -> Result<(), AnyError> {
     self.0.lock().env.check_all()
   }
+
+  #[inline(always)]
+  pub fn check_env_partial(
+    &mut self,
+    names: Vec<String>,
+  ) -> Result<Vec<String>, AnyError> {
+    self.0.lock().env.check_partial(names)
+  }
 }
 
 impl deno_flash::FlashPermissions for PermissionsContainer {
@@ -3126,6 +3182,83 @@ mod tests {
     assert_eq!(perms.env.revoke(Some("HomE")), PermissionState::Prompt);
   }
 
+  #[test]
+  fn test_env_wildcard() {
+    set_prompter(Box::new(TestPrompter));
+    let prompt_value = PERMISSION_PROMPT_STUB_VALUE_SETTER.lock();
+    let mut perms = Permissions::allow_all();
+    perms.env =
+      Permissions::new_env(&Some(svec!["AWS_*", "HOME"]), false).unwrap();
+
+    prompt_value.set(false);
+    assert_eq!(perms.env.query(None), PermissionState::Prompt);
+    assert_eq!(
+      perms.env.query(Some("AWS_REGION")),
+      PermissionState::Granted
+    );
+    assert_eq!(perms.env.query(Some("AWS_S3_*")), PermissionState::Granted);
+    assert_eq!(perms.env.query(Some("AWS_*")), PermissionState::Granted);
+    assert_eq!(perms.env.query(Some("AWS")), PermissionState::Prompt);
+    assert_eq!(perms.env.query(Some("HOME_*")), PermissionState::Prompt);
+    assert!(perms.env.check("AWS_SECRET_ACCESS_KEY").is_ok());
+    assert!(perms.env.check("OTEL_SERVICE_NAME").is_err());
+
+    assert_eq!(
+      perms
+        .env
+        .check_partial(svec!["AWS_REGION", "HOME", "PATH", "OTEL_X"])
+        .unwrap(),
+      svec!["AWS_REGION", "HOME"]
+    );
+
+    assert_eq!(
+      perms.env.revoke(Some("AWS_REGION")),
+      PermissionState::Prompt
+    );
+    assert_eq!(
+      perms.env.query(Some("AWS_S3_BUCKET")),
+      PermissionState::Prompt
+    );
+    assert_eq!(perms.env.query(Some("HOME")), PermissionState::Granted);
+
+    assert!(Permissions::new_env(&Some(svec!["*"]), false).is_ok());
+    assert!(Permissions::new_env(&Some(svec!["AWS_*_KEY"]), false).is_err());
+    assert!(Permissions::new_env(&Some(svec!["AWS_**"]), false).is_err());
+  }
+
+  #[test]
+  fn test_env_wildcard_denied() {
+    set_prompter(Box::new(TestPrompter));
+    let prompt_value = PERMISSION_PROMPT_STUB_VALUE_SETTER.lock();
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      prompt: true,
+      ..Default::default()
+    })
+    .unwrap();
+
+    prompt_value.set(false);
+    assert!(perms.env.check("AWS_SECRET_ACCESS_KEY").is_err());
+    assert_eq!(perms.env.query(Some("AWS_*")), PermissionState::Denied);
+    assert_eq!(perms.env.query(Some("OTEL_*")), PermissionState::Prompt);
+
+    // Without any partial grants, reading the whole environment still
+    // requires full access.
+    assert!(perms.env.check_partial(svec!["HOME"]).is_err());
+
+    // A denied pattern also denies the names it covers.
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      prompt: true,
+      ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(perms.env.request(Some("GCP_*")), PermissionState::Denied);
+    assert_eq!(
+      perms.env.query(Some("GCP_PROJECT")),
+      PermissionState::Denied
+    );
+    assert_eq!(perms.env.query(Some("GCP_IAM_*")), PermissionState::Denied);
+  }
+
   #[test]
   fn test_deserialize_child_permissions_arg() {
     set_prompter(Box::new(TestPrompter));
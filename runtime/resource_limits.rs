@@ -0,0 +1,252 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::error::generic_error;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde::Deserialize;
+use deno_core::v8;
+use deno_core::JsRuntime;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How often the watchdog thread checks the CPU and wall-clock budgets.
+const WATCHDOG_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Limits on the resources a single isolate (either the main worker or a web
+/// worker) is allowed to use. Once any of the limits is exceeded, execution
+/// of the isolate is terminated.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLimits {
+  /// Maximum size of the V8 heap, in megabytes.
+  pub max_heap_size_mb: Option<usize>,
+  /// Maximum CPU time the isolate's thread is allowed to consume, in
+  /// milliseconds.
+  pub max_cpu_time_ms: Option<u64>,
+  /// Maximum time the isolate is allowed to run for, in milliseconds.
+  pub max_wall_time_ms: Option<u64>,
+  /// Maximum number of resources that can be open at the same time.
+  pub max_resources: Option<usize>,
+}
+
+impl ResourceLimits {
+  pub fn is_empty(&self) -> bool {
+    self == &Self::default()
+  }
+
+  pub fn validate(&self) -> Result<(), AnyError> {
+    if self.max_heap_size_mb == Some(0) {
+      return Err(type_error("maxHeapSizeMb must be greater than 0"));
+    }
+    if self.max_cpu_time_ms.is_some() && !cpu_time::is_supported() {
+      return Err(type_error("maxCpuTimeMs is not supported on this platform"));
+    }
+    Ok(())
+  }
+
+  /// Returns the isolate parameters that apply the heap limit, if any.
+  pub fn create_params(&self) -> Option<v8::CreateParams> {
+    self.max_heap_size_mb.map(|max_heap_size_mb| {
+      v8::CreateParams::default()
+        .embedder_wrapper_type_info_offsets(
+          deno_core::V8_WRAPPER_TYPE_INDEX,
+          deno_core::V8_WRAPPER_OBJECT_INDEX,
+        )
+        .heap_limits(0, max_heap_size_mb * 1024 * 1024)
+    })
+  }
+}
+
+/// Callback invoked, possibly from another thread, when a limit is exceeded.
+/// It is expected to terminate the isolate's execution.
+pub type OnLimitExceededFn = dyn Fn() + Send + Sync;
+
+/// Enforces [`ResourceLimits`] on a `JsRuntime`. The CPU and wall-clock
+/// budgets are checked from a watchdog thread that lives as long as this
+/// struct; the number of open resources is checked by the owner of the
+/// runtime through [`Self::check_resources_after_poll`].
+pub struct ResourceLimitsEnforcer {
+  max_resources: Option<usize>,
+  exceeded: Arc<Mutex<Option<String>>>,
+  on_exceeded: Arc<OnLimitExceededFn>,
+  stop_watchdog: Arc<AtomicBool>,
+}
+
+impl ResourceLimitsEnforcer {
+  /// Must be called on the thread that runs `js_runtime`, so CPU time is
+  /// attributed to the right thread.
+  pub fn install(
+    limits: &ResourceLimits,
+    js_runtime: &mut JsRuntime,
+    on_exceeded: Arc<OnLimitExceededFn>,
+  ) -> Self {
+    let exceeded = Arc::new(Mutex::new(None));
+    let stop_watchdog = Arc::new(AtomicBool::new(false));
+
+    if let Some(max_heap_size_mb) = limits.max_heap_size_mb {
+      let exceeded = exceeded.clone();
+      let on_exceeded = on_exceeded.clone();
+      js_runtime.add_near_heap_limit_callback(move |current_limit, _| {
+        set_exceeded(
+          &exceeded,
+          &on_exceeded,
+          format!("maxHeapSizeMb ({max_heap_size_mb} MB)"),
+        );
+        // Give V8 some headroom to unwind the terminated execution instead of
+        // crashing the whole process with an OOM error.
+        current_limit * 2
+      });
+    }
+
+    if limits.max_cpu_time_ms.is_some() || limits.max_wall_time_ms.is_some() {
+      let max_cpu_time = limits.max_cpu_time_ms.map(Duration::from_millis);
+      let max_wall_time = limits.max_wall_time_ms.map(Duration::from_millis);
+      let cpu_clock = max_cpu_time.and_then(|_| cpu_time::current_thread());
+      let exceeded = exceeded.clone();
+      let on_exceeded = on_exceeded.clone();
+      let stop_watchdog = stop_watchdog.clone();
+      let start = Instant::now();
+      thread::spawn(move || {
+        while !stop_watchdog.load(Ordering::Relaxed) {
+          thread::sleep(WATCHDOG_INTERVAL);
+          let reason = if let Some(max) =
+            max_wall_time.filter(|max| start.elapsed() > *max)
+          {
+            format!("maxWallTimeMs ({} ms)", max.as_millis())
+          } else if let Some(max) = max_cpu_time.filter(|max| {
+            cpu_clock
+              .as_ref()
+              .map_or(false, |clock| clock.elapsed() > *max)
+          }) {
+            format!("maxCpuTimeMs ({} ms)", max.as_millis())
+          } else {
+            continue;
+          };
+          set_exceeded(&exceeded, &on_exceeded, reason);
+          break;
+        }
+      });
+    }
+
+    Self {
+      max_resources: limits.max_resources,
+      exceeded,
+      on_exceeded,
+      stop_watchdog,
+    }
+  }
+
+  /// Terminates the runtime if it holds more open resources than allowed.
+  ///
+  /// Opening a resource can't fail because of the limit, so this is meant to
+  /// be called after every turn of the event loop: a runtime may briefly hold
+  /// more resources than allowed, e.g. when it opens them synchronously, but
+  /// it is terminated before its next turn.
+  pub fn check_resources_after_poll(&self, js_runtime: &mut JsRuntime) {
+    if let Some(max_resources) = self.max_resources {
+      let op_state = js_runtime.op_state();
+      let count = op_state.borrow().resource_table.len();
+      if count > max_resources {
+        set_exceeded(
+          &self.exceeded,
+          &self.on_exceeded,
+          format!("maxResources ({max_resources})"),
+        );
+      }
+    }
+  }
+
+  /// Returns an error describing the exceeded limit, if execution was
+  /// terminated because of one.
+  pub fn error(&self) -> Option<AnyError> {
+    self.exceeded.lock().as_ref().map(|limit| {
+      generic_error(format!("Execution terminated: exceeded {limit}"))
+    })
+  }
+}
+
+impl Drop for ResourceLimitsEnforcer {
+  fn drop(&mut self) {
+    self.stop_watchdog.store(true, Ordering::Relaxed);
+  }
+}
+
+fn set_exceeded(
+  exceeded: &Mutex<Option<String>>,
+  on_exceeded: &Arc<OnLimitExceededFn>,
+  reason: String,
+) {
+  let mut exceeded = exceeded.lock();
+  if exceeded.is_none() {
+    *exceeded = Some(reason);
+    drop(exceeded);
+    on_exceeded();
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod cpu_time {
+  use std::time::Duration;
+
+  /// The CPU-time clock of a thread, readable from any other thread.
+  pub struct ThreadCpuClock(libc::clockid_t);
+
+  impl ThreadCpuClock {
+    pub fn elapsed(&self) -> Duration {
+      let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+      };
+      // SAFETY: `ts` is a valid timespec and the clock id was obtained from
+      // `pthread_getcpuclockid`.
+      let ret = unsafe { libc::clock_gettime(self.0, &mut ts) };
+      if ret != 0 {
+        return Duration::ZERO;
+      }
+      Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+    }
+  }
+
+  pub fn is_supported() -> bool {
+    true
+  }
+
+  pub fn current_thread() -> Option<ThreadCpuClock> {
+    let mut clock_id: libc::clockid_t = 0;
+    // SAFETY: `pthread_self` is always valid for the calling thread.
+    let ret = unsafe {
+      libc::pthread_getcpuclockid(libc::pthread_self(), &mut clock_id)
+    };
+    if ret == 0 {
+      Some(ThreadCpuClock(clock_id))
+    } else {
+      None
+    }
+  }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod cpu_time {
+  use std::time::Duration;
+
+  pub struct ThreadCpuClock;
+
+  impl ThreadCpuClock {
+    pub fn elapsed(&self) -> Duration {
+      Duration::ZERO
+    }
+  }
+
+  pub fn is_supported() -> bool {
+    false
+  }
+
+  pub fn current_thread() -> Option<ThreadCpuClock> {
+    None
+  }
+}
@@ -5,6 +5,8 @@ use crate::js;
 use crate::ops;
 use crate::ops::io::Stdio;
 use crate::permissions::PermissionsContainer;
+use crate::resource_limits::ResourceLimits;
+use crate::resource_limits::ResourceLimitsEnforcer;
 use crate::tokio_util::run_local;
 use crate::worker::FormatJsErrorFn;
 use crate::BootstrapOptions;
@@ -319,6 +321,7 @@ pub struct WebWorker {
   pub worker_type: WebWorkerType,
   pub main_module: ModuleSpecifier,
   poll_for_messages_fn: Option<v8::Global<v8::Value>>,
+  resource_limits: Option<ResourceLimitsEnforcer>,
 }
 
 pub struct WebWorkerOptions {
@@ -344,6 +347,7 @@ pub struct WebWorkerOptions {
   pub compiled_wasm_module_store: Option<CompiledWasmModuleStore>,
   pub cache_storage_dir: Option<std::path::PathBuf>,
   pub stdio: Stdio,
+  pub resource_limits: ResourceLimits,
 }
 
 impl WebWorker {
@@ -464,6 +468,7 @@ impl WebWorker {
       get_error_class_fn: options.get_error_class_fn,
       shared_array_buffer_store: options.shared_array_buffer_store.clone(),
       compiled_wasm_module_store: options.compiled_wasm_module_store.clone(),
+      create_params: options.resource_limits.create_params(),
       extensions,
       inspector: options.maybe_inspector_server.is_some(),
       ..Default::default()
@@ -493,6 +498,26 @@ impl WebWorker {
       (internal_handle, external_handle)
     };
 
+    let resource_limits = if options.resource_limits.is_empty() {
+      None
+    } else {
+      let has_terminated = internal_handle.has_terminated.clone();
+      let terminate_waker = internal_handle.terminate_waker.clone();
+      let isolate_handle = internal_handle.isolate_handle.clone();
+      Some(ResourceLimitsEnforcer::install(
+        &options.resource_limits,
+        &mut js_runtime,
+        Arc::new(move || {
+          // Unlike `WebWorkerInternalHandle::terminate()`, the control channel
+          // is left open so the exceeded limit can be reported to the host.
+          if !has_terminated.swap(true, Ordering::SeqCst) {
+            isolate_handle.terminate_execution();
+          }
+          terminate_waker.wake();
+        }),
+      ))
+    };
+
     (
       Self {
         id: worker_id,
@@ -502,6 +527,7 @@ impl WebWorker {
         worker_type: options.worker_type,
         main_module,
         poll_for_messages_fn: None,
+        resource_limits,
       },
       external_handle,
     )
@@ -633,7 +659,12 @@ impl WebWorker {
 
     self.internal_handle.terminate_waker.register(cx.waker());
 
-    match self.js_runtime.poll_event_loop(cx, wait_for_inspector) {
+    let poll = self.js_runtime.poll_event_loop(cx, wait_for_inspector);
+    if let Some(resource_limits) = &self.resource_limits {
+      resource_limits.check_resources_after_poll(&mut self.js_runtime);
+    }
+
+    match poll {
       Poll::Ready(r) => {
         // If js ended because we are terminating, just return Ok
         if self.internal_handle.terminate_if_needed() {
@@ -659,6 +690,15 @@ impl WebWorker {
     poll_fn(|cx| self.poll_event_loop(cx, wait_for_inspector)).await
   }
 
+  /// Returns an error describing the resource limit that caused this worker
+  /// to be terminated, if any.
+  pub fn resource_limit_error(&self) -> Option<AnyError> {
+    self
+      .resource_limits
+      .as_ref()
+      .and_then(|limits| limits.error())
+  }
+
   // Starts polling for messages from worker host from JavaScript.
   fn start_polling_for_messages(&mut self) {
     let poll_for_messages_fn = self.poll_for_messages_fn.take().unwrap();
@@ -753,14 +793,24 @@ pub fn run_web_worker(
       }
     };
 
+    // Exceeding a resource limit terminates the worker, which is reported to
+    // the host as a terminal error.
+    let limit_error = worker.resource_limit_error();
+
     // If sender is closed it means that worker has already been closed from
     // within using "globalThis.close()"
-    if internal_handle.is_terminated() {
+    if internal_handle.is_terminated() && limit_error.is_none() {
       return Ok(());
     }
 
-    let result = if result.is_ok() {
-      worker.run_event_loop(true).await
+    let result = if let Some(e) = limit_error {
+      Err(e)
+    } else if result.is_ok() {
+      let result = worker.run_event_loop(true).await;
+      match worker.resource_limit_error() {
+        Some(e) => Err(e),
+        None => result,
+      }
     } else {
       result
     };
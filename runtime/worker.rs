@@ -13,6 +13,8 @@ use deno_cache::CreateCache;
 use deno_cache::SqliteBackedCache;
 use deno_core::error::AnyError;
 use deno_core::error::JsError;
+use deno_core::futures::future::poll_fn;
+use deno_core::futures::task::AtomicWaker;
 use deno_core::futures::Future;
 use deno_core::located_script_name;
 use deno_core::v8;
@@ -39,6 +41,8 @@ use crate::js;
 use crate::ops;
 use crate::ops::io::Stdio;
 use crate::permissions::PermissionsContainer;
+use crate::resource_limits::ResourceLimits;
+use crate::resource_limits::ResourceLimitsEnforcer;
 use crate::BootstrapOptions;
 
 pub type FormatJsErrorFn = dyn Fn(&JsError) -> String + Sync + Send;
@@ -67,6 +71,8 @@ pub struct MainWorker {
   should_break_on_first_statement: bool,
   should_wait_for_inspector_session: bool,
   exit_code: ExitCode,
+  resource_limits: Option<ResourceLimitsEnforcer>,
+  resource_limit_waker: Arc<AtomicWaker>,
 }
 
 pub struct WorkerOptions {
@@ -142,6 +148,11 @@ pub struct WorkerOptions {
   /// `WebAssembly.Module` objects cannot be serialized.
   pub compiled_wasm_module_store: Option<CompiledWasmModuleStore>,
   pub stdio: Stdio,
+
+  /// Limits on the heap size, CPU time, wall-clock time and number of open
+  /// resources of the main isolate. Execution is terminated once any of them
+  /// is exceeded.
+  pub resource_limits: ResourceLimits,
 }
 
 impl Default for WorkerOptions {
@@ -178,6 +189,7 @@ impl Default for WorkerOptions {
       startup_snapshot: Default::default(),
       bootstrap: Default::default(),
       stdio: Default::default(),
+      resource_limits: Default::default(),
     }
   }
 }
@@ -293,6 +305,7 @@ impl MainWorker {
       get_error_class_fn: options.get_error_class_fn,
       shared_array_buffer_store: options.shared_array_buffer_store.clone(),
       compiled_wasm_module_store: options.compiled_wasm_module_store.clone(),
+      create_params: options.resource_limits.create_params(),
       extensions,
       extensions_with_js: options.extensions_with_js,
       inspector: options.maybe_inspector_server.is_some(),
@@ -300,6 +313,24 @@ impl MainWorker {
       ..Default::default()
     });
 
+    let resource_limit_waker = Arc::new(AtomicWaker::new());
+    let resource_limits = if options.resource_limits.is_empty() {
+      None
+    } else {
+      let isolate_handle = js_runtime.v8_isolate().thread_safe_handle();
+      let waker = resource_limit_waker.clone();
+      Some(ResourceLimitsEnforcer::install(
+        &options.resource_limits,
+        &mut js_runtime,
+        Arc::new(move || {
+          isolate_handle.terminate_execution();
+          // The event loop may be idle, e.g. waiting on a timer, so wake it
+          // up to report the error.
+          waker.wake();
+        }),
+      ))
+    };
+
     if let Some(server) = options.maybe_inspector_server.clone() {
       server.register_inspector(
         main_module.to_string(),
@@ -321,6 +352,8 @@ impl MainWorker {
       should_wait_for_inspector_session: options
         .should_wait_for_inspector_session,
       exit_code,
+      resource_limits,
+      resource_limit_waker,
     }
   }
 
@@ -369,7 +402,7 @@ impl MainWorker {
   ) -> Result<(), AnyError> {
     self.wait_for_inspector_session();
     let mut receiver = self.js_runtime.mod_evaluate(id);
-    tokio::select! {
+    let result = tokio::select! {
       // Not using biased mode leads to non-determinism for relatively simple
       // programs.
       biased;
@@ -384,7 +417,8 @@ impl MainWorker {
         let maybe_result = receiver.await;
         maybe_result.expect("Module evaluation result not provided.")
       }
-    }
+    };
+    self.map_resource_limit_error(result)
   }
 
   /// Loads, instantiates and executes specified JavaScript module.
@@ -431,14 +465,38 @@ impl MainWorker {
     cx: &mut Context,
     wait_for_inspector: bool,
   ) -> Poll<Result<(), AnyError>> {
-    self.js_runtime.poll_event_loop(cx, wait_for_inspector)
+    self.resource_limit_waker.register(cx.waker());
+    let poll = self.js_runtime.poll_event_loop(cx, wait_for_inspector);
+    if let Some(resource_limits) = &self.resource_limits {
+      resource_limits.check_resources_after_poll(&mut self.js_runtime);
+      // Execution is terminated, so the event loop might never make progress
+      // again; report the exceeded limit even if the poll is pending.
+      if let Some(err) = resource_limits.error() {
+        return Poll::Ready(Err(err));
+      }
+    }
+    poll
   }
 
   pub async fn run_event_loop(
     &mut self,
     wait_for_inspector: bool,
   ) -> Result<(), AnyError> {
-    self.js_runtime.run_event_loop(wait_for_inspector).await
+    poll_fn(|cx| self.poll_event_loop(cx, wait_for_inspector)).await
+  }
+
+  /// If execution was terminated because a resource limit was exceeded,
+  /// replaces the resulting error with one describing the exceeded limit.
+  fn map_resource_limit_error<T>(
+    &self,
+    result: Result<T, AnyError>,
+  ) -> Result<T, AnyError> {
+    match (result, &self.resource_limits) {
+      (Err(err), Some(resource_limits)) => {
+        Err(resource_limits.error().unwrap_or(err))
+      }
+      (result, _) => result,
+    }
   }
 
   /// A utility function that runs provided future concurrently with the event loop.